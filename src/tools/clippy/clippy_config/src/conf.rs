@@ -1,5 +1,7 @@
 use crate::msrvs::Msrv;
-use crate::types::{DisallowedPath, MacroMatcher, MatchLintBehaviour, PubUnderscoreFieldsBehaviour, Rename};
+use crate::types::{
+    DisallowedPath, DocCommentStyle, MacroMatcher, MatchLintBehaviour, PubUnderscoreFieldsBehaviour, Rename,
+};
 use crate::ClippyConfiguration;
 use rustc_data_structures::fx::FxHashSet;
 use rustc_errors::Applicability;
@@ -38,6 +40,7 @@
     "CamelCase",
 ];
 const DEFAULT_DISALLOWED_NAMES: &[&str] = &["foo", "baz", "quux"];
+const DEFAULT_PLACEHOLDER_MARKERS: &[&str] = &["TODO", "FIXME", "XXX", "WIP"];
 const DEFAULT_ALLOWED_IDENTS_BELOW_MIN_CHARS: &[&str] = &["i", "j", "x", "y", "z", "w", "n"];
 
 /// Conf with parse errors
@@ -558,10 +561,51 @@ pub fn get_configuration_metadata() -> Vec<ClippyConfiguration> {
     /// for _ in &mut *rmvec {}
     /// ```
     (enforce_iter_loop_reborrow: bool = false),
-    /// Lint: MISSING_SAFETY_DOC, UNNECESSARY_SAFETY_DOC, MISSING_PANICS_DOC, MISSING_ERRORS_DOC.
+    /// Lint: MISSING_SAFETY_DOC, UNNECESSARY_SAFETY_DOC, MISSING_PANICS_DOC, MISSING_ERRORS_DOC, DOC_MISSING_SUMMARY_BREAK.
     ///
     /// Whether to also run the listed lints on private items.
     (check_private_items: bool = false),
+    /// Lint: MISSING_PANICS_DOC.
+    ///
+    /// Whether to ignore panics that can never be reached according to the MIR of the function,
+    /// such as a `panic!` in a match arm whose pattern is already covered by the preceding arms.
+    (ignore_unreachable_panics: bool = false),
+    /// Lint: MISSING_PANICS_DOC.
+    ///
+    /// Whether to ignore panics that can only happen with debug assertions enabled, like the ones
+    /// of `debug_assert!` or in code under `#[cfg(debug_assertions)]`.
+    (ignore_debug_only_panics: bool = true),
+    /// Lint: DOC_COMMENT_STYLE.
+    ///
+    /// The style of doc comments to enforce, either `Line` (`///` and `//!`) or `Block` (`/** */`
+    /// and `/*! */`).
+    (doc_comment_style: DocCommentStyle = DocCommentStyle::Line),
+    /// Lint: DOC_MARKDOWN.
+    ///
+    /// Whether to also check the words in `#[doc = "..."]` attributes, not only those in doc
+    /// comments.
+    (check_doc_attributes: bool = false),
+    /// Lint: DOC_MARKDOWN.
+    ///
+    /// Whether to also lint Rust keywords, like `async()` or `crate::`. These mostly refer to the
+    /// concept rather than to code, so they are not linted by default.
+    (doc_tick_keywords: bool = false),
+    /// Lint: MISSING_RETURNS_NONE_DOC.
+    ///
+    /// The heading of the section describing when a function returns `None`.
+    (returns_none_doc_heading: String = String::from("Returns")),
+    /// Lint: PLACEHOLDER_DOC.
+    ///
+    /// The placeholder markers a doc comment must not only consist of. Case is ignored.
+    (doc_placeholder_markers: Vec<String> = DEFAULT_PLACEHOLDER_MARKERS.iter().map(ToString::to_string).collect()),
+    /// Lint: MISSING_REQUIRED_DOC_SECTION.
+    ///
+    /// The headings of the sections the docs of every public function must have, like `Complexity`.
+    (required_doc_sections: Vec<String> = Vec::new()),
+    /// Lint: DOC_EXAMPLE_STD_IN_NO_STD.
+    ///
+    /// Whether to also check the examples marked `ignore` or `no_run`, which rustdoc doesn't run.
+    (check_not_run_examples: bool = false),
     /// Lint: PUB_UNDERSCORE_FIELDS.
     ///
     /// Lint "public" fields in a struct that are prefixed with an underscore based on their
@@ -127,6 +127,12 @@ fn serialize<S>(&self, _serializer: S) -> Result<S::Ok, S::Error>
     MacroMatcher,
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub enum DocCommentStyle {
+    Line,
+    Block,
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
 pub enum PubUnderscoreFieldsBehaviour {
     PubliclyExported,
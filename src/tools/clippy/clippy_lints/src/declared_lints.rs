@@ -133,15 +133,49 @@
     crate::disallowed_names::DISALLOWED_NAMES_INFO,
     crate::disallowed_script_idents::DISALLOWED_SCRIPT_IDENTS_INFO,
     crate::disallowed_types::DISALLOWED_TYPES_INFO,
+    crate::doc::DOC_CODE_BEFORE_SUMMARY_INFO,
+    crate::doc::DOC_COMMENT_STYLE_INFO,
+    crate::doc::DOC_DUPLICATE_SECTION_INFO,
+    crate::doc::DOC_EXAMPLE_ASSERT_ORDER_INFO,
+    crate::doc::DOC_EXAMPLE_DEPRECATED_INFO,
+    crate::doc::DOC_EXAMPLE_NO_ASSERTION_INFO,
+    crate::doc::DOC_EXAMPLE_STATIC_MUT_INFO,
+    crate::doc::DOC_EXAMPLE_STD_IN_NO_STD_INFO,
+    crate::doc::DOC_EXAMPLE_STDERR_INFO,
+    crate::doc::DOC_EXAMPLE_UNEXPLAINED_UNSAFE_INFO,
+    crate::doc::DOC_EXAMPLE_UNSPECIFIED_EDITION_INFO,
+    crate::doc::DOC_EXAMPLE_WRONG_CRATE_NAME_INFO,
+    crate::doc::DOC_HEADING_JUMP_INFO,
+    crate::doc::DOC_HIDDEN_LINE_ERROR_INFO,
+    crate::doc::DOC_INVALID_ALIAS_INFO,
+    crate::doc::DOC_IRRELEVANT_SELF_INFO,
     crate::doc::DOC_LINK_WITH_QUOTES_INFO,
+    crate::doc::DOC_LITERAL_NEWLINE_ESCAPE_INFO,
+    crate::doc::DOC_MALFORMED_MARKDOWN_INFO,
+    crate::doc::DOC_MALFORMED_TABLE_INFO,
     crate::doc::DOC_MARKDOWN_INFO,
+    crate::doc::DOC_MISSING_SUMMARY_BREAK_INFO,
+    crate::doc::DOC_MISTAGGED_RUST_INFO,
+    crate::doc::DOC_NESTED_FENCE_INFO,
+    crate::doc::DOC_NONEXISTENT_PARAM_INFO,
+    crate::doc::DOC_NONSENSE_BACKTICKS_INFO,
+    crate::doc::DOC_RUST_IN_TEXT_BLOCK_INFO,
+    crate::doc::DOC_UNBALANCED_BACKTICKS_INFO,
+    crate::doc::DOC_UNKNOWN_PARAM_INFO,
+    crate::doc::EMPTY_DOC_COMMENT_INFO,
     crate::doc::MISSING_ERRORS_DOC_INFO,
+    crate::doc::MISSING_EXAMPLES_DOC_INFO,
     crate::doc::MISSING_PANICS_DOC_INFO,
+    crate::doc::MISSING_REQUIRED_DOC_SECTION_INFO,
+    crate::doc::MISSING_RETURNS_NONE_DOC_INFO,
     crate::doc::MISSING_SAFETY_DOC_INFO,
     crate::doc::NEEDLESS_DOCTEST_MAIN_INFO,
+    crate::doc::PLACEHOLDER_DOC_INFO,
     crate::doc::SUSPICIOUS_DOC_COMMENTS_INFO,
+    crate::doc::SUSPICIOUS_ERRORS_DOC_INFO,
     crate::doc::TEST_ATTR_IN_DOCTEST_INFO,
     crate::doc::UNNECESSARY_SAFETY_DOC_INFO,
+    crate::doc::UNNECESSARY_UNSAFE_MARKER_INFO,
     crate::double_parens::DOUBLE_PARENS_INFO,
     crate::drop_forget_ref::DROP_NON_DROP_INFO,
     crate::drop_forget_ref::FORGET_NON_DROP_INFO,
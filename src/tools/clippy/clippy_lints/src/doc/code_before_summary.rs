@@ -0,0 +1,25 @@
+use std::ops::Range;
+
+use clippy_utils::diagnostics::span_lint_and_help;
+use rustc_lint::LateContext;
+
+use super::{Fragments, DOC_CODE_BEFORE_SUMMARY};
+
+/// Checks whether the code block spanning `range` of the markdown is the first thing in the
+/// documentation.
+pub fn check(cx: &LateContext<'_>, range: Range<usize>, fragments: Fragments<'_>) {
+    let block = &fragments.doc[range.clone()];
+    let first_line_len = block.find('\n').unwrap_or(block.len());
+    if fragments.doc[..range.start].trim().is_empty()
+        && let Some(span) = fragments.span(cx, range.start..range.start + first_line_len)
+    {
+        span_lint_and_help(
+            cx,
+            DOC_CODE_BEFORE_SUMMARY,
+            span,
+            "documentation starts with a code block instead of a summary",
+            None,
+            "add a sentence summarizing the item before the code block",
+        );
+    }
+}
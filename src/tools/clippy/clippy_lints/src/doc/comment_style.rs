@@ -0,0 +1,38 @@
+use clippy_config::types::DocCommentStyle;
+use clippy_utils::diagnostics::span_lint_and_help;
+use rustc_ast::token::CommentKind;
+use rustc_ast::{AttrKind, AttrStyle, Attribute};
+use rustc_lint::LateContext;
+
+use super::DOC_COMMENT_STYLE;
+
+pub fn check(cx: &LateContext<'_>, attrs: &[Attribute], preferred: DocCommentStyle) {
+    let (preferred_kind, found) = match preferred {
+        DocCommentStyle::Line => (CommentKind::Line, "block"),
+        DocCommentStyle::Block => (CommentKind::Block, "line"),
+    };
+    let mut offending = attrs.iter().filter_map(|attr| match attr.kind {
+        AttrKind::DocComment(kind, _) if kind != preferred_kind && !attr.span.from_expansion() => {
+            Some((attr.span, attr.style))
+        },
+        _ => None,
+    });
+
+    if let Some((lo, style)) = offending.next() {
+        let span = offending.last().map_or(lo, |(hi, _)| lo.to(hi));
+        let replacement = match (preferred, style) {
+            (DocCommentStyle::Line, AttrStyle::Outer) => "`///`",
+            (DocCommentStyle::Line, AttrStyle::Inner) => "`//!`",
+            (DocCommentStyle::Block, AttrStyle::Outer) => "`/** */`",
+            (DocCommentStyle::Block, AttrStyle::Inner) => "`/*! */`",
+        };
+        span_lint_and_help(
+            cx,
+            DOC_COMMENT_STYLE,
+            span,
+            &format!("this documentation uses {found} doc comments"),
+            None,
+            &format!("use {replacement} doc comments instead"),
+        );
+    }
+}
@@ -0,0 +1,32 @@
+use std::ops::Range;
+
+use clippy_utils::diagnostics::span_lint_and_help;
+use rustc_data_structures::fx::FxHashSet;
+use rustc_lint::LateContext;
+
+use super::{Fragments, DOC_DUPLICATE_SECTION};
+
+/// Checks the heading of the section `name` spanning `range` of the markdown against the sections
+/// in `seen`, and adds it to them.
+pub fn check(
+    cx: &LateContext<'_>,
+    seen: &mut FxHashSet<String>,
+    name: String,
+    range: Range<usize>,
+    fragments: Fragments<'_>,
+) {
+    let heading = fragments.doc[range.clone()].trim_end();
+    if seen.contains(&name)
+        && let Some(span) = fragments.span(cx, range.start..range.start + heading.len())
+    {
+        span_lint_and_help(
+            cx,
+            DOC_DUPLICATE_SECTION,
+            span,
+            &format!("the documentation already has a `{name}` section"),
+            None,
+            "merge the two sections",
+        );
+    }
+    seen.insert(name);
+}
@@ -0,0 +1,23 @@
+use clippy_utils::diagnostics::span_lint_and_help;
+use rustc_lint::LateContext;
+use rustc_resolve::rustdoc::DocFragment;
+
+use super::EMPTY_DOC_COMMENT;
+
+/// Checks whether the doc comments of an item are present, but `doc`, their combined text, is
+/// blank.
+pub fn check(cx: &LateContext<'_>, fragments: &[DocFragment], doc: &str) {
+    if let (Some(first), Some(last)) = (fragments.first(), fragments.last())
+        && doc.trim().is_empty()
+        && !first.span.from_expansion()
+    {
+        span_lint_and_help(
+            cx,
+            EMPTY_DOC_COMMENT,
+            first.span.to(last.span),
+            "empty doc comment",
+            None,
+            "remove the doc comment, or document the item",
+        );
+    }
+}
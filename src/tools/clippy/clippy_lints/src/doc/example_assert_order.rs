@@ -0,0 +1,86 @@
+use std::ops::Range;
+
+use clippy_utils::diagnostics::span_lint_and_sugg;
+use rustc_ast::token::TokenKind;
+use rustc_ast::tokenstream::{TokenStream, TokenTree};
+use rustc_ast::{Expr, ExprKind, UnOp};
+use rustc_errors::{Applicability, DiagnosticBuilder};
+use rustc_lint::LateContext;
+use rustc_parse::parser::Parser;
+use rustc_session::parse::ParseSess;
+use rustc_span::{Pos, Span};
+
+use super::{Fragments, DOC_EXAMPLE_ASSERT_ORDER};
+
+/// An `assert_eq!` or `assert_ne!` whose first argument is a literal and whose second isn't, with
+/// the byte position ranges of both arguments.
+pub(super) struct SwappedAssert {
+    expected: Range<usize>,
+    actual: Range<usize>,
+}
+
+fn is_literal(expr: &Expr) -> bool {
+    match &expr.kind {
+        ExprKind::Lit(_) => true,
+        ExprKind::Unary(UnOp::Neg, inner) => matches!(inner.kind, ExprKind::Lit(_)),
+        _ => false,
+    }
+}
+
+/// Finds the `assert_eq!` and `assert_ne!` invocations in `tts`, including nested ones. Their
+/// arguments are parsed separately since the example as a whole needn't consist of items only.
+pub(super) fn find(sess: &ParseSess, tts: &TokenStream, swapped: &mut Vec<SwappedAssert>) {
+    let span_range = |span: Span| {
+        let sm = sess.source_map();
+        sm.lookup_byte_offset(span.lo()).pos.to_usize()..sm.lookup_byte_offset(span.hi()).pos.to_usize()
+    };
+    let mut trees = tts.trees();
+    while let Some(tree) = trees.next() {
+        match tree {
+            TokenTree::Token(token, _) => {
+                if let Some((ident, false)) = token.ident()
+                    && matches!(ident.as_str(), "assert_eq" | "assert_ne")
+                    && let Some(TokenTree::Token(bang, _)) = trees.look_ahead(0)
+                    && bang.kind == TokenKind::Not
+                    && let Some(TokenTree::Delimited(.., args)) = trees.look_ahead(1)
+                {
+                    let mut parser = Parser::new(sess, args.clone(), None);
+                    if let Ok(first) = parser.parse_expr().map_err(DiagnosticBuilder::cancel)
+                        && parser.eat(&TokenKind::Comma)
+                        && let Ok(second) = parser.parse_expr().map_err(DiagnosticBuilder::cancel)
+                        && is_literal(&first)
+                        && !is_literal(&second)
+                    {
+                        swapped.push(SwappedAssert {
+                            expected: span_range(first.span),
+                            actual: span_range(second.span),
+                        });
+                    }
+                }
+            },
+            TokenTree::Delimited(.., inner) => find(sess, inner, swapped),
+        }
+    }
+}
+
+pub fn check(
+    cx: &LateContext<'_>,
+    text: &str,
+    swapped_asserts: &[SwappedAssert],
+    range: Range<usize>,
+    fragments: Fragments<'_>,
+) {
+    for SwappedAssert { expected, actual } in swapped_asserts {
+        if let Some(span) = fragments.span(cx, (range.start + expected.start)..(range.start + actual.end)) {
+            span_lint_and_sugg(
+                cx,
+                DOC_EXAMPLE_ASSERT_ORDER,
+                span,
+                "the expected value is passed before the value under test",
+                "swap the arguments",
+                format!("{}, {}", &text[actual.clone()], &text[expected.clone()]),
+                Applicability::MaybeIncorrect,
+            );
+        }
+    }
+}
@@ -0,0 +1,90 @@
+use std::ops::Range;
+
+use clippy_utils::def_path_def_ids;
+use clippy_utils::diagnostics::span_lint_and_help;
+use rustc_ast::token::TokenKind;
+use rustc_ast::tokenstream::{TokenStream, TokenTree};
+use rustc_lint::LateContext;
+use rustc_session::parse::ParseSess;
+use rustc_span::Pos;
+
+use super::{Fragments, DOC_EXAMPLE_DEPRECATED};
+
+/// A path in an example starting with the name of the documented crate.
+pub(super) struct CratePath {
+    /// The segments of the path, starting with the crate name.
+    segments: Vec<String>,
+    /// The byte position range of the path.
+    range: Range<usize>,
+}
+
+/// Finds the paths in `tts` starting with `crate_name`, including nested ones. A path ends before
+/// anything that is not a `::` followed by an identifier, like the `{..}` group of a `use` item.
+pub(super) fn find(sess: &ParseSess, tts: &TokenStream, crate_name: &str, paths: &mut Vec<CratePath>) {
+    let mut after_path_sep = false;
+    let mut trees = tts.trees();
+    while let Some(tree) = trees.next() {
+        match tree {
+            TokenTree::Token(token, _) => {
+                if !after_path_sep
+                    && let Some((ident, false)) = token.ident()
+                    && ident.as_str() == crate_name
+                {
+                    let mut segments = vec![ident.to_string()];
+                    let mut hi = ident.span.hi();
+                    while let Some(TokenTree::Token(sep, _)) = trees.look_ahead(0)
+                        && sep.kind == TokenKind::ModSep
+                        && let Some(TokenTree::Token(next, _)) = trees.look_ahead(1)
+                        && let Some((segment, _)) = next.ident()
+                    {
+                        segments.push(segment.to_string());
+                        hi = segment.span.hi();
+                        trees.next();
+                        trees.next();
+                    }
+                    if segments.len() > 1 {
+                        let sm = sess.source_map();
+                        paths.push(CratePath {
+                            segments,
+                            range: sm.lookup_byte_offset(ident.span.lo()).pos.to_usize()
+                                ..sm.lookup_byte_offset(hi).pos.to_usize(),
+                        });
+                    }
+                    after_path_sep = false;
+                } else {
+                    // A name after `::` or `.` is not the crate.
+                    after_path_sep = matches!(token.kind, TokenKind::ModSep | TokenKind::Dot);
+                }
+            },
+            TokenTree::Delimited(.., inner) => {
+                after_path_sep = false;
+                find(sess, inner, crate_name, paths);
+            },
+        }
+    }
+}
+
+pub fn check(cx: &LateContext<'_>, paths: &[CratePath], range: Range<usize>, fragments: Fragments<'_>) {
+    for path in paths {
+        // The path is resolved against the items of the crate and the inherent impls of its types,
+        // ignoring re-exports. Only paths all resolutions of which are deprecated are linted, as
+        // the example may declare items of the same name.
+        let segments = path.segments.iter().map(String::as_str).collect::<Vec<_>>();
+        let def_ids = def_path_def_ids(cx, &segments).collect::<Vec<_>>();
+        if !def_ids.is_empty()
+            && def_ids
+                .iter()
+                .all(|&def_id| def_id.is_local() && cx.tcx.lookup_deprecation(def_id).is_some())
+            && let Some(span) = fragments.span(cx, (range.start + path.range.start)..(range.start + path.range.end))
+        {
+            span_lint_and_help(
+                cx,
+                DOC_EXAMPLE_DEPRECATED,
+                span,
+                "this example uses a deprecated item of the crate",
+                None,
+                "show how to use the item replacing it instead",
+            );
+        }
+    }
+}
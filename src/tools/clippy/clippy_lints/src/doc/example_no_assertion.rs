@@ -0,0 +1,168 @@
+use std::io;
+use std::ops::Range;
+
+use clippy_utils::diagnostics::span_lint_and_help;
+use rustc_ast::token::TokenKind;
+use rustc_ast::tokenstream::{TokenStream, TokenTree};
+use rustc_ast::{Block, Expr, ExprKind, Fn, ItemKind, Local, PatKind, StmtKind};
+use rustc_data_structures::sync::Lrc;
+use rustc_errors::emitter::HumanEmitter;
+use rustc_errors::{DiagCtxt, DiagnosticBuilder};
+use rustc_lint::LateContext;
+use rustc_parse::maybe_new_parser_from_source_str;
+use rustc_parse::parser::ForceCollect;
+use rustc_session::parse::ParseSess;
+use rustc_span::source_map::{FilePathMapping, SourceMap};
+use rustc_span::{sym, FileName, Pos};
+
+use super::{Fragments, DOC_EXAMPLE_NO_ASSERTION};
+
+const WRAPPER_START: &str = "fn main() {\n";
+
+fn contains_assertion(tts: &TokenStream) -> bool {
+    let mut trees = tts.trees();
+    while let Some(tree) = trees.next() {
+        match tree {
+            TokenTree::Token(token, _) => {
+                if let Some((ident, false)) = token.ident()
+                    && matches!(
+                        ident.as_str(),
+                        "assert" | "assert_eq" | "assert_ne" | "debug_assert" | "debug_assert_eq" | "debug_assert_ne"
+                    )
+                    && let Some(TokenTree::Token(bang, _)) = trees.look_ahead(0)
+                    && bang.kind == TokenKind::Not
+                {
+                    return true;
+                }
+            },
+            TokenTree::Delimited(.., inner) => {
+                if contains_assertion(inner) {
+                    return true;
+                }
+            },
+        }
+    }
+    false
+}
+
+/// Returns whether a function or method of this name most likely creates a value to be used
+/// rather than computes a result worth checking.
+fn is_constructor(name: &str) -> bool {
+    matches!(
+        name,
+        "new" | "default" | "build" | "builder" | "from" | "into" | "clone" | "to_owned" | "to_string"
+    ) || name.starts_with(char::is_uppercase)
+        || name.starts_with("new_")
+        || name.starts_with("with_")
+        || name.starts_with("from_")
+}
+
+fn computes_value(expr: &Expr) -> bool {
+    match &expr.kind {
+        ExprKind::Try(inner) => computes_value(inner),
+        ExprKind::MethodCall(call) if matches!(call.seg.ident.name, sym::unwrap | sym::expect) => {
+            computes_value(&call.receiver)
+        },
+        ExprKind::MethodCall(call) => !is_constructor(call.seg.ident.as_str()),
+        ExprKind::Call(func, _) => {
+            if let ExprKind::Path(_, path) = &func.kind
+                && let Some(segment) = path.segments.last()
+            {
+                !is_constructor(segment.ident.as_str())
+            } else {
+                false
+            }
+        },
+        _ => false,
+    }
+}
+
+/// Returns the first `let` statement of `block`, or of a function declared in it, that binds the
+/// result of a call computing a value.
+fn find_computed_binding(block: &Block) -> Option<&Local> {
+    block.stmts.iter().find_map(|stmt| match &stmt.kind {
+        StmtKind::Local(local)
+            if matches!(local.pat.kind, PatKind::Ident(..)) && local.kind.init().is_some_and(computes_value) =>
+        {
+            Some(&**local)
+        },
+        StmtKind::Item(item) => match &item.kind {
+            ItemKind::Fn(box Fn { body: Some(body), .. }) => find_computed_binding(body),
+            _ => None,
+        },
+        _ => None,
+    })
+}
+
+/// Parses the example the way rustdoc compiles it, that is wrapped in a `fn main`, and returns
+/// the byte position range of the first `let` statement binding a computed value, if the example
+/// contains no assertion. Must be called with the session globals set.
+pub(super) fn find(code: &str) -> Option<Range<usize>> {
+    // Replace the markers of hidden lines with spaces, so the positions in the source are the
+    // positions in the example.
+    let mut source = WRAPPER_START.to_owned();
+    for line in code.split_inclusive('\n') {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("# ") || trimmed.trim_end() == "#" {
+            let indent = line.len() - trimmed.len();
+            source.push_str(&line[..indent]);
+            source.push(' ');
+            source.push_str(&trimmed[1..]);
+        } else {
+            source.push_str(line);
+        }
+    }
+    source.push_str("\n}");
+
+    let fallback_bundle = rustc_errors::fallback_fluent_bundle(rustc_driver::DEFAULT_LOCALE_RESOURCES.to_vec(), false);
+    let emitter = HumanEmitter::new(Box::new(io::sink()), fallback_bundle);
+    let dcx = DiagCtxt::with_emitter(Box::new(emitter)).disable_warnings();
+    #[expect(clippy::arc_with_non_send_sync)] // `Lrc` is expected by with_dcx
+    let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+    let sess = ParseSess::with_dcx(dcx, sm);
+    let filename = FileName::anon_source_code(&source);
+
+    let mut parser = match maybe_new_parser_from_source_str(&sess, filename.clone(), source.clone()) {
+        Ok(parser) => parser,
+        Err(errs) => {
+            errs.into_iter().for_each(DiagnosticBuilder::cancel);
+            return None;
+        },
+    };
+    let item = match parser.parse_item(ForceCollect::No) {
+        Ok(item) => item?,
+        Err(e) => {
+            e.cancel();
+            return None;
+        },
+    };
+    let ItemKind::Fn(box Fn { body: Some(body), .. }) = &item.kind else {
+        return None;
+    };
+    let local = find_computed_binding(body)?;
+
+    let mut parser = maybe_new_parser_from_source_str(&sess, filename, source)
+        .map_err(|errs| errs.into_iter().for_each(DiagnosticBuilder::cancel))
+        .ok()?;
+    if contains_assertion(&parser.parse_tokens()) {
+        return None;
+    }
+
+    let sm = sess.source_map();
+    let lo = sm.lookup_byte_offset(local.span.lo()).pos.to_usize();
+    let hi = sm.lookup_byte_offset(local.span.hi()).pos.to_usize();
+    Some((lo - WRAPPER_START.len())..(hi - WRAPPER_START.len()))
+}
+
+pub fn check(cx: &LateContext<'_>, binding: Range<usize>, range: Range<usize>, fragments: Fragments<'_>) {
+    if let Some(span) = fragments.span(cx, (range.start + binding.start)..(range.start + binding.end)) {
+        span_lint_and_help(
+            cx,
+            DOC_EXAMPLE_NO_ASSERTION,
+            span,
+            "this example computes a value, but never checks it",
+            None,
+            "add an assertion like `assert_eq!` to show the expected result",
+        );
+    }
+}
@@ -0,0 +1,49 @@
+use std::ops::Range;
+
+use clippy_utils::diagnostics::span_lint_and_help;
+use rustc_ast::tokenstream::{TokenStream, TokenTree};
+use rustc_lint::LateContext;
+use rustc_session::parse::ParseSess;
+use rustc_span::symbol::kw;
+use rustc_span::Pos;
+
+use super::{Fragments, DOC_EXAMPLE_STATIC_MUT};
+
+/// Finds the `static mut` items in `tts`, including nested ones, and returns the byte position
+/// ranges from their `static` keyword to their name.
+pub(super) fn find(sess: &ParseSess, tts: &TokenStream, statics: &mut Vec<Range<usize>>) {
+    let mut trees = tts.trees();
+    while let Some(tree) = trees.next() {
+        match tree {
+            TokenTree::Token(token, _) => {
+                // `&'static mut T` is a lifetime followed by `mut`, not the `static` keyword.
+                if token.is_keyword(kw::Static)
+                    && let Some(TokenTree::Token(next, _)) = trees.look_ahead(0)
+                    && next.is_keyword(kw::Mut)
+                    && let Some(TokenTree::Token(name, _)) = trees.look_ahead(1)
+                {
+                    let sm = sess.source_map();
+                    let lo = sm.lookup_byte_offset(token.span.lo()).pos.to_usize();
+                    let hi = sm.lookup_byte_offset(name.span.hi()).pos.to_usize();
+                    statics.push(lo..hi);
+                }
+            },
+            TokenTree::Delimited(.., inner) => find(sess, inner, statics),
+        }
+    }
+}
+
+pub fn check(cx: &LateContext<'_>, statics: &[Range<usize>], range: Range<usize>, fragments: Fragments<'_>) {
+    for item in statics {
+        if let Some(span) = fragments.span(cx, (range.start + item.start)..(range.start + item.end)) {
+            span_lint_and_help(
+                cx,
+                DOC_EXAMPLE_STATIC_MUT,
+                span,
+                "this example declares a `static mut`",
+                None,
+                "use an atomic type or a `Mutex` in a regular `static` instead",
+            );
+        }
+    }
+}
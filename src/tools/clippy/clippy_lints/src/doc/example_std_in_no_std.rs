@@ -0,0 +1,56 @@
+use std::ops::Range;
+
+use clippy_utils::diagnostics::span_lint_and_help;
+use rustc_ast::token::TokenKind;
+use rustc_ast::tokenstream::{TokenStream, TokenTree};
+use rustc_lint::LateContext;
+use rustc_session::parse::ParseSess;
+use rustc_span::{sym, Pos};
+
+use super::{Fragments, DOC_EXAMPLE_STD_IN_NO_STD};
+
+/// Finds the paths starting with `std` in `tts`, including nested ones, and returns the byte
+/// position ranges of their first segment.
+pub(super) fn find(sess: &ParseSess, tts: &TokenStream, paths: &mut Vec<Range<usize>>) {
+    let mut after_path_sep = false;
+    let mut trees = tts.trees();
+    while let Some(tree) = trees.next() {
+        match tree {
+            TokenTree::Token(token, _) => {
+                if !after_path_sep
+                    && let Some((ident, false)) = token.ident()
+                    && ident.name == sym::std
+                    && let Some(TokenTree::Token(next, _)) = trees.look_ahead(0)
+                    && next.kind == TokenKind::ModSep
+                {
+                    let sm = sess.source_map();
+                    paths.push(
+                        sm.lookup_byte_offset(ident.span.lo()).pos.to_usize()
+                            ..sm.lookup_byte_offset(ident.span.hi()).pos.to_usize(),
+                    );
+                }
+                // `std` after `::` or `.` is not the `std` crate.
+                after_path_sep = matches!(token.kind, TokenKind::ModSep | TokenKind::Dot);
+            },
+            TokenTree::Delimited(.., inner) => {
+                after_path_sep = false;
+                find(sess, inner, paths);
+            },
+        }
+    }
+}
+
+pub fn check(cx: &LateContext<'_>, paths: &[Range<usize>], range: Range<usize>, fragments: Fragments<'_>) {
+    for path in paths {
+        if let Some(span) = fragments.span(cx, (range.start + path.start)..(range.start + path.end)) {
+            span_lint_and_help(
+                cx,
+                DOC_EXAMPLE_STD_IN_NO_STD,
+                span,
+                "this example of a `no_std` crate uses `std`",
+                None,
+                "use `core` or `alloc` instead",
+            );
+        }
+    }
+}
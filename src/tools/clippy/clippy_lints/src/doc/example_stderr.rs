@@ -0,0 +1,62 @@
+use std::ops::Range;
+
+use clippy_utils::diagnostics::span_lint_and_help;
+use rustc_ast::token::{Delimiter, TokenKind};
+use rustc_ast::tokenstream::{TokenStream, TokenTree};
+use rustc_lint::LateContext;
+use rustc_session::parse::ParseSess;
+use rustc_span::Pos;
+
+use super::{Fragments, DOC_EXAMPLE_STDERR};
+
+/// Finds the `eprint!` and `eprintln!` calls and the calls of a `stderr` function in `tts`,
+/// including nested ones, and returns their byte position ranges, up to the `!` of the macros and
+/// including the arguments of the functions.
+pub(super) fn find(sess: &ParseSess, tts: &TokenStream, writes: &mut Vec<Range<usize>>) {
+    let sm = sess.source_map();
+    let mut after_dot = false;
+    let mut trees = tts.trees();
+    while let Some(tree) = trees.next() {
+        match tree {
+            TokenTree::Token(token, _) => {
+                if !after_dot && let Some((ident, false)) = token.ident() {
+                    let hi = match (ident.as_str(), trees.look_ahead(0)) {
+                        ("eprint" | "eprintln", Some(TokenTree::Token(bang, _))) if bang.kind == TokenKind::Not => {
+                            Some(bang.span.hi())
+                        },
+                        // A `stderr` method or field, like the one of `std::process::Output`, is
+                        // not the standard error stream of the example.
+                        ("stderr", Some(TokenTree::Delimited(span, _, Delimiter::Parenthesis, _))) => {
+                            Some(span.close.hi())
+                        },
+                        _ => None,
+                    };
+                    if let Some(hi) = hi {
+                        let lo = sm.lookup_byte_offset(ident.span.lo()).pos.to_usize();
+                        writes.push(lo..sm.lookup_byte_offset(hi).pos.to_usize());
+                    }
+                }
+                after_dot = token.kind == TokenKind::Dot;
+            },
+            TokenTree::Delimited(.., inner) => {
+                after_dot = false;
+                find(sess, inner, writes);
+            },
+        }
+    }
+}
+
+pub fn check(cx: &LateContext<'_>, writes: &[Range<usize>], range: Range<usize>, fragments: Fragments<'_>) {
+    for write in writes {
+        if let Some(span) = fragments.span(cx, (range.start + write.start)..(range.start + write.end)) {
+            span_lint_and_help(
+                cx,
+                DOC_EXAMPLE_STDERR,
+                span,
+                "this example writes to stderr",
+                None,
+                "write to stdout instead",
+            );
+        }
+    }
+}
@@ -0,0 +1,60 @@
+use std::ops::Range;
+
+use clippy_utils::diagnostics::span_lint_and_help;
+use rustc_ast::token::Delimiter;
+use rustc_ast::tokenstream::{TokenStream, TokenTree};
+use rustc_lint::LateContext;
+use rustc_session::parse::ParseSess;
+use rustc_span::symbol::kw;
+use rustc_span::Pos;
+
+use super::{Fragments, DOC_EXAMPLE_UNEXPLAINED_UNSAFE};
+
+/// Returns whether the line of `code` containing `pos`, or the line before it, contains a comment.
+/// Anything looking like a comment counts, even within a string literal.
+fn has_adjacent_comment(code: &str, pos: usize) -> bool {
+    let is_comment = |line: &str| line.contains("//") || line.contains("/*") || line.contains("*/");
+    let line_start = code[..pos].rfind('\n').map_or(0, |i| i + 1);
+    let line_end = code[pos..].find('\n').map_or(code.len(), |i| pos + i);
+    is_comment(&code[line_start..line_end])
+        || (line_start > 0 && code[..line_start - 1].rsplit('\n').next().is_some_and(is_comment))
+}
+
+/// Finds the `unsafe` blocks in `tts`, including nested ones, without a comment on the line of the
+/// `unsafe` keyword or the line before it, and returns the byte position ranges of their keyword.
+pub(super) fn find(sess: &ParseSess, tts: &TokenStream, code: &str, blocks: &mut Vec<Range<usize>>) {
+    let mut trees = tts.trees();
+    while let Some(tree) = trees.next() {
+        match tree {
+            TokenTree::Token(token, _) => {
+                // Unlike `unsafe fn` and `unsafe impl`, an `unsafe` block is followed by braces.
+                if token.is_keyword(kw::Unsafe)
+                    && let Some(TokenTree::Delimited(_, _, Delimiter::Brace, _)) = trees.look_ahead(0)
+                {
+                    let sm = sess.source_map();
+                    let lo = sm.lookup_byte_offset(token.span.lo()).pos.to_usize();
+                    let hi = sm.lookup_byte_offset(token.span.hi()).pos.to_usize();
+                    if !has_adjacent_comment(code, lo) {
+                        blocks.push(lo..hi);
+                    }
+                }
+            },
+            TokenTree::Delimited(.., inner) => find(sess, inner, code, blocks),
+        }
+    }
+}
+
+pub fn check(cx: &LateContext<'_>, blocks: &[Range<usize>], range: Range<usize>, fragments: Fragments<'_>) {
+    for block in blocks {
+        if let Some(span) = fragments.span(cx, (range.start + block.start)..(range.start + block.end)) {
+            span_lint_and_help(
+                cx,
+                DOC_EXAMPLE_UNEXPLAINED_UNSAFE,
+                span,
+                "this example uses `unsafe` without explaining why",
+                None,
+                "add a `// SAFETY:` comment stating why the block is sound",
+            );
+        }
+    }
+}
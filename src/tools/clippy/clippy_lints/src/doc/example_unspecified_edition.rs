@@ -0,0 +1,26 @@
+use std::ops::Range;
+
+use clippy_utils::diagnostics::span_lint_and_help;
+use rustc_lint::LateContext;
+
+use super::{Fragments, DOC_EXAMPLE_UNSPECIFIED_EDITION};
+
+/// Checks the fenced code block at `range` with the info string `lang`. `is_rust` is whether
+/// rustdoc treats the code block as Rust.
+pub fn check(cx: &LateContext<'_>, lang: &str, is_rust: bool, range: Range<usize>, fragments: Fragments<'_>) {
+    if !is_rust || lang.split(',').any(|item| item.trim().starts_with("edition")) {
+        return;
+    }
+    let block = &fragments.doc[range.clone()];
+    let fence_len = block.find('\n').unwrap_or(block.len());
+    if let Some(span) = fragments.span(cx, range.start..range.start + fence_len) {
+        span_lint_and_help(
+            cx,
+            DOC_EXAMPLE_UNSPECIFIED_EDITION,
+            span,
+            "this example does not specify the edition it is compiled with",
+            None,
+            "add an edition tag like `edition2021` to the code block",
+        );
+    }
+}
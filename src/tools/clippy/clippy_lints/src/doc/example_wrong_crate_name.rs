@@ -0,0 +1,102 @@
+use std::ops::Range;
+
+use clippy_utils::diagnostics::span_lint_and_sugg;
+use rustc_ast::{Fn, Item, ItemKind, StmtKind, UseTree, UseTreeKind};
+use rustc_errors::Applicability;
+use rustc_hir::def_id::LOCAL_CRATE;
+use rustc_lint::LateContext;
+use rustc_span::edit_distance::edit_distance;
+use rustc_span::symbol::kw;
+use rustc_span::Pos;
+
+use super::{Fragments, DOC_EXAMPLE_WRONG_CRATE_NAME};
+
+/// The names an example may refer to crates by, other than the names of the crates themselves.
+#[derive(Default)]
+pub(super) struct CrateNames {
+    /// The first segments of the paths in `use` items and the names of `extern crate` items, with
+    /// their byte position ranges.
+    used: Vec<(String, Range<usize>)>,
+    /// The names of the items declared in the example, including `extern crate` aliases.
+    declared: Vec<String>,
+}
+
+impl CrateNames {
+    pub(super) fn collect(&mut self, item: &Item) {
+        match &item.kind {
+            ItemKind::Use(tree) => self.collect_use_tree(tree),
+            // Unlike `extern crate foo as bar;`, this doesn't declare a new name
+            ItemKind::ExternCrate(None) => {
+                let span = item.ident.span;
+                self.used
+                    .push((item.ident.to_string(), span.lo().to_usize()..span.hi().to_usize()));
+            },
+            kind => {
+                self.declared.push(item.ident.to_string());
+                if let ItemKind::Fn(box Fn { body: Some(block), .. }) = kind {
+                    for stmt in &block.stmts {
+                        if let StmtKind::Item(item) = &stmt.kind {
+                            self.collect(item);
+                        }
+                    }
+                }
+            },
+        }
+    }
+
+    fn collect_use_tree(&mut self, tree: &UseTree) {
+        match tree
+            .prefix
+            .segments
+            .iter()
+            .find(|segment| segment.ident.name != kw::PathRoot)
+        {
+            Some(segment) => {
+                let span = segment.ident.span;
+                self.used
+                    .push((segment.ident.to_string(), span.lo().to_usize()..span.hi().to_usize()));
+            },
+            None => {
+                if let UseTreeKind::Nested(trees) = &tree.kind {
+                    for (tree, _) in trees {
+                        self.collect_use_tree(tree);
+                    }
+                }
+            },
+        }
+    }
+}
+
+pub fn check(cx: &LateContext<'_>, crate_names: &CrateNames, range: Range<usize>, fragments: Fragments<'_>) {
+    let crate_name = cx.tcx.crate_name(LOCAL_CRATE);
+    let crate_name = crate_name.as_str();
+    let is_known = |name: &str| {
+        matches!(
+            name,
+            "std" | "core" | "alloc" | "proc_macro" | "test" | "crate" | "self" | "super"
+        ) || crate_names.declared.iter().any(|declared| declared == name)
+            || cx
+                .tcx
+                .crates(())
+                .iter()
+                .any(|&cnum| cx.tcx.crate_name(cnum).as_str() == name)
+    };
+    for (name, span) in &crate_names.used {
+        // Only names resembling the crate's name are likely to be meant to refer to it.
+        if name != crate_name
+            && !is_known(name)
+            && edit_distance(name, crate_name, (crate_name.len() / 3).max(1)).is_some()
+            && let Some(span) = fragments.span(cx, (range.start + span.start)..(range.start + span.end))
+        {
+            span_lint_and_sugg(
+                cx,
+                DOC_EXAMPLE_WRONG_CRATE_NAME,
+                span,
+                "this example refers to the crate under a wrong name",
+                "use the name of the crate",
+                crate_name.to_owned(),
+                Applicability::MaybeIncorrect,
+            );
+        }
+    }
+}
@@ -0,0 +1,35 @@
+use std::ops::Range;
+
+use clippy_utils::diagnostics::span_lint_and_help;
+use pulldown_cmark::HeadingLevel;
+use rustc_lint::LateContext;
+
+use super::{Fragments, DOC_HEADING_JUMP};
+
+/// Checks the heading of `level` spanning `range` of the markdown against the level of the
+/// previous heading of the documentation, if any.
+pub fn check(
+    cx: &LateContext<'_>,
+    previous: Option<HeadingLevel>,
+    level: HeadingLevel,
+    range: Range<usize>,
+    fragments: Fragments<'_>,
+) {
+    let heading = fragments.doc[range.clone()].trim_end();
+    if let Some(previous) = previous
+        && level as usize > previous as usize + 1
+        && let Some(span) = fragments.span(cx, range.start..range.start + heading.len())
+    {
+        span_lint_and_help(
+            cx,
+            DOC_HEADING_JUMP,
+            span,
+            &format!(
+                "this heading of level {} follows a heading of level {}",
+                level as usize, previous as usize
+            ),
+            None,
+            &format!("use a heading of level {} instead", previous as usize + 1),
+        );
+    }
+}
@@ -0,0 +1,143 @@
+use std::ops::Range;
+
+use clippy_utils::diagnostics::span_lint_and_help;
+use rustc_data_structures::sync::{Lock, Lrc};
+use rustc_errors::emitter::Emitter;
+use rustc_errors::translation::Translate;
+use rustc_errors::{DiagCtxt, Diagnostic, DiagnosticBuilder, FluentBundle, LazyFallbackBundle};
+use rustc_lint::LateContext;
+use rustc_parse::maybe_new_parser_from_source_str;
+use rustc_parse::parser::ForceCollect;
+use rustc_session::parse::ParseSess;
+use rustc_span::source_map::{FilePathMapping, SourceMap};
+use rustc_span::{FileName, Pos, Span};
+
+use super::{Fragments, DOC_HIDDEN_LINE_ERROR};
+
+/// Collects the primary spans of the errors emitted while parsing, including the ones the parser
+/// recovered from.
+struct ErrorSpanEmitter {
+    spans: Lrc<Lock<Vec<Span>>>,
+    fallback_bundle: LazyFallbackBundle,
+}
+
+impl Translate for ErrorSpanEmitter {
+    fn fluent_bundle(&self) -> Option<&Lrc<FluentBundle>> {
+        None
+    }
+
+    fn fallback_fluent_bundle(&self) -> &FluentBundle {
+        &self.fallback_bundle
+    }
+}
+
+impl Emitter for ErrorSpanEmitter {
+    fn emit_diagnostic(&mut self, diag: &Diagnostic) {
+        if diag.is_error()
+            && let Some(span) = diag.span.primary_span()
+        {
+            self.spans.borrow_mut().push(span);
+        }
+    }
+
+    fn source_map(&self) -> Option<&Lrc<SourceMap>> {
+        None
+    }
+}
+
+/// A line of a code example, with the markers of hidden lines removed like rustdoc does.
+struct Line<'a> {
+    /// The byte position range of the line in the example, without the line break.
+    range: Range<usize>,
+    code: &'a str,
+    hidden: bool,
+}
+
+fn lines(code: &str) -> Vec<Line<'_>> {
+    let mut offset = 0;
+    code.split_inclusive('\n')
+        .map(|full_line| {
+            let line = full_line.trim_end_matches(['\n', '\r']);
+            let range = offset..offset + line.len();
+            offset += full_line.len();
+            let trimmed = line.trim();
+            let (code, hidden) = if trimmed.starts_with("##") {
+                (&trimmed[1..], false)
+            } else if let Some(code) = trimmed.strip_prefix("# ") {
+                (code, true)
+            } else if trimmed == "#" {
+                ("", true)
+            } else {
+                (line, false)
+            };
+            Line { range, code, hidden }
+        })
+        .collect()
+}
+
+/// Parses the example the way rustdoc compiles it, that is with the hidden lines included and
+/// wrapped in a `fn main`, and returns the byte position range of the hidden line the first
+/// syntax error is on, if any. Must be called with the session globals set.
+pub(super) fn find(code: &str) -> Option<Range<usize>> {
+    let lines = lines(code);
+    if !lines.iter().any(|line| line.hidden) {
+        return None;
+    }
+
+    let mut source = "fn main() {\n".to_owned();
+    for line in &lines {
+        source.push_str(line.code);
+        source.push('\n');
+    }
+    source.push('}');
+
+    #[expect(clippy::arc_with_non_send_sync)] // the spans are shared with the emitter
+    let spans = Lrc::new(Lock::new(Vec::new()));
+    let fallback_bundle = rustc_errors::fallback_fluent_bundle(rustc_driver::DEFAULT_LOCALE_RESOURCES.to_vec(), false);
+    let emitter = ErrorSpanEmitter {
+        spans: Lrc::clone(&spans),
+        fallback_bundle,
+    };
+    let dcx = DiagCtxt::with_emitter(Box::new(emitter)).disable_warnings();
+    #[expect(clippy::arc_with_non_send_sync)] // `Lrc` is expected by with_dcx
+    let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+    let sess = ParseSess::with_dcx(dcx, sm);
+    let filename = FileName::anon_source_code(&source);
+
+    let first_error = match maybe_new_parser_from_source_str(&sess, filename, source.clone()) {
+        Ok(mut parser) => loop {
+            match parser.parse_item(ForceCollect::No) {
+                Ok(Some(_)) => {},
+                Ok(None) => break spans.borrow().first().copied(),
+                Err(e) => {
+                    let span = e.span.primary_span();
+                    e.cancel();
+                    break spans.borrow().first().copied().or(span);
+                },
+            }
+        },
+        Err(errs) => {
+            let span = errs.first().and_then(|e| e.span.primary_span());
+            errs.into_iter().for_each(DiagnosticBuilder::cancel);
+            span
+        },
+    }?;
+
+    let pos = sess.source_map().lookup_byte_offset(first_error.lo()).pos.to_usize();
+    // The first line is the one of the `fn main` wrapper.
+    let line = lines.get(source[..pos].matches('\n').count().checked_sub(1)?)?;
+    line.hidden.then(|| line.range.clone())
+}
+
+pub fn check(cx: &LateContext<'_>, error_line: Range<usize>, range: Range<usize>, fragments: Fragments<'_>) {
+    if let Some(span) = fragments.span(cx, (range.start + error_line.start)..(range.start + error_line.end)) {
+        span_lint_and_help(
+            cx,
+            DOC_HIDDEN_LINE_ERROR,
+            span,
+            "this hidden line of the example has a syntax error",
+            None,
+            "hidden lines are compiled as part of the example, even though they aren't shown",
+        );
+    }
+}
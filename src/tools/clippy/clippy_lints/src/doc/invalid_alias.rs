@@ -0,0 +1,46 @@
+use clippy_utils::diagnostics::span_lint_and_help;
+use rustc_ast::{Attribute, LitKind};
+use rustc_lint::LateContext;
+use rustc_span::{sym, Span, Symbol};
+
+use super::DOC_INVALID_ALIAS;
+
+pub fn check(cx: &LateContext<'_>, attrs: &[Attribute]) {
+    for attr in attrs {
+        if !attr.has_name(sym::doc) {
+            continue;
+        }
+        for item in attr.meta_item_list().iter().flatten() {
+            if !item.has_name(sym::alias) {
+                continue;
+            }
+            if let Some(alias) = item.value_str() {
+                // `#[doc(alias = "...")]`
+                check_alias(cx, alias, item.name_value_literal_span().unwrap_or(item.span()));
+            } else {
+                // `#[doc(alias("...", "..."))]`
+                for alias in item.meta_item_list().into_iter().flatten() {
+                    if let Some(lit) = alias.lit()
+                        && let LitKind::Str(value, _) = lit.kind
+                    {
+                        check_alias(cx, value, lit.span);
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn check_alias(cx: &LateContext<'_>, alias: Symbol, span: Span) {
+    // rustc rejects whitespace other than spaces, so only the other control characters get here.
+    if alias.as_str().contains(char::is_control) {
+        span_lint_and_help(
+            cx,
+            DOC_INVALID_ALIAS,
+            span,
+            "this documentation alias contains a control character",
+            None,
+            "remove the control character",
+        );
+    }
+}
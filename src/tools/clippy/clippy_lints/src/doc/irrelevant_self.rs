@@ -0,0 +1,33 @@
+use clippy_utils::diagnostics::span_lint_and_help;
+use rustc_lint::LateContext;
+
+use super::{DocHeaders, DOC_IRRELEVANT_SELF};
+
+/// Checks the documentation of a free function, which has neither a receiver nor an implementing
+/// type to refer to. Documentation referring to a method likely mentions the receiver of that
+/// method instead.
+pub fn check(cx: &LateContext<'_>, headers: &DocHeaders) {
+    if headers.mentions_method {
+        return;
+    }
+    for (code, span) in &headers.self_code {
+        span_lint_and_help(
+            cx,
+            DOC_IRRELEVANT_SELF,
+            *span,
+            &format!("the documentation of a free function refers to `{code}`"),
+            None,
+            "refer to the parameter or type by its name",
+        );
+    }
+}
+
+/// Returns whether the inline code `code` is the path of a method, like `Vec::reserve` or
+/// `Vec::reserve()`.
+pub fn is_method_path(code: &str) -> bool {
+    let mut segments = code.trim_end_matches("()").rsplit("::");
+    segments
+        .next()
+        .is_some_and(|method| method.starts_with(char::is_lowercase))
+        && segments.next().is_some_and(|ty| ty.starts_with(char::is_uppercase))
+}
@@ -0,0 +1,30 @@
+use std::ops::Range;
+
+use clippy_utils::diagnostics::span_lint_and_help;
+use rustc_lint::LateContext;
+
+use super::{Fragments, DOC_LITERAL_NEWLINE_ESCAPE};
+
+/// Checks the markdown source of the text at `range` for `\n`. A `\n` preceded by another
+/// backslash is an escaped backslash followed by `n`, which is most likely intended.
+pub fn check(cx: &LateContext<'_>, range: Range<usize>, fragments: Fragments<'_>) {
+    let source = &fragments.doc[range.clone()];
+    for (pos, _) in source.match_indices("\\n") {
+        let start = range.start + pos;
+        // The escaping backslash may be outside of `range`, as markdown turns `\\` into a text
+        // event of its own.
+        if fragments.doc[..start].ends_with('\\') {
+            continue;
+        }
+        if let Some(span) = fragments.span(cx, start..start + 2) {
+            span_lint_and_help(
+                cx,
+                DOC_LITERAL_NEWLINE_ESCAPE,
+                span,
+                "`\\n` in documentation is not a newline",
+                None,
+                "doc comments are not string literals, start a new line instead",
+            );
+        }
+    }
+}
@@ -0,0 +1,79 @@
+use std::ops::Range;
+
+use clippy_utils::diagnostics::span_lint_and_help;
+use rustc_lint::LateContext;
+
+use super::nested_fence::fence_run;
+use super::{Fragments, DOC_MALFORMED_MARKDOWN};
+
+/// Checks that the fenced code block spanning `range` of the markdown is closed. pulldown-cmark
+/// silently ends an unclosed code block at the end of its container, swallowing everything after
+/// the opening fence.
+pub fn check_code_block(cx: &LateContext<'_>, range: Range<usize>, fragments: Fragments<'_>) {
+    let block = fragments.doc[range.clone()].trim_end();
+    let Some(fence_char) = block.chars().next().filter(|&c| c == '`' || c == '~') else {
+        return;
+    };
+    let fence_len = fence_run(block, fence_char);
+    let first_line_len = block.find('\n').unwrap_or(block.len());
+
+    // The closing fence may be indented or prefixed by the markers of an enclosing block quote.
+    let closed = block[first_line_len..].rsplit_once('\n').is_some_and(|(_, last_line)| {
+        let last_line = last_line.trim_start_matches([' ', '>']);
+        fence_run(last_line, fence_char) >= fence_len && last_line.trim_start_matches(fence_char).trim().is_empty()
+    });
+    if !closed && let Some(span) = fragments.span(cx, range.start..range.start + first_line_len) {
+        span_lint_and_help(
+            cx,
+            DOC_MALFORMED_MARKDOWN,
+            span,
+            "this code block is never closed",
+            None,
+            "add a closing fence to end the code block",
+        );
+    }
+}
+
+/// Checks the text at `range` of the markdown for a heading that is missing the space after its
+/// `#`s, and for the destination of an inline link that is never closed. `unknown_link_end` is the
+/// end of the last link whose target could not be resolved, since that is how pulldown-cmark parses
+/// the text of an inline link whose destination is missing its closing parenthesis.
+pub fn check_text(
+    cx: &LateContext<'_>,
+    text: &str,
+    range: Range<usize>,
+    fragments: Fragments<'_>,
+    unknown_link_end: Option<usize>,
+) {
+    let before = fragments.doc[..range.start].trim_end_matches(' ');
+    let hashes = text.len() - text.trim_start_matches('#').len();
+    if (1..=6).contains(&hashes)
+        && (before.is_empty() || before.ends_with('\n'))
+        && text[hashes..].starts_with(char::is_alphabetic)
+        && let Some(span) = fragments.span(cx, range.start..range.start + hashes)
+    {
+        span_lint_and_help(
+            cx,
+            DOC_MALFORMED_MARKDOWN,
+            span,
+            "this looks like a heading, but is missing the space after the `#`",
+            None,
+            "add a space after the `#` to make this line a heading",
+        );
+    }
+
+    if unknown_link_end == Some(range.start)
+        && text.starts_with('(')
+        && !text.contains(')')
+        && let Some(span) = fragments.span(cx, range)
+    {
+        span_lint_and_help(
+            cx,
+            DOC_MALFORMED_MARKDOWN,
+            span,
+            "the destination of this link is never closed",
+            None,
+            "add a closing `)` after the link destination",
+        );
+    }
+}
@@ -0,0 +1,42 @@
+use std::ops::Range;
+
+use clippy_utils::diagnostics::span_lint_and_help;
+use rustc_lint::LateContext;
+
+use super::{Fragments, DOC_MALFORMED_TABLE};
+
+/// Checks the paragraph spanning `range` of the markdown for rows of a table that is missing the
+/// separator row after its header. Without it, pulldown-cmark and rustdoc render the rows as text.
+/// Only rows starting with a `|` are recognized.
+pub fn check(cx: &LateContext<'_>, range: Range<usize>, fragments: Fragments<'_>) {
+    let paragraph = fragments.doc[range.clone()].trim_end();
+    let mut rows = paragraph.lines().map(str::trim);
+    let Some(header) = rows.next() else {
+        return;
+    };
+    // A separator row whose number of cells doesn't match the header is malformed differently.
+    let is_row = |line: &str| {
+        line.len() > 1
+            && line.starts_with('|')
+            && line.ends_with('|')
+            && !line.chars().all(|c| matches!(c, '|' | '-' | ':' | ' '))
+    };
+    if is_row(header)
+        && rows.clone().next().is_some()
+        && rows.all(is_row)
+        && let Some(span) = fragments.span(cx, range.start..range.start + paragraph.len())
+    {
+        let columns = header[1..header.len() - 1].split('|').count();
+        span_lint_and_help(
+            cx,
+            DOC_MALFORMED_TABLE,
+            span,
+            "this looks like a table, but is missing the separator row after the header",
+            None,
+            &format!(
+                "add a row like `|{}` after the first one to make this a table",
+                " --- |".repeat(columns)
+            ),
+        );
+    }
+}
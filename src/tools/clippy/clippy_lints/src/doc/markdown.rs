@@ -1,14 +1,137 @@
-use clippy_utils::diagnostics::{span_lint, span_lint_and_then};
+use std::iter;
+use std::ops::Range;
+
+use clippy_utils::diagnostics::{span_lint_and_sugg, span_lint_and_then};
 use clippy_utils::source::snippet_with_applicability;
 use rustc_data_structures::fx::FxHashSet;
 use rustc_errors::{Applicability, SuggestionStyle};
-use rustc_lint::LateContext;
+use rustc_lexer::unescape::{unescape_unicode, Mode};
+use rustc_lint::{LateContext, LintContext};
+use rustc_resolve::rustdoc::{add_doc_fragment, DocFragmentKind};
 use rustc_span::{BytePos, Pos, Span};
 use url::Url;
 
+use super::Fragments;
 use crate::doc::DOC_MARKDOWN;
 
-pub fn check(cx: &LateContext<'_>, valid_idents: &FxHashSet<String>, text: &str, span: Span) {
+pub fn check(
+    cx: &LateContext<'_>,
+    valid_idents: &FxHashSet<String>,
+    tick_keywords: bool,
+    in_link: bool,
+    text: &str,
+    span: Span,
+) {
+    check_words(cx, valid_idents, tick_keywords, in_link, text, |word| {
+        Span::new(
+            span.lo() + BytePos::from_usize(word.start),
+            span.lo() + BytePos::from_usize(word.end),
+            span.ctxt(),
+            span.parent(),
+        )
+    });
+}
+
+/// Checks `text`, which spans `range` of the markdown, if it comes from a `#[doc = "..."]`
+/// attribute. The words are mapped back into the string literal of the attribute, taking its
+/// escapes into account.
+pub fn check_doc_attribute(
+    cx: &LateContext<'_>,
+    valid_idents: &FxHashSet<String>,
+    tick_keywords: bool,
+    in_link: bool,
+    text: &str,
+    range: Range<usize>,
+    fragments: Fragments<'_>,
+) {
+    // Render the fragments the same way `check_attrs` does to find the one containing the text.
+    let mut rendered = String::new();
+    let mut fragment = None;
+    for frag in fragments.fragments {
+        let start = rendered.len();
+        add_doc_fragment(&mut rendered, frag);
+        if range.start >= start && range.end <= rendered.len() {
+            fragment = Some((frag, start));
+            break;
+        }
+    }
+    let Some((fragment, fragment_start)) = fragment else {
+        return;
+    };
+    if fragment.kind != DocFragmentKind::RawDoc {
+        return;
+    }
+
+    // The string literal is the last token of the attribute.
+    let Ok(snippet) = cx.sess().source_map().span_to_snippet(fragment.span) else {
+        return;
+    };
+    let Some(close) = snippet.rfind('"') else {
+        return;
+    };
+    let Some(open) = snippet[..close].find('"') else {
+        return;
+    };
+    let mode = if snippet[..open].trim_end_matches('#').ends_with('r') {
+        Mode::RawStr
+    } else {
+        Mode::Str
+    };
+
+    // The range of the literal's source each byte of its value comes from.
+    let mut source_ranges = Vec::new();
+    let mut value = String::new();
+    unescape_unicode(&snippet[open + 1..close], mode, &mut |source, c| {
+        if let Ok(c) = c {
+            value.push(c);
+            source_ranges.extend(iter::repeat(source).take(c.len_utf8()));
+        }
+    });
+
+    // The fragment's lines have been trimmed and unindented, so look them up in the value one
+    // after the other to find the line of the text.
+    let fragment_text = &rendered[fragment_start..];
+    let offset = range.start - fragment_start;
+    let line_start = fragment_text[..offset].rfind('\n').map_or(0, |i| i + 1);
+    let mut line_pos = 0;
+    let mut line_end = 0;
+    for line in fragment_text[..line_start]
+        .lines()
+        .chain(fragment_text[line_start..].lines().take(1))
+    {
+        let Some(pos) = value[line_end..].find(line) else {
+            return;
+        };
+        line_pos = line_end + pos;
+        line_end = line_pos + line.len();
+    }
+    let text_start = line_pos + (offset - line_start);
+    if text_start + text.len() > source_ranges.len() {
+        return;
+    }
+
+    let literal_start = fragment.span.lo() + BytePos::from_usize(open + 1);
+    check_words(cx, valid_idents, tick_keywords, in_link, text, |word| {
+        let start = source_ranges[text_start + word.start].start;
+        let end = source_ranges[text_start + word.end - 1].end;
+        fragment
+            .span
+            .with_lo(literal_start + BytePos::from_usize(start))
+            .with_hi(literal_start + BytePos::from_usize(end))
+    });
+}
+
+/// Checks the words of `text`. `span_of` returns the span of the word at the given range of the
+/// text. Rust keywords are only checked if `tick_keywords` is set. Paths are not checked if the
+/// text is the label of a link, as in the intra-doc link `[std::vec::Vec]`.
+fn check_words(
+    cx: &LateContext<'_>,
+    valid_idents: &FxHashSet<String>,
+    tick_keywords: bool,
+    in_link: bool,
+    text: &str,
+    span_of: impl Fn(Range<usize>) -> Span,
+) {
     for orig_word in text.split(|c: char| c.is_whitespace() || c == '\'') {
         // Trim punctuation as in `some comment (see foo::bar).`
         //                                                   ^^
@@ -33,23 +156,33 @@ pub fn check(cx: &LateContext<'_>, valid_idents: &FxHashSet<String>, text: &str,
             word = word.trim_end_matches(':');
         }
 
-        if valid_idents.contains(word) || word.chars().all(|c| c == ':') {
+        if valid_idents.contains(word)
+            || word.chars().all(|c| c == ':')
+            || (!tick_keywords && is_keyword(word))
+            || (in_link && word.contains("::"))
+        {
             continue;
         }
 
         // Adjust for the current word
         let offset = word.as_ptr() as usize - text.as_ptr() as usize;
-        let span = Span::new(
-            span.lo() + BytePos::from_usize(offset),
-            span.lo() + BytePos::from_usize(offset + word.len()),
-            span.ctxt(),
-            span.parent(),
-        );
-
-        check_word(cx, word, span);
+        check_word(cx, word, span_of(offset..offset + word.len()));
     }
 }
 
+/// The keywords of Rust, not including the ones only reserved for future use.
+const KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern", "false", "fn",
+    "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref", "return", "self", "Self",
+    "static", "struct", "super", "trait", "true", "type", "union", "unsafe", "use", "where", "while",
+];
+
+/// Returns whether `word` is a Rust keyword, possibly followed by `()` or prefixed or suffixed by
+/// `::`, like `async()` or `crate::`. Those mostly refer to the concept rather than to code.
+fn is_keyword(word: &str) -> bool {
+    KEYWORDS.contains(&word.trim_end_matches("()").trim_matches(':'))
+}
+
 fn check_word(cx: &LateContext<'_>, word: &str, span: Span) {
     /// Checks if a string is upper-camel-case, i.e., starts with an uppercase and
     /// contains at least two uppercase letters (`Clippy` is ok) and one lower-case
@@ -78,11 +211,16 @@ fn has_hyphen(s: &str) -> bool {
     if let Ok(url) = Url::parse(word) {
         // try to get around the fact that `foo::bar` parses as a valid URL
         if !url.cannot_be_a_base() {
-            span_lint(
+            let mut applicability = Applicability::MachineApplicable;
+            let snippet = snippet_with_applicability(cx, span, "..", &mut applicability);
+            span_lint_and_sugg(
                 cx,
                 DOC_MARKDOWN,
                 span,
                 "you should put bare URLs between `<`/`>` or make a proper Markdown link",
+                "try",
+                format!("<{snippet}>"),
+                applicability,
             );
 
             return;
@@ -1,20 +1,26 @@
-use clippy_utils::diagnostics::{span_lint, span_lint_and_note};
+use clippy_utils::diagnostics::{span_lint, span_lint_and_help, span_lint_and_note, span_lint_and_then};
 use clippy_utils::ty::{implements_trait, is_type_diagnostic_item};
-use clippy_utils::{is_doc_hidden, return_ty};
+use clippy_utils::{is_doc_hidden, is_lint_allowed, return_ty};
+use rustc_errors::Diagnostic;
 use rustc_hir::{BodyId, FnSig, OwnerId, Unsafety};
 use rustc_lint::LateContext;
-use rustc_middle::ty;
+use rustc_middle::ty::{self, Ty};
+use rustc_span::edit_distance::edit_distance;
 use rustc_span::{sym, Span};
 
-use super::{DocHeaders, MISSING_ERRORS_DOC, MISSING_PANICS_DOC, MISSING_SAFETY_DOC, UNNECESSARY_SAFETY_DOC};
+use super::{
+    DocHeaders, Panic, Section, CHECKED_SECTIONS, DOC_NONEXISTENT_PARAM, DOC_UNKNOWN_PARAM, MISSING_ERRORS_DOC,
+    MISSING_EXAMPLES_DOC, MISSING_PANICS_DOC, MISSING_REQUIRED_DOC_SECTION, MISSING_RETURNS_NONE_DOC,
+    MISSING_SAFETY_DOC, SUSPICIOUS_ERRORS_DOC, UNNECESSARY_SAFETY_DOC,
+};
 
 pub fn check(
     cx: &LateContext<'_>,
     owner_id: OwnerId,
     sig: &FnSig<'_>,
-    headers: DocHeaders,
+    headers: &DocHeaders,
     body_id: Option<BodyId>,
-    panic_span: Option<Span>,
+    panic: Option<Panic>,
     check_private_items: bool,
 ) {
     if !check_private_items && !cx.effective_visibilities.is_exported(owner_id.def_id) {
@@ -33,14 +39,90 @@ pub fn check(
     }
 
     let span = cx.tcx.def_span(owner_id);
-    match (headers.safety, sig.header.unsafety) {
-        (false, Unsafety::Unsafe) => span_lint(
+    check_safety_section(cx, span, headers, sig.header.unsafety);
+    check_panics_section(cx, span, sig, headers, panic);
+    if !headers.returns && is_type_diagnostic_item(cx, return_ty(cx, owner_id), sym::Option) {
+        span_lint_and_help(
+            cx,
+            MISSING_RETURNS_NONE_DOC,
+            span,
+            "docs for function returning `Option` do not describe when `None` is returned",
+            None,
+            "add a section describing when `None` is returned",
+        );
+    }
+    if let Some(section) = &headers.errors_section
+        && !section.text.trim().is_empty()
+        && let ret_ty = peel_lazy_alias(cx, return_ty(cx, owner_id))
+        && is_type_diagnostic_item(cx, ret_ty, sym::Result)
+        && let ty::Adt(_, args) = ret_ty.kind()
+        && !mentions_error(&section.text, args.type_at(1))
+    {
+        span_lint_and_help(
+            cx,
+            SUSPICIOUS_ERRORS_DOC,
+            section.span,
+            "this `# Errors` section does not describe any error",
+            None,
+            "describe the conditions under which an `Err` is returned",
+        );
+    }
+    match headers.section("Errors") {
+        Section::Absent if returns_result(cx, owner_id, body_id) => span_lint(
+            cx,
+            MISSING_ERRORS_DOC,
+            span,
+            "docs for function returning `Result` missing `# Errors` section",
+        ),
+        Section::Empty(heading) if returns_result(cx, owner_id, body_id) => span_lint_and_note(
+            cx,
+            MISSING_ERRORS_DOC,
+            span,
+            "docs for function returning `Result` have an empty `# Errors` section",
+            Some(heading),
+            "the section has no content",
+        ),
+        _ => (),
+    }
+    check_documented_params(cx, owner_id, headers);
+    // Functions without any docs are left to `missing_docs`.
+    let has_docs = cx
+        .tcx
+        .hir()
+        .attrs(owner_id.into())
+        .iter()
+        .any(|attr| attr.doc_str().is_some());
+    if !headers.examples && has_docs {
+        span_lint(
+            cx,
+            MISSING_EXAMPLES_DOC,
+            span,
+            "docs for function missing `# Examples` section",
+        );
+    }
+    if !has_docs {
+        return;
+    }
+    check_required_sections(cx, span, headers);
+}
+
+fn check_safety_section(cx: &LateContext<'_>, span: Span, headers: &DocHeaders, unsafety: Unsafety) {
+    match (headers.section("Safety"), unsafety) {
+        (Section::Absent, Unsafety::Unsafe) => span_lint(
             cx,
             MISSING_SAFETY_DOC,
             span,
             "unsafe function's docs miss `# Safety` section",
         ),
-        (true, Unsafety::Normal) => span_lint(
+        (Section::Empty(heading), Unsafety::Unsafe) => span_lint_and_note(
+            cx,
+            MISSING_SAFETY_DOC,
+            span,
+            "unsafe function's docs have an empty `# Safety` section",
+            Some(heading),
+            "the section has no content",
+        ),
+        (Section::Empty(_) | Section::Present, Unsafety::Normal) => span_lint(
             cx,
             UNNECESSARY_SAFETY_DOC,
             span,
@@ -48,39 +130,151 @@ pub fn check(
         ),
         _ => (),
     }
-    if !headers.panics && panic_span.is_some() {
-        span_lint_and_note(
+}
+
+fn check_panics_section(cx: &LateContext<'_>, span: Span, sig: &FnSig<'_>, headers: &DocHeaders, panic: Option<Panic>) {
+    let panics = headers.section("Panics");
+    if let Section::Empty(heading) = panics
+        && let Some(panic) = panic
+    {
+        span_lint_and_then(
             cx,
             MISSING_PANICS_DOC,
             span,
-            "docs for function which may panic missing `# Panics` section",
-            panic_span,
-            "first possible panic found here",
+            "docs for function which may panic have an empty `# Panics` section",
+            |diag| {
+                diag.span_note(heading, "the section has no content");
+                note_panic(diag, panic);
+            },
         );
     }
-    if !headers.errors {
-        if is_type_diagnostic_item(cx, return_ty(cx, owner_id), sym::Result) {
-            span_lint(
+    if panics == Section::Absent
+        && let Some(panic) = panic
+    {
+        // Panics in const contexts of the body, like `const` items, are compilation errors and thus
+        // not found in the first place. The remaining ones of a `const fn` can only happen when it
+        // is called at runtime.
+        if sig.header.is_const() {
+            span_lint_and_then(
                 cx,
-                MISSING_ERRORS_DOC,
+                MISSING_PANICS_DOC,
                 span,
-                "docs for function returning `Result` missing `# Errors` section",
+                "docs for `const fn` which may panic when called at runtime missing `# Panics` section",
+                |diag| {
+                    note_panic(diag, panic);
+                    diag.note("a panic during compile-time evaluation is a compilation error instead");
+                },
             );
-        } else if let Some(body_id) = body_id
-            && let Some(future) = cx.tcx.lang_items().future_trait()
-            && let typeck = cx.tcx.typeck_body(body_id)
-            && let body = cx.tcx.hir().body(body_id)
-            && let ret_ty = typeck.expr_ty(body.value)
-            && implements_trait(cx, ret_ty, future, &[])
-            && let ty::Coroutine(_, subs) = ret_ty.kind()
-            && is_type_diagnostic_item(cx, subs.as_coroutine().return_ty(), sym::Result)
-        {
-            span_lint(
+        } else {
+            span_lint_and_then(
                 cx,
-                MISSING_ERRORS_DOC,
+                MISSING_PANICS_DOC,
                 span,
-                "docs for function returning `Result` missing `# Errors` section",
+                "docs for function which may panic missing `# Panics` section",
+                |diag| note_panic(diag, panic),
             );
         }
     }
 }
+
+/// Checks the sections required by the `required-doc-sections` configuration.
+fn check_required_sections(cx: &LateContext<'_>, span: Span, headers: &DocHeaders) {
+    for (name, &section) in &headers.sections {
+        if CHECKED_SECTIONS.contains(&name.as_str()) {
+            continue;
+        }
+        match section {
+            Section::Absent => span_lint(
+                cx,
+                MISSING_REQUIRED_DOC_SECTION,
+                span,
+                &format!("docs for function missing `# {name}` section"),
+            ),
+            Section::Empty(heading) => span_lint_and_note(
+                cx,
+                MISSING_REQUIRED_DOC_SECTION,
+                span,
+                &format!("docs for function have an empty `# {name}` section"),
+                Some(heading),
+                "the section has no content",
+            ),
+            Section::Present => (),
+        }
+    }
+}
+
+/// Points to the first possible `panic` of the function, noting if it only happens with debug
+/// assertions enabled.
+fn note_panic(diag: &mut Diagnostic, panic: Panic) {
+    diag.span_note(panic.span, "first possible panic found here");
+    if panic.debug_only {
+        diag.note("this panic can only happen with debug assertions enabled");
+    }
+}
+
+fn check_documented_params(cx: &LateContext<'_>, owner_id: OwnerId, headers: &DocHeaders) {
+    let params = cx.tcx.fn_arg_names(owner_id.to_def_id());
+    // Misspellings are already linted, with a suggestion, by `DOC_UNKNOWN_PARAM`.
+    let unknown_param_allowed = is_lint_allowed(cx, DOC_UNKNOWN_PARAM, owner_id.into());
+    for (code, span) in &headers.documented_params {
+        if params.iter().any(|param| {
+            param.name.as_str() == code
+                || (!unknown_param_allowed && edit_distance(code, param.name.as_str(), 1).is_some())
+        }) {
+            continue;
+        }
+        span_lint_and_help(
+            cx,
+            DOC_NONEXISTENT_PARAM,
+            *span,
+            &format!("`{code}` is documented as a parameter, but the function has no parameter with that name"),
+            None,
+            "update the documentation to the current parameters of the function",
+        );
+    }
+}
+
+/// Checks whether `text` mentions an error, or a variant of `err_ty` if it is an enum of the local
+/// crate.
+fn mentions_error(text: &str, err_ty: Ty<'_>) -> bool {
+    let lowercase = text.to_lowercase();
+    lowercase.contains("err")
+        || lowercase.contains("fail")
+        || err_ty.ty_adt_def().is_some_and(|def| {
+            def.is_enum()
+                && def.did().is_local()
+                && def
+                    .variants()
+                    .iter()
+                    .any(|variant| text.contains(variant.name.as_str()))
+        })
+}
+
+/// Returns whether the function returns a `Result`, directly or as the output of the `Future`
+/// returned by an `async fn`.
+fn returns_result(cx: &LateContext<'_>, owner_id: OwnerId, body_id: Option<BodyId>) -> bool {
+    if is_type_diagnostic_item(cx, peel_lazy_alias(cx, return_ty(cx, owner_id)), sym::Result) {
+        true
+    } else if let Some(body_id) = body_id
+        && let Some(future) = cx.tcx.lang_items().future_trait()
+        && let typeck = cx.tcx.typeck_body(body_id)
+        && let body = cx.tcx.hir().body(body_id)
+        && let ret_ty = typeck.expr_ty(body.value)
+        && implements_trait(cx, ret_ty, future, &[])
+        && let ty::Coroutine(_, subs) = ret_ty.kind()
+    {
+        is_type_diagnostic_item(cx, subs.as_coroutine().return_ty(), sym::Result)
+    } else {
+        false
+    }
+}
+
+/// Replaces a lazy type alias (see `#![feature(lazy_type_alias)]`) by the type it aliases. Only one
+/// level is peeled, so there is no risk of cycles. Other type aliases are already expanded in `ty`.
+fn peel_lazy_alias<'tcx>(cx: &LateContext<'tcx>, ty: Ty<'tcx>) -> Ty<'tcx> {
+    if let ty::Alias(ty::Weak, alias) = *ty.kind() {
+        cx.tcx.type_of(alias.def_id).instantiate(cx.tcx, alias.args)
+    } else {
+        ty
+    }
+}
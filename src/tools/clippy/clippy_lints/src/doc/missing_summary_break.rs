@@ -0,0 +1,69 @@
+use std::ops::Range;
+
+use clippy_utils::diagnostics::span_lint_and_help;
+use rustc_hir::OwnerId;
+use rustc_lint::LateContext;
+use rustc_span::Span;
+
+use super::{DocHeaders, Fragments, DOC_MISSING_SUMMARY_BREAK};
+
+/// Abbreviations that end with a period without ending the sentence.
+const ABBREVIATIONS: &[&str] = &["approx", "cf", "e.g", "etc", "i.e", "vs"];
+
+/// Returns whether the last word of `text` ends a sentence.
+fn ends_sentence(text: &str) -> bool {
+    let last_word = text.split_whitespace().next_back().unwrap_or_default();
+    match last_word.strip_suffix('.') {
+        Some(word) => !word.is_empty() && !ABBREVIATIONS.contains(&word.to_ascii_lowercase().as_str()),
+        None => last_word.ends_with(['!', '?']),
+    }
+}
+
+/// Returns the span of the first line of the paragraph spanning `range` of the markdown, if the
+/// paragraph is the first thing in the documentation and continues after that line. Sentences are
+/// hard to detect, so this only finds a first line that ends a sentence, followed by lines that
+/// start with a capital letter and end a sentence. The first word of the following lines must also
+/// fit on the first line, within the length of the longest line of the paragraph. Otherwise the
+/// first sentence may just happen to end where the paragraph is wrapped.
+pub fn summary_line(cx: &LateContext<'_>, range: Range<usize>, fragments: Fragments<'_>) -> Option<Span> {
+    if !fragments.doc[..range.start].trim().is_empty() {
+        return None;
+    }
+    let paragraph = fragments.doc[range.clone()].trim_end();
+    let (first_line, rest) = paragraph.split_once('\n')?;
+    let first_line = first_line.trim_end();
+    let rest = rest.trim_start();
+    let width = paragraph
+        .lines()
+        .map(|line| line.trim().chars().count())
+        .max()
+        .unwrap_or_default();
+    let next_word = rest.split_whitespace().next().unwrap_or_default();
+    if ends_sentence(first_line)
+        && ends_sentence(rest)
+        && rest.starts_with(char::is_uppercase)
+        && first_line.chars().count() + 1 + next_word.chars().count() <= width
+    {
+        fragments.span(cx, range.start..range.start + first_line.len())
+    } else {
+        None
+    }
+}
+
+/// Checks the documentation of an item. The summary is shown in the item listings of the
+/// crate's documentation, so only the items that are part of its public API are linted, unless
+/// `check_private_items` is set.
+pub fn check(cx: &LateContext<'_>, owner_id: OwnerId, headers: &DocHeaders, check_private_items: bool) {
+    if let Some(span) = headers.summary_line
+        && (check_private_items || cx.effective_visibilities.is_exported(owner_id.def_id))
+    {
+        span_lint_and_help(
+            cx,
+            DOC_MISSING_SUMMARY_BREAK,
+            span,
+            "the summary of this documentation is not followed by a blank line",
+            None,
+            "add a blank line after the first sentence, otherwise the whole paragraph is used as the summary",
+        );
+    }
+}
@@ -0,0 +1,117 @@
+use std::ops::Range;
+use std::{io, thread};
+
+use clippy_utils::diagnostics::span_lint_and_help;
+use rustc_ast::{ItemKind, StmtKind};
+use rustc_data_structures::sync::Lrc;
+use rustc_errors::emitter::HumanEmitter;
+use rustc_errors::{DiagCtxt, DiagnosticBuilder};
+use rustc_lint::{LateContext, Lint};
+use rustc_parse::maybe_new_parser_from_source_str;
+use rustc_parse::parser::ForceCollect;
+use rustc_session::parse::ParseSess;
+use rustc_span::edition::Edition;
+use rustc_span::source_map::{FilePathMapping, SourceMap};
+use rustc_span::FileName;
+
+use super::Fragments;
+
+/// Tags of code blocks that are neither Rust nor another language.
+const NOT_A_LANGUAGE: &[&str] = &["ignore", "text", "plain", "console", "output"];
+
+/// Tags of code blocks that are Rust, but that rustdoc neither highlights nor tests. They are used
+/// on purpose for code that is not meant to be tested.
+const RUST_ALIASES: &[&str] = &["rs"];
+
+/// Returns the language a fenced code block with the info string `lang` is tagged as, if it is
+/// not Rust. `is_rust` is whether rustdoc treats the code block as Rust.
+pub(super) fn other_language(lang: &str, is_rust: bool) -> Option<&str> {
+    let tag = lang.split(',').next()?.trim();
+    (!is_rust
+        && !tag.is_empty()
+        && !RUST_ALIASES.contains(&tag)
+        && !lang.split(',').any(|item| NOT_A_LANGUAGE.contains(&item.trim())))
+    .then_some(tag)
+}
+
+/// Returns the tag of a fenced code block with the info string `lang` if it is only tagged as
+/// plain text. Other tags, like `ignore`, or `console` for shell sessions, are left alone.
+pub(super) fn text_tag(lang: &str) -> Option<&str> {
+    let tag = lang.trim();
+    ["text", "plain"].contains(&tag).then_some(tag)
+}
+
+/// Parses `source`, an example wrapped in a `fn main` the way rustdoc compiles it, and returns
+/// whether it parses without a single error and declares an item or a `let` binding. Code of other
+/// languages often happens to parse as a lone Rust expression, so that is not enough.
+fn is_valid_rust(source: String, edition: Edition) -> bool {
+    rustc_driver::catch_fatal_errors(|| {
+        rustc_span::create_session_globals_then(edition, || {
+            let fallback_bundle =
+                rustc_errors::fallback_fluent_bundle(rustc_driver::DEFAULT_LOCALE_RESOURCES.to_vec(), false);
+            let emitter = HumanEmitter::new(Box::new(io::sink()), fallback_bundle);
+            let dcx = DiagCtxt::with_emitter(Box::new(emitter)).disable_warnings();
+            #[expect(clippy::arc_with_non_send_sync)] // `Lrc` is expected by with_dcx
+            let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+            let sess = ParseSess::with_dcx(dcx, sm);
+            let filename = FileName::anon_source_code(&source);
+
+            let mut parser = match maybe_new_parser_from_source_str(&sess, filename, source) {
+                Ok(parser) => parser,
+                Err(errs) => {
+                    errs.into_iter().for_each(DiagnosticBuilder::cancel);
+                    return false;
+                },
+            };
+            let item = match parser.parse_item(ForceCollect::No) {
+                Ok(Some(item)) => item,
+                Ok(None) => return false,
+                Err(e) => {
+                    e.cancel();
+                    return false;
+                },
+            };
+            let declares_something = match &item.kind {
+                ItemKind::Fn(func) => func.body.as_ref().is_some_and(|body| {
+                    body.stmts
+                        .iter()
+                        .any(|stmt| matches!(stmt.kind, StmtKind::Local(_) | StmtKind::Item(_)))
+                }),
+                _ => false,
+            };
+            declares_something
+                && matches!(parser.parse_item(ForceCollect::No), Ok(None))
+                && sess.dcx.has_errors().is_none()
+        })
+    })
+    .unwrap_or(false)
+}
+
+/// Checks the code of a code block tagged as `lang`, whose opening fence is at `fence` of the
+/// markdown, and emits `lint` if it is Rust.
+pub fn check(
+    cx: &LateContext<'_>,
+    lint: &'static Lint,
+    code: &str,
+    lang: &str,
+    fence: Range<usize>,
+    fragments: Fragments<'_>,
+) {
+    // Because of the global session, we need to create a new session in a different thread with
+    // the edition we need.
+    let edition = cx.tcx.sess.edition();
+    let source = format!("fn main() {{\n{code}\n}}");
+    let is_rust = thread::spawn(move || is_valid_rust(source, edition))
+        .join()
+        .expect("thread::spawn failed");
+    if is_rust && let Some(span) = fragments.span(cx, fence) {
+        span_lint_and_help(
+            cx,
+            lint,
+            span,
+            &format!("this code block is tagged as `{lang}`, but contains valid Rust code"),
+            None,
+            "tag the code block as `rust`, or remove the tag, to have it tested as an example",
+        );
+    }
+}
@@ -1,22 +1,25 @@
+use clippy_config::types::DocCommentStyle;
 use clippy_utils::attrs::is_doc_hidden;
-use clippy_utils::diagnostics::{span_lint, span_lint_and_help};
+use clippy_utils::diagnostics::{span_lint, span_lint_and_help, span_lint_and_note};
 use clippy_utils::macros::{is_panic, root_macro_call_first_node};
 use clippy_utils::ty::is_type_diagnostic_item;
 use clippy_utils::visitors::Visitable;
-use clippy_utils::{is_entrypoint_fn, method_chain_args};
+use clippy_utils::{is_entrypoint_fn, match_any_def_paths, method_chain_args, peel_blocks};
 use pulldown_cmark::Event::{
     Code, End, FootnoteReference, HardBreak, Html, Rule, SoftBreak, Start, TaskListMarker, Text,
 };
-use pulldown_cmark::Tag::{CodeBlock, Heading, Item, Link, Paragraph};
-use pulldown_cmark::{BrokenLink, CodeBlockKind, CowStr, Options};
+use pulldown_cmark::Tag::{BlockQuote, CodeBlock, Heading, Item, Link, List, Paragraph};
+use pulldown_cmark::{BrokenLink, CodeBlockKind, CowStr, LinkType, Options};
 use rustc_ast::ast::Attribute;
-use rustc_data_structures::fx::FxHashSet;
+use rustc_data_structures::fx::{FxHashMap, FxHashSet};
 use rustc_hir as hir;
+use rustc_hir::def_id::LocalDefId;
 use rustc_hir::intravisit::{self, Visitor};
-use rustc_hir::{AnonConst, Expr};
+use rustc_hir::{AnonConst, Expr, ExprKind, HirId, Local};
 use rustc_lint::{LateContext, LateLintPass};
 use rustc_middle::hir::nested_filter;
 use rustc_middle::lint::in_external_macro;
+use rustc_middle::mir::traversal;
 use rustc_middle::ty;
 use rustc_resolve::rustdoc::{
     add_doc_fragment, attrs_to_doc_fragments, main_body_opts, source_span_for_markdown_range, DocFragment,
@@ -24,14 +27,43 @@
 use rustc_session::impl_lint_pass;
 use rustc_span::edition::Edition;
 use rustc_span::{sym, Span};
+use std::collections::BTreeMap;
 use std::ops::Range;
+use std::{iter, mem};
 use url::Url;
 
+mod code_before_summary;
+mod comment_style;
+mod duplicate_section;
+mod empty_doc_comment;
+mod example_assert_order;
+mod example_deprecated;
+mod example_no_assertion;
+mod example_static_mut;
+mod example_std_in_no_std;
+mod example_stderr;
+mod example_unexplained_unsafe;
+mod example_unspecified_edition;
+mod example_wrong_crate_name;
+mod heading_jump;
+mod hidden_line_error;
+mod invalid_alias;
+mod irrelevant_self;
 mod link_with_quotes;
+mod literal_newline_escape;
+mod malformed_markdown;
+mod malformed_table;
 mod markdown;
 mod missing_headers;
+mod missing_summary_break;
+mod mistagged_rust;
 mod needless_doctest_main;
+mod nested_fence;
+mod nonsense_backticks;
+mod placeholder_doc;
 mod suspicious_doc_comments;
+mod unknown_param;
+mod unnecessary_unsafe_marker;
 
 declare_clippy_lint! {
     /// ### What it does
@@ -78,7 +110,7 @@
 declare_clippy_lint! {
     /// ### What it does
     /// Checks for the doc comments of publicly visible
-    /// unsafe functions and warns if there is no `# Safety` section.
+    /// unsafe functions and warns if there is no `# Safety` section, or if the section is empty.
     ///
     /// ### Why is this bad?
     /// Unsafe functions should document their safety
@@ -113,7 +145,8 @@
 declare_clippy_lint! {
     /// ### What it does
     /// Checks the doc comments of publicly visible functions that
-    /// return a `Result` type and warns if there is no `# Errors` section.
+    /// return a `Result` type and warns if there is no `# Errors` section, or if the section is
+    /// empty.
     ///
     /// ### Why is this bad?
     /// Documenting the type of errors that can be returned from a
@@ -142,217 +175,1351 @@
 declare_clippy_lint! {
     /// ### What it does
     /// Checks the doc comments of publicly visible functions that
-    /// may panic and warns if there is no `# Panics` section.
+    /// may panic and warns if there is no `# Panics` section, or if the section is empty.
+    ///
+    /// Besides `panic!`-like macros, `unwrap` and `expect`, a function whose body only forwards to
+    /// a standard library method that panics on invalid arguments, like `Vec::remove`, may panic.
+    /// Panics that only happen with debug assertions enabled, like the ones of `debug_assert!`, are
+    /// ignored unless the `ignore-debug-only-panics` configuration is disabled.
     ///
     /// ### Why is this bad?
     /// Documenting the scenarios in which panicking occurs
     /// can help callers who do not want to panic to avoid those situations.
     ///
-    /// ### Examples
-    /// Since the following function may panic it has a `# Panics` section in
-    /// its doc comment:
+    /// ### Examples
+    /// Since the following function may panic it has a `# Panics` section in
+    /// its doc comment:
+    ///
+    /// ```no_run
+    /// /// # Panics
+    /// ///
+    /// /// Will panic if y is 0
+    /// pub fn divide_by(x: i32, y: i32) -> i32 {
+    ///     if y == 0 {
+    ///         panic!("Cannot divide by 0")
+    ///     } else {
+    ///         x / y
+    ///     }
+    /// }
+    /// ```
+    #[clippy::version = "1.51.0"]
+    pub MISSING_PANICS_DOC,
+    pedantic,
+    "`pub fn` may panic without `# Panics` in doc comment"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for `fn main() { .. }` in doctests, including a `main` returning `Result<(), E>` and
+    /// ending in `Ok(())`, which rustdoc supports as `Ok::<(), E>(())` at the end of the example.
+    /// Examples marked `compile_fail` are not checked.
+    ///
+    /// ### Why is this bad?
+    /// The test can be shorter (and likely more readable)
+    /// if the `fn main()` is left implicit.
+    ///
+    /// ### Examples
+    /// ```no_run
+    /// /// An example of a doctest with a `main()` function
+    /// ///
+    /// /// # Examples
+    /// ///
+    /// /// ```
+    /// /// fn main() {
+    /// ///     // this needs not be in an `fn`
+    /// /// }
+    /// /// ```
+    /// fn needless_main() {
+    ///     unimplemented!();
+    /// }
+    /// ```
+    #[clippy::version = "1.40.0"]
+    pub NEEDLESS_DOCTEST_MAIN,
+    style,
+    "presence of `fn main() {` in code examples"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for `#[test]` in doctests unless they are marked with
+    /// either `ignore`, `no_run` or `compile_fail`.
+    ///
+    /// ### Why is this bad?
+    /// Code in examples marked as `#[test]` will somewhat
+    /// surprisingly not be run by `cargo test`. If you really want
+    /// to show how to test stuff in an example, mark it `no_run` to
+    /// make the intent clear.
+    ///
+    /// ### Examples
+    /// ```no_run
+    /// /// An example of a doctest with a `main()` function
+    /// ///
+    /// /// # Examples
+    /// ///
+    /// /// ```
+    /// /// #[test]
+    /// /// fn equality_works() {
+    /// ///     assert_eq!(1_u8, 1);
+    /// /// }
+    /// /// ```
+    /// fn test_attr_in_doctest() {
+    ///     unimplemented!();
+    /// }
+    /// ```
+    #[clippy::version = "1.40.0"]
+    pub TEST_ATTR_IN_DOCTEST,
+    suspicious,
+    "presence of `#[test]` in code examples"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Detects the syntax `['foo']` in documentation comments (notice quotes instead of backticks)
+    /// outside of code blocks
+    /// ### Why is this bad?
+    /// It is likely a typo when defining an intra-doc link
+    ///
+    /// ### Example
+    /// ```no_run
+    /// /// See also: ['foo']
+    /// fn bar() {}
+    /// ```
+    /// Use instead:
+    /// ```no_run
+    /// /// See also: [`foo`]
+    /// fn bar() {}
+    /// ```
+    #[clippy::version = "1.63.0"]
+    pub DOC_LINK_WITH_QUOTES,
+    pedantic,
+    "possible typo for an intra-doc link"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for the doc comments of publicly visible
+    /// safe functions and traits and warns if there is a `# Safety` section.
+    ///
+    /// ### Why is this bad?
+    /// Safe functions and traits are safe to implement and therefore do not
+    /// need to describe safety preconditions that users are required to uphold.
+    ///
+    /// ### Examples
+    /// ```no_run
+    ///# type Universe = ();
+    /// /// # Safety
+    /// ///
+    /// /// This function should not be called before the horsemen are ready.
+    /// pub fn start_apocalypse_but_safely(u: &mut Universe) {
+    ///     unimplemented!();
+    /// }
+    /// ```
+    ///
+    /// The function is safe, so there shouldn't be any preconditions
+    /// that have to be explained for safety reasons.
+    ///
+    /// ```no_run
+    ///# type Universe = ();
+    /// /// This function should really be documented
+    /// pub fn start_apocalypse(u: &mut Universe) {
+    ///     unimplemented!();
+    /// }
+    /// ```
+    #[clippy::version = "1.67.0"]
+    pub UNNECESSARY_SAFETY_DOC,
+    restriction,
+    "`pub fn` or `pub trait` with `# Safety` docs"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Detects the use of outer doc comments (`///`, `/**`) followed by a bang (`!`): `///!`
+    ///
+    /// ### Why is this bad?
+    /// Triple-slash comments (known as "outer doc comments") apply to items that follow it.
+    /// An outer doc comment followed by a bang (i.e. `///!`) has no specific meaning.
+    ///
+    /// The user most likely meant to write an inner doc comment (`//!`, `/*!`), which
+    /// applies to the parent item (i.e. the item that the comment is contained in,
+    /// usually a module or crate).
+    ///
+    /// ### Known problems
+    /// Inner doc comments can only appear before items, so there are certain cases where the suggestion
+    /// made by this lint is not valid code. For example:
+    /// ```rs
+    /// fn foo() {}
+    /// ///!
+    /// fn bar() {}
+    /// ```
+    /// This lint detects the doc comment and suggests changing it to `//!`, but an inner doc comment
+    /// is not valid at that position.
+    ///
+    /// ### Example
+    /// In this example, the doc comment is attached to the *function*, rather than the *module*.
+    /// ```no_run
+    /// pub mod util {
+    ///     ///! This module contains utility functions.
+    ///
+    ///     pub fn dummy() {}
+    /// }
+    /// ```
+    ///
+    /// Use instead:
+    /// ```no_run
+    /// pub mod util {
+    ///     //! This module contains utility functions.
+    ///
+    ///     pub fn dummy() {}
+    /// }
+    /// ```
+    #[clippy::version = "1.70.0"]
+    pub SUSPICIOUS_DOC_COMMENTS,
+    suspicious,
+    "suspicious usage of (outer) doc comments"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for fenced code blocks in documentation that rustdoc will not render as
+    /// intended: a fence that directly follows a list item or block quote but is not indented
+    /// enough to be part of it, and a fence nested in a code block whose enclosing fence is not
+    /// longer than the nested one.
+    ///
+    /// ### Why is this bad?
+    /// A code block that is not indented to line up with its list item ends the list, and a
+    /// nested fence that is as long as the enclosing one closes the enclosing code block early.
+    /// In both cases the rendered documentation differs from what the source suggests.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// /// - Call it like this:
+    /// /// ```
+    /// /// foo();
+    /// /// ```
+    /// fn foo() {}
+    /// ```
+    /// Use instead:
+    /// ```no_run
+    /// /// - Call it like this:
+    /// ///
+    /// ///   ```
+    /// ///   foo();
+    /// ///   ```
+    /// fn foo() {}
+    /// ```
+    #[clippy::version = "1.77.0"]
+    pub DOC_NESTED_FENCE,
+    pedantic,
+    "code fence in documentation that is not rendered as nested"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for markdown in documentation that pulldown-cmark accepts, but most likely does not
+    /// render as intended: code blocks that are never closed, headings that are missing the space
+    /// after their `#`s and inline links whose destination is never closed.
+    ///
+    /// ### Why is this bad?
+    /// Markdown has no syntax errors, so malformed markdown is rendered as something else without
+    /// any warning. An unclosed code block is particularly bad, as it swallows all the
+    /// documentation that follows it.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// /// #Examples
+    /// /// ```
+    /// /// foo();
+    /// fn foo() {}
+    /// ```
+    /// Use instead:
+    /// ```no_run
+    /// /// # Examples
+    /// /// ```
+    /// /// foo();
+    /// /// ```
+    /// fn foo() {}
+    /// ```
+    #[clippy::version = "1.77.0"]
+    pub DOC_MALFORMED_MARKDOWN,
+    pedantic,
+    "documentation markdown that is not rendered as intended"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for `use` and `extern crate` items in documentation examples that refer to the
+    /// documented crate under a name that is similar to, but not the same as, its actual name.
+    ///
+    /// ### Why is this bad?
+    /// Documentation examples are compiled as if they were in a separate crate depending on the
+    /// documented one, so they have to refer to it by its actual name. An example using a
+    /// different name fails to compile, or is silently not run if it is marked `no_run` or
+    /// `ignore`.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// //! ```
+    /// //! use my_crat::Foo;
+    /// //! ```
+    /// ```
+    /// Use instead:
+    /// ```no_run
+    /// //! ```
+    /// //! use my_crate::Foo;
+    /// //! ```
+    /// ```
+    #[clippy::version = "1.77.0"]
+    pub DOC_EXAMPLE_WRONG_CRATE_NAME,
+    pedantic,
+    "documentation example referring to the documented crate under a wrong name"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for inline code spans in documentation that contain prose rather than code, like a
+    /// sentence that was put in backticks by mistake.
+    ///
+    /// ### Why is this bad?
+    /// Text in backticks is rendered as code, which makes prose harder to read and suggests to the
+    /// reader that it is something they could write in their program.
+    ///
+    /// ### Known problems
+    /// To avoid false positives, only a run of plain words that can't be Rust code is linted.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// /// Returns `the number of elements`.
+    /// fn len() -> usize { 0 }
+    /// ```
+    /// Use instead:
+    /// ```no_run
+    /// /// Returns the number of elements.
+    /// fn len() -> usize { 0 }
+    /// ```
+    #[clippy::version = "1.77.0"]
+    pub DOC_NONSENSE_BACKTICKS,
+    pedantic,
+    "text in backticks in documentation that is not code"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for `#[doc(alias = "...")]` and `#[doc(alias("..."))]` attributes whose alias
+    /// contains a control character other than whitespace. Other invalid aliases, like empty ones
+    /// or ones containing quotes, are already rejected by the compiler.
+    ///
+    /// ### Why is this bad?
+    /// Such a character can't be typed into rustdoc's search, so a search can never match the
+    /// alias.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// #[doc(alias = "push\u{8}back")]
+    /// pub fn push() {}
+    /// ```
+    /// Use instead:
+    /// ```no_run
+    /// #[doc(alias = "push back")]
+    /// pub fn push() {}
+    /// ```
+    #[clippy::version = "1.77.0"]
+    pub DOC_INVALID_ALIAS,
+    suspicious,
+    "documentation alias that rustdoc's search can never find"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks the `# Errors` section of the documentation of functions returning `Result` for
+    /// whether it describes an error at all, that is whether it mentions `Err`, an error, a
+    /// failure or one of the variants of the error type if that is an enum of the crate.
+    ///
+    /// ### Why is this bad?
+    /// An `# Errors` section that doesn't describe when an error is returned is most likely
+    /// incomplete, or describes something else.
+    ///
+    /// ### Known problems
+    /// This is a heuristic: the section may describe the error conditions without using any of
+    /// these words.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// /// # Errors
+    /// ///
+    /// /// The file is read into memory.
+    /// pub fn read(path: &str) -> std::io::Result<Vec<u8>> {
+    ///     std::fs::read(path)
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```no_run
+    /// /// # Errors
+    /// ///
+    /// /// Returns an error if the file doesn't exist or can't be read.
+    /// pub fn read(path: &str) -> std::io::Result<Vec<u8>> {
+    ///     std::fs::read(path)
+    /// }
+    /// ```
+    #[clippy::version = "1.77.0"]
+    pub SUSPICIOUS_ERRORS_DOC,
+    pedantic,
+    "`# Errors` section in documentation that doesn't describe any error"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for documentation that starts with a code block rather than with prose.
+    ///
+    /// ### Why is this bad?
+    /// Rustdoc uses the first paragraph of the documentation as the summary of the item, shown
+    /// in the item lists of modules and in search results. If the documentation starts with a code
+    /// block, the summary is empty.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// /// ```
+    /// /// let _ = foo();
+    /// /// ```
+    /// /// Returns the answer.
+    /// fn foo() -> u32 { 42 }
+    /// ```
+    /// Use instead:
+    /// ```no_run
+    /// /// Returns the answer.
+    /// ///
+    /// /// ```
+    /// /// let _ = foo();
+    /// /// ```
+    /// fn foo() -> u32 { 42 }
+    /// ```
+    #[clippy::version = "1.77.0"]
+    pub DOC_CODE_BEFORE_SUMMARY,
+    pedantic,
+    "documentation starting with a code block instead of a summary"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for doc comments that don't use the style configured with `doc-comment-style`,
+    /// line doc comments (`///` and `//!`) by default.
+    ///
+    /// ### Why is this bad?
+    /// Using a single style for all documentation keeps the code base consistent.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// /** Returns the answer. */
+    /// fn answer() -> u32 { 42 }
+    /// ```
+    /// Use instead:
+    /// ```no_run
+    /// /// Returns the answer.
+    /// fn answer() -> u32 { 42 }
+    /// ```
+    #[clippy::version = "1.77.0"]
+    pub DOC_COMMENT_STYLE,
+    restriction,
+    "doc comments not using the configured style"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for public `unsafe fn`s whose body contains no unsafe block and performs no
+    /// operation that requires an unsafe context, like dereferencing a raw pointer or calling
+    /// another unsafe function.
+    ///
+    /// ### Why is this bad?
+    /// Callers have to use an unsafe block and uphold the documented safety requirements, even
+    /// though calling the function may not be able to cause undefined behavior at all.
+    ///
+    /// ### Known problems
+    /// A function can be unsafe without performing an unsafe operation itself, for example when
+    /// it sets up an invariant that other unsafe code relies on, like `Vec::set_len`.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// /// # Safety
+    /// ///
+    /// /// `b` must not be zero.
+    /// pub unsafe fn div(a: u32, b: u32) -> u32 {
+    ///     a / b
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```no_run
+    /// pub fn div(a: u32, b: u32) -> u32 {
+    ///     a / b
+    /// }
+    /// ```
+    #[clippy::version = "1.77.0"]
+    pub UNNECESSARY_UNSAFE_MARKER,
+    pedantic,
+    "`unsafe fn` that does not perform any unsafe operation"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for `assert_eq!` and `assert_ne!` invocations in documentation examples whose first
+    /// argument is a literal while the second one isn't.
+    ///
+    /// ### Why is this bad?
+    /// By convention, the value under test is passed first and the expected value second, which
+    /// is also the order in which the failure message of these macros lists them. Examples are
+    /// read and copied more than most code, so they should follow the convention.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// /// ```
+    /// /// assert_eq!(4, my_crate::add(2, 2));
+    /// /// ```
+    /// pub fn add(a: u32, b: u32) -> u32 {
+    ///     a + b
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```no_run
+    /// /// ```
+    /// /// assert_eq!(my_crate::add(2, 2), 4);
+    /// /// ```
+    /// pub fn add(a: u32, b: u32) -> u32 {
+    ///     a + b
+    /// }
+    /// ```
+    #[clippy::version = "1.77.0"]
+    pub DOC_EXAMPLE_ASSERT_ORDER,
+    restriction,
+    "`assert_eq!` in a documentation example with the expected value passed first"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for hidden lines, that is lines starting with `# `, in documentation examples that
+    /// have a syntax error.
+    ///
+    /// ### Why is this bad?
+    /// Hidden lines are compiled as part of the example, but aren't shown in the documentation.
+    /// The error makes the example fail to compile, and is hard to spot when reading the rendered
+    /// documentation.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// /// ```
+    /// /// # let x = ;
+    /// /// assert_eq!(x, 1);
+    /// /// ```
+    /// fn f() {}
+    /// ```
+    /// Use instead:
+    /// ```no_run
+    /// /// ```
+    /// /// # let x = 1;
+    /// /// assert_eq!(x, 1);
+    /// /// ```
+    /// fn f() {}
+    /// ```
+    #[clippy::version = "1.77.0"]
+    pub DOC_HIDDEN_LINE_ERROR,
+    pedantic,
+    "syntax error in a hidden line of a documentation example"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks the documentation of functions for identifiers in backticks that are one edit away
+    /// from the name of a parameter of the function.
+    ///
+    /// ### Why is this bad?
+    /// It is most likely a misspelling of the parameter name, which confuses readers of the
+    /// documentation.
+    ///
+    /// ### Known problems
+    /// An identifier in backticks that refers to something else, like a local variable of the
+    /// function, is linted too if its name is one edit away from the name of a parameter.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// /// Resizes the window to be `widht` pixels wide.
+    /// fn resize(width: u32) {}
+    /// ```
+    /// Use instead:
+    /// ```no_run
+    /// /// Resizes the window to be `width` pixels wide.
+    /// fn resize(width: u32) {}
+    /// ```
+    #[clippy::version = "1.77.0"]
+    pub DOC_UNKNOWN_PARAM,
+    pedantic,
+    "misspelled parameter name in the documentation of a function"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for the documentation of publicly visible items whose first paragraph has more
+    /// than one sentence, with a line break after the first sentence where the next word would
+    /// still have fit on the line.
+    ///
+    /// ### Why is this bad?
+    /// Rustdoc uses the whole first paragraph as the summary of the item, which is shown in
+    /// item listings and search results. A long summary renders awkwardly there, and the line
+    /// break after the first sentence suggests that the summary was meant to end there.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// /// Returns the answer.
+    /// /// It is computed very slowly.
+    /// fn answer() -> u32 { 42 }
+    /// ```
+    /// Use instead:
+    /// ```no_run
+    /// /// Returns the answer.
+    /// ///
+    /// /// It is computed very slowly.
+    /// fn answer() -> u32 { 42 }
+    /// ```
+    #[clippy::version = "1.77.0"]
+    pub DOC_MISSING_SUMMARY_BREAK,
+    pedantic,
+    "documentation summary not separated from the rest of the first paragraph"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for headings in documentation that are more than one level deeper than the
+    /// previous heading, like a `###` heading directly after a `#` heading.
+    ///
+    /// ### Why is this bad?
+    /// Skipping a heading level breaks the structure of the rendered documentation, and is often
+    /// a typo.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// /// # Examples
+    /// ///
+    /// /// ### Basic usage
+    /// fn f() {}
+    /// ```
+    /// Use instead:
+    /// ```no_run
+    /// /// # Examples
+    /// ///
+    /// /// ## Basic usage
+    /// fn f() {}
+    /// ```
+    #[clippy::version = "1.77.0"]
+    pub DOC_HEADING_JUMP,
+    pedantic,
+    "documentation heading more than one level deeper than the previous heading"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for paths starting with `std` in the documentation examples of `no_std` crates.
+    ///
+    /// ### Why is this bad?
+    /// The examples of a `no_std` crate should show how to use it without the standard library.
+    /// An example that uses `std` can't be compiled for targets that don't have it, and suggests
+    /// that the crate needs it.
+    ///
+    /// Examples marked `ignore` or `no_run` are only checked with the `check-not-run-examples`
+    /// configuration, and examples marked `compile_fail` are never checked.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// /// ```
+    /// /// let one: std::num::NonZeroU8 = my_crate::one();
+    /// /// ```
+    /// pub fn one() -> core::num::NonZeroU8 { core::num::NonZeroU8::MIN }
+    /// ```
+    /// Use instead:
+    /// ```no_run
+    /// /// ```
+    /// /// let one: core::num::NonZeroU8 = my_crate::one();
+    /// /// ```
+    /// pub fn one() -> core::num::NonZeroU8 { core::num::NonZeroU8::MIN }
+    /// ```
+    #[clippy::version = "1.77.0"]
+    pub DOC_EXAMPLE_STD_IN_NO_STD,
+    pedantic,
+    "use of `std` in a documentation example of a `no_std` crate"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for documentation examples that bind the result of a function call, but contain no
+    /// `assert!`, `assert_eq!` or `assert_ne!`.
+    ///
+    /// ### Why is this bad?
+    /// An example that asserts its result shows the reader what to expect, and is checked to stay
+    /// correct when it is run as a test.
+    ///
+    /// ### Known problems
+    /// Calls that are only made for their side effects, or that create a value to be used later,
+    /// are linted too unless their name looks like a constructor, like `new` or `with_capacity`.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// /// ```
+    /// /// let sum = my_crate::add(2, 2);
+    /// /// ```
+    /// pub fn add(a: u32, b: u32) -> u32 {
+    ///     a + b
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```no_run
+    /// /// ```
+    /// /// let sum = my_crate::add(2, 2);
+    /// /// assert_eq!(sum, 4);
+    /// /// ```
+    /// pub fn add(a: u32, b: u32) -> u32 {
+    ///     a + b
+    /// }
+    /// ```
+    #[clippy::version = "1.77.0"]
+    pub DOC_EXAMPLE_NO_ASSERTION,
+    restriction,
+    "documentation example that computes a value without asserting anything about it"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for doc comments and `#[doc]` attributes whose combined text is empty or only
+    /// whitespace. Items without any doc comment are not linted.
+    ///
+    /// ### Why is this bad?
+    /// An empty doc comment documents nothing, but still counts as documentation for lints like
+    /// `missing_docs`, hiding that the item is undocumented.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// ///
+    /// pub fn frobnicate() {}
+    /// ```
+    /// Use instead:
+    /// ```no_run
+    /// /// Frobnicates the global frobnicator.
+    /// pub fn frobnicate() {}
+    /// ```
+    #[clippy::version = "1.77.0"]
+    pub EMPTY_DOC_COMMENT,
+    pedantic,
+    "doc comment without any content"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for code blocks in documentation that are tagged as another language, but contain
+    /// code that parses as Rust without any error and declares an item or a `let` binding.
+    ///
+    /// ### Why is this bad?
+    /// The code block was likely meant to be a Rust example. Tagged as another language, it is
+    /// neither highlighted as Rust nor tested.
+    ///
+    /// ### Known problems
+    /// Code of languages with a syntax close to Rust, like `let x = 1;` in JavaScript, is linted.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// /// ```python
+    /// /// let sum = my_crate::add(2, 2);
+    /// /// ```
+    /// pub fn add(a: u32, b: u32) -> u32 {
+    ///     a + b
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```no_run
+    /// /// ```
+    /// /// let sum = my_crate::add(2, 2);
+    /// /// ```
+    /// pub fn add(a: u32, b: u32) -> u32 {
+    ///     a + b
+    /// }
+    /// ```
+    #[clippy::version = "1.77.0"]
+    pub DOC_MISTAGGED_RUST,
+    pedantic,
+    "code block tagged as another language that contains Rust code"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks the doc comments of publicly visible functions that return an `Option` and warns
+    /// if there is no `# Returns` section. The name of the heading can be configured with
+    /// `returns-none-doc-heading`.
+    ///
+    /// ### Why is this bad?
+    /// Callers need to know when `None` is returned to handle it appropriately, just like they
+    /// need to know the errors of a function returning a `Result`.
+    ///
+    /// ### Examples
+    /// ```no_run
+    /// /// Finds the first even number.
+    /// pub fn first_even(numbers: &[u32]) -> Option<u32> {
+    ///     numbers.iter().copied().find(|n| n % 2 == 0)
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```no_run
+    /// /// Finds the first even number.
+    /// ///
+    /// /// # Returns
+    /// ///
+    /// /// `None` if there is no even number in `numbers`.
+    /// pub fn first_even(numbers: &[u32]) -> Option<u32> {
+    ///     numbers.iter().copied().find(|n| n % 2 == 0)
+    /// }
+    /// ```
+    #[clippy::version = "1.77.0"]
+    pub MISSING_RETURNS_NONE_DOC,
+    restriction,
+    "`pub fn` returns `Option` without `# Returns` section in docs"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for doc comments whose whole text is a placeholder marker like `TODO` or `FIXME`.
+    /// The markers can be configured with `doc-placeholder-markers`.
+    ///
+    /// ### Why is this bad?
+    /// A placeholder documents nothing, but still counts as documentation for lints like
+    /// `missing_docs`, hiding that the item is undocumented.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// /// TODO
+    /// pub fn frobnicate() {}
+    /// ```
+    /// Use instead:
+    /// ```no_run
+    /// /// Frobnicates the global frobnicator.
+    /// pub fn frobnicate() {}
+    /// ```
+    #[clippy::version = "1.77.0"]
+    pub PLACEHOLDER_DOC,
+    pedantic,
+    "doc comment that only consists of a placeholder like `TODO`"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks the doc comments of publicly visible functions for an `# Examples` section or a
+    /// fenced code block. Functions without any doc comment are not linted.
+    ///
+    /// ### Why is this bad?
+    /// Examples are the quickest way for users to learn how to use a function, and are
+    /// tested as doctests.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// /// Adds one to the number.
+    /// pub fn add_one(x: i32) -> i32 {
+    ///     x + 1
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```no_run
+    /// /// Adds one to the number.
+    /// ///
+    /// /// # Examples
+    /// ///
+    /// /// ```
+    /// /// assert_eq!(add_one(1), 2);
+    /// /// ```
+    /// pub fn add_one(x: i32) -> i32 {
+    ///     x + 1
+    /// }
+    /// ```
+    #[clippy::version = "1.77.0"]
+    pub MISSING_EXAMPLES_DOC,
+    pedantic,
+    "`pub fn` docs without an `# Examples` section or code block"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for documentation examples using `#[deprecated]` items of the documented crate.
+    /// Only paths starting with the name of the crate are checked, and re-exports are not
+    /// followed. Examples marked `no_run` or `compile_fail` are not checked.
+    ///
+    /// ### Why is this bad?
+    /// Examples show users how to use the crate. An example using a deprecated item teaches
+    /// them to use an API they should move away from.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// /// ```
+    /// /// my_crate::old_frobnicate();
+    /// /// ```
+    /// #[deprecated = "use `frobnicate` instead"]
+    /// pub fn old_frobnicate() {}
+    /// ```
+    /// Use instead:
+    /// ```no_run
+    /// /// ```
+    /// /// my_crate::frobnicate();
+    /// /// ```
+    /// #[deprecated = "use `frobnicate` instead"]
+    /// pub fn old_frobnicate() {}
+    /// ```
+    #[clippy::version = "1.77.0"]
+    pub DOC_EXAMPLE_DEPRECATED,
+    pedantic,
+    "documentation example using a deprecated item of the documented crate"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for `\n` in the text of doc comments, outside of code.
+    ///
+    /// ### Why is this bad?
+    /// Doc comments are not string literals, so `\n` is not an escape sequence. It is rendered
+    /// as is instead of starting a new line.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// /// Frobnicates the frobnicator.\nReturns the number of frobnications.
+    /// pub fn frobnicate() -> u32 { 0 }
+    /// ```
+    /// Use instead:
+    /// ```no_run
+    /// /// Frobnicates the frobnicator.
+    /// /// Returns the number of frobnications.
+    /// pub fn frobnicate() -> u32 { 0 }
+    /// ```
+    #[clippy::version = "1.77.0"]
+    pub DOC_LITERAL_NEWLINE_ESCAPE,
+    pedantic,
+    "`\\n` in doc comments, which is not a newline"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for `unsafe` blocks in documentation examples without a comment on the same line or
+    /// the line before explaining them.
+    ///
+    /// ### Why is this bad?
+    /// Readers learn how to use the crate from its examples. An `unsafe` block without an
+    /// explanation suggests that using it is routine, and doesn't tell what makes it sound.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// /// ```
+    /// /// let bytes = b"hello";
+    /// /// let s = unsafe { std::str::from_utf8_unchecked(bytes) };
+    /// /// ```
+    /// pub fn hello() {}
+    /// ```
+    /// Use instead:
+    /// ```no_run
+    /// /// ```
+    /// /// let bytes = b"hello";
+    /// /// // SAFETY: the bytes are ASCII, which is valid UTF-8.
+    /// /// let s = unsafe { std::str::from_utf8_unchecked(bytes) };
+    /// /// ```
+    /// pub fn hello() {}
+    /// ```
+    #[clippy::version = "1.77.0"]
+    pub DOC_EXAMPLE_UNEXPLAINED_UNSAFE,
+    restriction,
+    "`unsafe` block in a documentation example without a comment explaining it"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for fenced Rust code blocks in documentation without an edition tag like
+    /// `edition2021`.
+    ///
+    /// ### Why is this bad?
+    /// An example without an edition tag is compiled with the edition of the crate, so its
+    /// behavior can change when the crate moves to a new edition. Some crates want each example to
+    /// state the edition it demonstrates.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// /// ```rust
+    /// /// let array = [1, 2, 3];
+    /// /// for n in array.into_iter() {}
+    /// /// ```
+    /// pub fn array() {}
+    /// ```
+    /// Use instead:
+    /// ```no_run
+    /// /// ```rust,edition2021
+    /// /// let array = [1, 2, 3];
+    /// /// for n in array.into_iter() {}
+    /// /// ```
+    /// pub fn array() {}
+    /// ```
+    #[clippy::version = "1.77.0"]
+    pub DOC_EXAMPLE_UNSPECIFIED_EDITION,
+    restriction,
+    "documentation example without an edition tag"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks the documentation of functions for identifiers in backticks that are documented as
+    /// parameters, but aren't the name of any parameter of the function. An identifier is
+    /// documented as a parameter if it starts an item of a list in an `# Arguments` or
+    /// `# Parameters` section, or if it is directly followed by "parameter" or "argument".
+    ///
+    /// ### Why is this bad?
+    /// The parameter was most likely renamed or removed without updating the documentation,
+    /// which confuses readers of the documentation.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// /// Resizes the window.
+    /// ///
+    /// /// # Arguments
+    /// ///
+    /// /// * `size` - the new width in pixels
+    /// pub fn resize(width: u32) {}
+    /// ```
+    /// Use instead:
+    /// ```no_run
+    /// /// Resizes the window.
+    /// ///
+    /// /// # Arguments
+    /// ///
+    /// /// * `width` - the new width in pixels
+    /// pub fn resize(width: u32) {}
+    /// ```
+    #[clippy::version = "1.77.0"]
+    pub DOC_NONEXISTENT_PARAM,
+    pedantic,
+    "documentation of a parameter the function doesn't have"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks the documentation of free functions for `self` or `Self` in backticks. The
+    /// documentation of a function referring to a method, like `Vec::reserve`, is not checked, as
+    /// `self` is likely the receiver of that method.
+    ///
+    /// ### Why is this bad?
+    /// A free function has neither a receiver nor a `Self` type, so the documentation was most
+    /// likely copied from a method and not adapted.
     ///
+    /// ### Example
     /// ```no_run
-    /// /// # Panics
-    /// ///
-    /// /// Will panic if y is 0
-    /// pub fn divide_by(x: i32, y: i32) -> i32 {
-    ///     if y == 0 {
-    ///         panic!("Cannot divide by 0")
-    ///     } else {
-    ///         x / y
-    ///     }
+    /// /// Returns the length of `self`.
+    /// pub fn len(s: &str) -> usize {
+    ///     s.len()
     /// }
     /// ```
-    #[clippy::version = "1.51.0"]
-    pub MISSING_PANICS_DOC,
+    /// Use instead:
+    /// ```no_run
+    /// /// Returns the length of `s`.
+    /// pub fn len(s: &str) -> usize {
+    ///     s.len()
+    /// }
+    /// ```
+    #[clippy::version = "1.77.0"]
+    pub DOC_IRRELEVANT_SELF,
     pedantic,
-    "`pub fn` may panic without `# Panics` in doc comment"
+    "`self` or `Self` in the documentation of a free function"
 }
 
 declare_clippy_lint! {
     /// ### What it does
-    /// Checks for `fn main() { .. }` in doctests
+    /// Checks for documentation with more than one heading of a section like `# Examples` or
+    /// `# Panics`.
     ///
     /// ### Why is this bad?
-    /// The test can be shorter (and likely more readable)
-    /// if the `fn main()` is left implicit.
+    /// Rustdoc renders both sections, which is confusing. A duplicate section is usually left over
+    /// from merging changes to the documentation.
     ///
-    /// ### Examples
+    /// ### Example
     /// ```no_run
-    /// /// An example of a doctest with a `main()` function
+    /// /// # Panics
     /// ///
-    /// /// # Examples
+    /// /// Panics if `b` is zero.
     /// ///
-    /// /// ```
-    /// /// fn main() {
-    /// ///     // this needs not be in an `fn`
-    /// /// }
-    /// /// ```
-    /// fn needless_main() {
-    ///     unimplemented!();
+    /// /// # Panics
+    /// ///
+    /// /// Panics if the division overflows.
+    /// pub fn div(a: i32, b: i32) -> i32 {
+    ///     a / b
     /// }
     /// ```
-    #[clippy::version = "1.40.0"]
-    pub NEEDLESS_DOCTEST_MAIN,
-    style,
-    "presence of `fn main() {` in code examples"
+    /// Use instead:
+    /// ```no_run
+    /// /// # Panics
+    /// ///
+    /// /// Panics if `b` is zero or if the division overflows.
+    /// pub fn div(a: i32, b: i32) -> i32 {
+    ///     a / b
+    /// }
+    /// ```
+    #[clippy::version = "1.77.0"]
+    pub DOC_DUPLICATE_SECTION,
+    pedantic,
+    "section heading appearing more than once in the documentation of an item"
 }
 
 declare_clippy_lint! {
     /// ### What it does
-    /// Checks for `#[test]` in doctests unless they are marked with
-    /// either `ignore`, `no_run` or `compile_fail`.
+    /// Checks for `static mut` items in documentation examples.
     ///
     /// ### Why is this bad?
-    /// Code in examples marked as `#[test]` will somewhat
-    /// surprisingly not be run by `cargo test`. If you really want
-    /// to show how to test stuff in an example, mark it `no_run` to
-    /// make the intent clear.
+    /// Readers copy patterns from examples. Accessing a `static mut` is `unsafe` and easily
+    /// unsound, a regular `static` of an atomic type or a `Mutex` is almost always what should be
+    /// shown instead.
     ///
-    /// ### Examples
+    /// Only the declarations are checked, a `static mut` of the documented crate used by an example
+    /// is not linted.
+    ///
+    /// ### Example
     /// ```no_run
-    /// /// An example of a doctest with a `main()` function
-    /// ///
-    /// /// # Examples
-    /// ///
     /// /// ```
-    /// /// #[test]
-    /// /// fn equality_works() {
-    /// ///     assert_eq!(1_u8, 1);
-    /// /// }
+    /// /// static mut COUNT: u32 = 0;
+    /// /// unsafe { COUNT += 1 };
     /// /// ```
-    /// fn test_attr_in_doctest() {
-    ///     unimplemented!();
-    /// }
+    /// pub fn count() {}
     /// ```
-    #[clippy::version = "1.40.0"]
-    pub TEST_ATTR_IN_DOCTEST,
-    suspicious,
-    "presence of `#[test]` in code examples"
+    /// Use instead:
+    /// ```no_run
+    /// /// ```
+    /// /// use std::sync::atomic::{AtomicU32, Ordering};
+    /// /// static COUNT: AtomicU32 = AtomicU32::new(0);
+    /// /// COUNT.fetch_add(1, Ordering::Relaxed);
+    /// /// ```
+    /// pub fn count() {}
+    /// ```
+    #[clippy::version = "1.77.0"]
+    pub DOC_EXAMPLE_STATIC_MUT,
+    restriction,
+    "`static mut` item in a documentation example"
 }
 
 declare_clippy_lint! {
     /// ### What it does
-    /// Detects the syntax `['foo']` in documentation comments (notice quotes instead of backticks)
-    /// outside of code blocks
+    /// Checks for code blocks in documentation that are tagged as `text` or `plain`, but contain
+    /// code that parses as Rust without any error and declares an item or a `let` binding.
+    ///
     /// ### Why is this bad?
-    /// It is likely a typo when defining an intra-doc link
+    /// Rust code is sometimes tagged as text just to have it rendered. Unlike a Rust example, it is
+    /// not tested, so it may stop compiling without anyone noticing.
     ///
     /// ### Example
     /// ```no_run
-    /// /// See also: ['foo']
-    /// fn bar() {}
+    /// /// ```text
+    /// /// let sum = my_crate::add(2, 2);
+    /// /// ```
+    /// pub fn add(a: u32, b: u32) -> u32 {
+    ///     a + b
+    /// }
     /// ```
     /// Use instead:
     /// ```no_run
-    /// /// See also: [`foo`]
-    /// fn bar() {}
+    /// /// ```
+    /// /// let sum = my_crate::add(2, 2);
+    /// /// ```
+    /// pub fn add(a: u32, b: u32) -> u32 {
+    ///     a + b
+    /// }
     /// ```
-    #[clippy::version = "1.63.0"]
-    pub DOC_LINK_WITH_QUOTES,
+    #[clippy::version = "1.77.0"]
+    pub DOC_RUST_IN_TEXT_BLOCK,
     pedantic,
-    "possible typo for an intra-doc link"
+    "code block tagged as text that contains Rust code"
 }
 
 declare_clippy_lint! {
     /// ### What it does
-    /// Checks for the doc comments of publicly visible
-    /// safe functions and traits and warns if there is a `# Safety` section.
+    /// Checks the doc comments of publicly visible functions for the sections configured with
+    /// `required-doc-sections`, and warns if one is missing or empty. Nothing is checked without
+    /// this configuration.
     ///
     /// ### Why is this bad?
-    /// Safe functions and traits are safe to implement and therefore do not
-    /// need to describe safety preconditions that users are required to uphold.
+    /// Some projects document more aspects of every function than its safety, errors and panics,
+    /// like the locks it takes or its complexity, and want to do so consistently.
     ///
-    /// ### Examples
+    /// ### Example
+    /// With `required-doc-sections = ["Complexity"]`:
     /// ```no_run
-    ///# type Universe = ();
-    /// /// # Safety
+    /// /// Sorts the slice.
+    /// pub fn sort(v: &mut [u32]) {
+    ///     v.sort();
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```no_run
+    /// /// Sorts the slice.
     /// ///
-    /// /// This function should not be called before the horsemen are ready.
-    /// pub fn start_apocalypse_but_safely(u: &mut Universe) {
-    ///     unimplemented!();
+    /// /// # Complexity
+    /// ///
+    /// /// O(n * log(n)) in the length of the slice.
+    /// pub fn sort(v: &mut [u32]) {
+    ///     v.sort();
     /// }
     /// ```
+    #[clippy::version = "1.77.0"]
+    pub MISSING_REQUIRED_DOC_SECTION,
+    style,
+    "`pub fn` without a section required by the configuration in doc comment"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for documentation examples that write to stderr, with `eprint!`, `eprintln!` or a
+    /// call of `io::stderr()`.
     ///
-    /// The function is safe, so there shouldn't be any preconditions
-    /// that have to be explained for safety reasons.
+    /// ### Why is this bad?
+    /// Depending on how the doctests are run, the output of an example on stderr may not be
+    /// captured with the rest of its output, which is confusing when the example demonstrates it.
     ///
+    /// ### Example
     /// ```no_run
-    ///# type Universe = ();
-    /// /// This function should really be documented
-    /// pub fn start_apocalypse(u: &mut Universe) {
-    ///     unimplemented!();
+    /// /// ```
+    /// /// eprintln!("{}", my_crate::greeting());
+    /// /// ```
+    /// pub fn greeting() -> &'static str {
+    ///     "Hello"
     /// }
     /// ```
-    #[clippy::version = "1.67.0"]
-    pub UNNECESSARY_SAFETY_DOC,
+    /// Use instead:
+    /// ```no_run
+    /// /// ```
+    /// /// println!("{}", my_crate::greeting());
+    /// /// ```
+    /// pub fn greeting() -> &'static str {
+    ///     "Hello"
+    /// }
+    /// ```
+    #[clippy::version = "1.77.0"]
+    pub DOC_EXAMPLE_STDERR,
     restriction,
-    "`pub fn` or `pub trait` with `# Safety` docs"
+    "documentation example writing to stderr"
 }
 
 declare_clippy_lint! {
     /// ### What it does
-    /// Detects the use of outer doc comments (`///`, `/**`) followed by a bang (`!`): `///!`
+    /// Checks for rows of a markdown table in documentation that are missing the separator row
+    /// after their header, like `| --- | --- |`. Only rows starting and ending with a `|` are
+    /// recognized.
     ///
     /// ### Why is this bad?
-    /// Triple-slash comments (known as "outer doc comments") apply to items that follow it.
-    /// An outer doc comment followed by a bang (i.e. `///!`) has no specific meaning.
-    ///
-    /// The user most likely meant to write an inner doc comment (`//!`, `/*!`), which
-    /// applies to the parent item (i.e. the item that the comment is contained in,
-    /// usually a module or crate).
+    /// Without the separator row, the rows are not a table and are rendered as a single line of
+    /// text, pipes included.
     ///
-    /// ### Known problems
-    /// Inner doc comments can only appear before items, so there are certain cases where the suggestion
-    /// made by this lint is not valid code. For example:
-    /// ```rs
-    /// fn foo() {}
-    /// ///!
-    /// fn bar() {}
+    /// ### Example
+    /// ```no_run
+    /// /// | Input | Output |
+    /// /// | 0     | 1      |
+    /// /// | 1     | 2      |
+    /// fn successor(n: u32) -> u32 { n + 1 }
     /// ```
-    /// This lint detects the doc comment and suggests changing it to `//!`, but an inner doc comment
-    /// is not valid at that position.
+    /// Use instead:
+    /// ```no_run
+    /// /// | Input | Output |
+    /// /// | ----- | ------ |
+    /// /// | 0     | 1      |
+    /// /// | 1     | 2      |
+    /// fn successor(n: u32) -> u32 { n + 1 }
+    /// ```
+    #[clippy::version = "1.77.0"]
+    pub DOC_MALFORMED_TABLE,
+    pedantic,
+    "markdown table in documentation missing the separator row after its header"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for paragraphs of documentation with a backtick that doesn't start or end an inline
+    /// code span. Backticks escaped with a backslash and the ones in code blocks are ignored.
+    ///
+    /// ### Why is this bad?
+    /// A missing or stray backtick is most likely a typo. Depending on where it is, rustdoc renders
+    /// a large part of the paragraph as code, or the backtick as is.
     ///
     /// ### Example
-    /// In this example, the doc comment is attached to the *function*, rather than the *module*.
     /// ```no_run
-    /// pub mod util {
-    ///     ///! This module contains utility functions.
-    ///
-    ///     pub fn dummy() {}
-    /// }
+    /// /// Returns the `Foo value of the item.
+    /// fn foo() {}
     /// ```
-    ///
     /// Use instead:
     /// ```no_run
-    /// pub mod util {
-    ///     //! This module contains utility functions.
-    ///
-    ///     pub fn dummy() {}
-    /// }
+    /// /// Returns the `Foo` value of the item.
+    /// fn foo() {}
     /// ```
-    #[clippy::version = "1.70.0"]
-    pub SUSPICIOUS_DOC_COMMENTS,
-    suspicious,
-    "suspicious usage of (outer) doc comments"
+    #[clippy::version = "1.77.0"]
+    pub DOC_UNBALANCED_BACKTICKS,
+    style,
+    "paragraph of documentation with unbalanced backticks"
 }
 
 #[derive(Clone)]
+#[expect(clippy::struct_excessive_bools)]
 pub struct Documentation {
     valid_idents: FxHashSet<String>,
     in_trait_impl: bool,
     check_private_items: bool,
+    ignore_unreachable_panics: bool,
+    ignore_debug_only_panics: bool,
+    doc_comment_style: DocCommentStyle,
+    check_doc_attributes: bool,
+    doc_tick_keywords: bool,
+    returns_none_doc_heading: String,
+    doc_placeholder_markers: Vec<String>,
+    required_doc_sections: Vec<String>,
+    check_not_run_examples: bool,
 }
 
 impl Documentation {
-    pub fn new(valid_idents: &[String], check_private_items: bool) -> Self {
+    #[expect(clippy::too_many_arguments, clippy::fn_params_excessive_bools)]
+    pub fn new(
+        valid_idents: &[String],
+        check_private_items: bool,
+        ignore_unreachable_panics: bool,
+        ignore_debug_only_panics: bool,
+        doc_comment_style: DocCommentStyle,
+        check_doc_attributes: bool,
+        doc_tick_keywords: bool,
+        returns_none_doc_heading: &str,
+        doc_placeholder_markers: &[String],
+        required_doc_sections: &[String],
+        check_not_run_examples: bool,
+    ) -> Self {
         Self {
             valid_idents: valid_idents.iter().cloned().collect(),
             in_trait_impl: false,
             check_private_items,
+            ignore_unreachable_panics,
+            ignore_debug_only_panics,
+            doc_comment_style,
+            check_doc_attributes,
+            doc_tick_keywords,
+            returns_none_doc_heading: returns_none_doc_heading.to_owned(),
+            doc_placeholder_markers: doc_placeholder_markers.to_vec(),
+            required_doc_sections: required_doc_sections.to_vec(),
+            check_not_run_examples,
         }
     }
+
+    /// Finds the first possible panic in the body of the function `owner`, preferring one that
+    /// doesn't depend on debug assertions.
+    fn find_panic<'tcx>(&self, cx: &LateContext<'tcx>, owner: hir::OwnerId, body: &'tcx Expr<'tcx>) -> Option<Panic> {
+        let typeck_results = cx.tcx.typeck(owner);
+        FindPanicUnwrap::find(cx, typeck_results, body, self.ignore_unreachable_panics)
+            .filter(|panic| !(panic.debug_only && self.ignore_debug_only_panics))
+            .or_else(|| {
+                find_forwarded_panic(cx, typeck_results, body).map(|span| Panic {
+                    span,
+                    debug_only: false,
+                })
+            })
+    }
+}
+
+/// A possible panic in the body of a function.
+#[derive(Clone, Copy)]
+struct Panic {
+    span: Span,
+    /// Whether the panic can only happen with debug assertions enabled, e.g. in a `debug_assert!`.
+    debug_only: bool,
 }
 
 impl_lint_pass!(Documentation => [
@@ -364,48 +1531,94 @@ pub fn new(valid_idents: &[String], check_private_items: bool) -> Self {
     NEEDLESS_DOCTEST_MAIN,
     TEST_ATTR_IN_DOCTEST,
     UNNECESSARY_SAFETY_DOC,
-    SUSPICIOUS_DOC_COMMENTS
+    SUSPICIOUS_DOC_COMMENTS,
+    DOC_NESTED_FENCE,
+    DOC_MALFORMED_MARKDOWN,
+    DOC_EXAMPLE_WRONG_CRATE_NAME,
+    DOC_NONSENSE_BACKTICKS,
+    DOC_INVALID_ALIAS,
+    SUSPICIOUS_ERRORS_DOC,
+    DOC_CODE_BEFORE_SUMMARY,
+    DOC_COMMENT_STYLE,
+    UNNECESSARY_UNSAFE_MARKER,
+    DOC_EXAMPLE_ASSERT_ORDER,
+    DOC_HIDDEN_LINE_ERROR,
+    DOC_UNKNOWN_PARAM,
+    DOC_MISSING_SUMMARY_BREAK,
+    DOC_HEADING_JUMP,
+    DOC_EXAMPLE_STD_IN_NO_STD,
+    DOC_EXAMPLE_NO_ASSERTION,
+    EMPTY_DOC_COMMENT,
+    DOC_MISTAGGED_RUST,
+    MISSING_RETURNS_NONE_DOC,
+    PLACEHOLDER_DOC,
+    MISSING_EXAMPLES_DOC,
+    DOC_EXAMPLE_DEPRECATED,
+    DOC_LITERAL_NEWLINE_ESCAPE,
+    DOC_EXAMPLE_UNEXPLAINED_UNSAFE,
+    DOC_EXAMPLE_UNSPECIFIED_EDITION,
+    DOC_NONEXISTENT_PARAM,
+    DOC_IRRELEVANT_SELF,
+    DOC_DUPLICATE_SECTION,
+    DOC_EXAMPLE_STATIC_MUT,
+    DOC_RUST_IN_TEXT_BLOCK,
+    MISSING_REQUIRED_DOC_SECTION,
+    DOC_EXAMPLE_STDERR,
+    DOC_MALFORMED_TABLE,
+    DOC_UNBALANCED_BACKTICKS
 ]);
 
 impl<'tcx> LateLintPass<'tcx> for Documentation {
     fn check_crate(&mut self, cx: &LateContext<'tcx>) {
         let attrs = cx.tcx.hir().attrs(hir::CRATE_HIR_ID);
-        check_attrs(cx, &self.valid_idents, attrs);
+        check_attrs(cx, self, attrs);
     }
 
     fn check_item(&mut self, cx: &LateContext<'tcx>, item: &'tcx hir::Item<'_>) {
         let attrs = cx.tcx.hir().attrs(item.hir_id());
-        let Some(headers) = check_attrs(cx, &self.valid_idents, attrs) else {
+        let Some(headers) = check_attrs(cx, self, attrs) else {
             return;
         };
+        missing_summary_break::check(cx, item.owner_id, &headers, self.check_private_items);
         match item.kind {
             hir::ItemKind::Fn(ref sig, _, body_id) => {
                 if !(is_entrypoint_fn(cx, item.owner_id.to_def_id()) || in_external_macro(cx.tcx.sess, item.span)) {
                     let body = cx.tcx.hir().body(body_id);
 
-                    let panic_span = FindPanicUnwrap::find_span(cx, cx.tcx.typeck(item.owner_id), body.value);
+                    let panic = self.find_panic(cx, item.owner_id, body.value);
+                    unknown_param::check(cx, item.owner_id, &headers);
+                    irrelevant_self::check(cx, &headers);
                     missing_headers::check(
                         cx,
                         item.owner_id,
                         sig,
-                        headers,
+                        &headers,
                         Some(body_id),
-                        panic_span,
+                        panic,
                         self.check_private_items,
                     );
+                    unnecessary_unsafe_marker::check(cx, item.owner_id, sig, body_id);
                 }
             },
             hir::ItemKind::Impl(impl_) => {
                 self.in_trait_impl = impl_.of_trait.is_some();
             },
-            hir::ItemKind::Trait(_, unsafety, ..) => match (headers.safety, unsafety) {
-                (false, hir::Unsafety::Unsafe) => span_lint(
+            hir::ItemKind::Trait(_, unsafety, ..) => match (headers.section("Safety"), unsafety) {
+                (Section::Absent, hir::Unsafety::Unsafe) => span_lint(
                     cx,
                     MISSING_SAFETY_DOC,
                     cx.tcx.def_span(item.owner_id),
                     "docs for unsafe trait missing `# Safety` section",
                 ),
-                (true, hir::Unsafety::Normal) => span_lint(
+                (Section::Empty(heading), hir::Unsafety::Unsafe) => span_lint_and_note(
+                    cx,
+                    MISSING_SAFETY_DOC,
+                    cx.tcx.def_span(item.owner_id),
+                    "docs for unsafe trait have an empty `# Safety` section",
+                    Some(heading),
+                    "the section has no content",
+                ),
+                (Section::Empty(_) | Section::Present, hir::Unsafety::Normal) => span_lint(
                     cx,
                     UNNECESSARY_SAFETY_DOC,
                     cx.tcx.def_span(item.owner_id),
@@ -425,37 +1638,42 @@ fn check_item_post(&mut self, _cx: &LateContext<'tcx>, item: &'tcx hir::Item<'_>
 
     fn check_trait_item(&mut self, cx: &LateContext<'tcx>, item: &'tcx hir::TraitItem<'_>) {
         let attrs = cx.tcx.hir().attrs(item.hir_id());
-        let Some(headers) = check_attrs(cx, &self.valid_idents, attrs) else {
+        let Some(headers) = check_attrs(cx, self, attrs) else {
             return;
         };
+        missing_summary_break::check(cx, item.owner_id, &headers, self.check_private_items);
         if let hir::TraitItemKind::Fn(ref sig, ..) = item.kind {
             if !in_external_macro(cx.tcx.sess, item.span) {
-                missing_headers::check(cx, item.owner_id, sig, headers, None, None, self.check_private_items);
+                unknown_param::check(cx, item.owner_id, &headers);
+                missing_headers::check(cx, item.owner_id, sig, &headers, None, None, self.check_private_items);
             }
         }
     }
 
     fn check_impl_item(&mut self, cx: &LateContext<'tcx>, item: &'tcx hir::ImplItem<'_>) {
         let attrs = cx.tcx.hir().attrs(item.hir_id());
-        let Some(headers) = check_attrs(cx, &self.valid_idents, attrs) else {
+        let Some(headers) = check_attrs(cx, self, attrs) else {
             return;
         };
         if self.in_trait_impl || in_external_macro(cx.tcx.sess, item.span) {
             return;
         }
+        missing_summary_break::check(cx, item.owner_id, &headers, self.check_private_items);
         if let hir::ImplItemKind::Fn(ref sig, body_id) = item.kind {
             let body = cx.tcx.hir().body(body_id);
 
-            let panic_span = FindPanicUnwrap::find_span(cx, cx.tcx.typeck(item.owner_id), body.value);
+            let panic = self.find_panic(cx, item.owner_id, body.value);
+            unknown_param::check(cx, item.owner_id, &headers);
             missing_headers::check(
                 cx,
                 item.owner_id,
                 sig,
-                headers,
+                &headers,
                 Some(body_id),
-                panic_span,
+                panic,
                 self.check_private_items,
             );
+            unnecessary_unsafe_marker::check(cx, item.owner_id, sig, body_id);
         }
     }
 }
@@ -472,11 +1690,103 @@ fn span(self, cx: &LateContext<'_>, range: Range<usize>) -> Option<Span> {
     }
 }
 
-#[derive(Copy, Clone, Default)]
+/// How rustdoc treats the code of an example.
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum ExampleKind {
+    /// The example is compiled and run.
+    Run,
+    /// The example is marked `no_run`, it is compiled but not run.
+    NoRun,
+    /// The example is marked `compile_fail`, it is expected not to compile.
+    CompileFail,
+    /// The example is marked `ignore`, it is not compiled.
+    Ignore,
+}
+
+/// Returns whether the markdown source in `range` contains a backtick that is not escaped with a
+/// backslash. Escaped backticks are rendered as text, so they can't start or end an inline code
+/// span.
+fn has_unescaped_backtick(doc: &str, range: Range<usize>) -> bool {
+    doc[range.clone()]
+        .match_indices('`')
+        .any(|(i, _)| !doc[..range.start + i].ends_with('\\'))
+}
+
+#[derive(Clone, Default)]
 struct DocHeaders {
-    safety: bool,
-    errors: bool,
-    panics: bool,
+    /// The sections whose content is checked, by heading. `# Implementation safety` sections are
+    /// stored as `Safety`.
+    sections: BTreeMap<String, Section>,
+    /// Whether the section describing when an `Option` is `None` was found, see
+    /// [`MISSING_RETURNS_NONE_DOC`].
+    returns: bool,
+    /// Whether an `# Examples` section or a fenced code block was found, see
+    /// [`MISSING_EXAMPLES_DOC`].
+    examples: bool,
+    errors_section: Option<ErrorsSection>,
+    /// The inline code spans outside of headings that could be the name of a parameter.
+    param_like_code: Vec<(String, Span)>,
+    /// The inline code spans that are documented as a parameter, see [`DOC_NONEXISTENT_PARAM`].
+    documented_params: Vec<(String, Span)>,
+    /// The inline code spans outside of headings that are `self` or `Self`, see
+    /// [`DOC_IRRELEVANT_SELF`].
+    self_code: Vec<(String, Span)>,
+    /// Whether an inline code span is the path of a method, see [`DOC_IRRELEVANT_SELF`].
+    mentions_method: bool,
+    /// The first line of the documentation, if the rest of the first paragraph follows it without
+    /// a blank line, see [`DOC_MISSING_SUMMARY_BREAK`].
+    summary_line: Option<Span>,
+}
+
+impl DocHeaders {
+    /// Creates the headers of documentation in which the sections of [`CHECKED_SECTIONS`] and the
+    /// `required` ones are checked.
+    fn new(required: &[String]) -> Self {
+        let sections = CHECKED_SECTIONS
+            .iter()
+            .map(|&name| name.to_owned())
+            .chain(required.iter().cloned())
+            .map(|name| (name, Section::Absent))
+            .collect();
+        Self {
+            sections,
+            ..Self::default()
+        }
+    }
+
+    /// The section with the heading `heading`, if it is one whose content is checked.
+    fn section_mut(&mut self, heading: &str) -> Option<&mut Section> {
+        let name = match heading {
+            "Implementation safety" | "Implementation Safety" => "Safety",
+            _ => heading,
+        };
+        self.sections.get_mut(name)
+    }
+
+    /// The section named `name`, `Absent` if it isn't checked.
+    fn section(&self, name: &str) -> Section {
+        self.sections.get(name).copied().unwrap_or_default()
+    }
+}
+
+/// Whether a section of the documentation was found, and whether there is anything under its
+/// heading before the next heading.
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+enum Section {
+    #[default]
+    Absent,
+    /// The section only has a heading, with the given span.
+    Empty(Span),
+    Present,
+}
+
+/// The `# Errors` section of the documentation.
+#[derive(Clone)]
+struct ErrorsSection {
+    /// The span of the heading.
+    span: Span,
+    /// The text of the section, including inline code.
+    text: String,
 }
 
 /// Does some pre-processing on raw, desugared `#[doc]` attributes such as parsing them and
@@ -486,7 +1796,7 @@ struct DocHeaders {
 /// Others are checked elsewhere, e.g. in `check_doc` if they need access to markdown, or
 /// back in the various late lint pass methods if they need the final doc headers, like "Safety" or
 /// "Panics" sections.
-fn check_attrs(cx: &LateContext<'_>, valid_idents: &FxHashSet<String>, attrs: &[Attribute]) -> Option<DocHeaders> {
+fn check_attrs(cx: &LateContext<'_>, conf: &Documentation, attrs: &[Attribute]) -> Option<DocHeaders> {
     /// We don't want the parser to choke on intra doc links. Since we don't
     /// actually care about rendering them, just pretend that all broken links
     /// point to a fake address.
@@ -500,6 +1810,8 @@ fn fake_broken_link_callback<'a>(_: BrokenLink<'_>) -> Option<(CowStr<'a>, CowSt
     }
 
     suspicious_doc_comments::check(cx, attrs);
+    invalid_alias::check(cx, attrs);
+    comment_style::check(cx, attrs, conf.doc_comment_style);
 
     let (fragments, _) = attrs_to_doc_fragments(attrs.iter().map(|attr| (attr, None)), true);
     let mut doc = String::new();
@@ -508,6 +1820,8 @@ fn fake_broken_link_callback<'a>(_: BrokenLink<'_>) -> Option<(CowStr<'a>, CowSt
     }
     doc.pop();
 
+    empty_doc_comment::check(cx, &fragments, &doc);
+    placeholder_doc::check(cx, &fragments, &doc, &conf.doc_placeholder_markers);
     if doc.is_empty() {
         return Some(DocHeaders::default());
     }
@@ -520,7 +1834,12 @@ fn fake_broken_link_callback<'a>(_: BrokenLink<'_>) -> Option<(CowStr<'a>, CowSt
 
     Some(check_doc(
         cx,
-        valid_idents,
+        &conf.valid_idents,
+        conf.check_doc_attributes,
+        conf.doc_tick_keywords,
+        &conf.returns_none_doc_heading,
+        &conf.required_doc_sections,
+        conf.check_not_run_examples,
         parser.into_offset_iter(),
         Fragments {
             fragments: &fragments,
@@ -531,42 +1850,119 @@ fn fake_broken_link_callback<'a>(_: BrokenLink<'_>) -> Option<(CowStr<'a>, CowSt
 
 const RUST_CODE: &[&str] = &["rust", "no_run", "should_panic", "compile_fail"];
 
+/// The sections whose content is always checked, see [`MISSING_SAFETY_DOC`], [`MISSING_ERRORS_DOC`]
+/// and [`MISSING_PANICS_DOC`].
+const CHECKED_SECTIONS: &[&str] = &["Safety", "Errors", "Panics"];
+
+/// The headings of sections whose list items start with the name of a parameter.
+const ARGUMENTS_HEADINGS: &[&str] = &["Arguments", "Parameters"];
+
+/// The headings of the other sections the lints look for, besides the one configured with
+/// `returns-none-doc-heading`.
+const SECTION_HEADINGS: &[&str] = &[
+    "Safety",
+    "Implementation safety",
+    "Implementation Safety",
+    "Errors",
+    "Panics",
+    "Examples",
+];
+
 /// Checks parsed documentation.
 /// This walks the "events" (think sections of markdown) produced by `pulldown_cmark`,
 /// so lints here will generally access that information.
 /// Returns documentation headers -- whether a "Safety", "Errors", "Panic" section was found
 #[allow(clippy::too_many_lines)] // Only a big match statement
+#[expect(clippy::too_many_arguments)]
 fn check_doc<'a, Events: Iterator<Item = (pulldown_cmark::Event<'a>, Range<usize>)>>(
     cx: &LateContext<'_>,
     valid_idents: &FxHashSet<String>,
+    check_doc_attributes: bool,
+    tick_keywords: bool,
+    returns_heading: &str,
+    required_sections: &[String],
+    check_not_run_examples: bool,
     events: Events,
     fragments: Fragments<'_>,
 ) -> DocHeaders {
-    // true if a safety header was found
-    let mut headers = DocHeaders::default();
+    let mut headers = DocHeaders::new(required_sections);
     let mut in_code = false;
     let mut in_link = None;
     let mut in_heading = false;
+    let mut heading_level = None;
     let mut is_rust = false;
     let mut no_test = false;
-    let mut ignore = false;
+    let mut example_kind = ExampleKind::Run;
     let mut edition = None;
+    // The range of the Rust code in the current code block that is yet to be checked.
+    let mut rust_code: Option<Range<usize>> = None;
+    let check_rust_code = |code: Range<usize>, edition: Option<Edition>, kind| {
+        let edition = edition.unwrap_or_else(|| cx.tcx.sess.edition());
+        needless_doctest_main::check(
+            cx,
+            &fragments.doc[code.clone()],
+            edition,
+            code,
+            fragments,
+            kind,
+            check_not_run_examples,
+        );
+    };
+    let mut other_lang = None;
     let mut ticks_unbalanced = false;
-    let mut text_to_check: Vec<(CowStr<'_>, Range<usize>)> = Vec::new();
+    let mut text_to_check: Vec<(CowStr<'_>, Range<usize>, bool)> = Vec::new();
     let mut paragraph_range = 0..0;
+    let mut closed_container = false;
+    let mut unknown_link_end = None;
+    let mut in_errors_section = false;
+    let mut in_arguments_section = false;
+    let mut at_item_start = false;
+    let mut prev_param_like_code = None;
+    let mut sections = FxHashSet::default();
+    let mut heading_section = None;
+    // The heading of the section whose content is checked, while nothing has been found under it.
+    let mut empty_section: Option<String> = None;
+    let mut checked_heading: Option<String> = None;
     for (event, range) in events {
+        let after_container = mem::replace(&mut closed_container, matches!(event, End(List(_) | BlockQuote)));
+        let param_like_code = prev_param_like_code.take();
+        if !in_heading
+            && let Some(heading) = &empty_section
+            && match &event {
+                Text(text) | FootnoteReference(text) => !text.trim().is_empty(),
+                Code(_) | Start(CodeBlock(_)) | Rule => true,
+                _ => false,
+            }
+        {
+            if let Some(section) = headers.section_mut(heading) {
+                *section = Section::Present;
+            }
+            empty_section = None;
+        }
         match event {
             Start(CodeBlock(ref kind)) => {
                 in_code = true;
+                code_before_summary::check(cx, range.clone(), fragments);
                 if let CodeBlockKind::Fenced(lang) = kind {
+                    headers.examples = true;
+                    nested_fence::check(cx, after_container, range.clone(), fragments);
                     for item in lang.split(',') {
                         if item == "ignore" {
+                            // With another language, this is not an example.
+                            if lang
+                                .split(',')
+                                .all(|item| item.is_empty() || item == "ignore" || item == "rust")
+                            {
+                                example_kind = ExampleKind::Ignore;
+                            }
                             is_rust = false;
                             break;
                         } else if item == "no_test" {
                             no_test = true;
-                        } else if item == "no_run" || item == "compile_fail" {
-                            ignore = true;
+                        } else if item == "no_run" && example_kind == ExampleKind::Run {
+                            example_kind = ExampleKind::NoRun;
+                        } else if item == "compile_fail" {
+                            example_kind = ExampleKind::CompileFail;
                         }
                         if let Some(stripped) = item.strip_prefix("edition") {
                             is_rust = true;
@@ -575,39 +1971,108 @@ fn check_doc<'a, Events: Iterator<Item = (pulldown_cmark::Event<'a>, Range<usize
                             is_rust = true;
                         }
                     }
+                    example_unspecified_edition::check(cx, lang, is_rust, range.clone(), fragments);
+                    let other = mistagged_rust::other_language(lang, is_rust)
+                        .map(|lang| (lang, DOC_MISTAGGED_RUST))
+                        .or_else(|| mistagged_rust::text_tag(lang).map(|lang| (lang, DOC_RUST_IN_TEXT_BLOCK)));
+                    if let Some((lang, lint)) = other {
+                        let block = &fragments.doc[range.clone()];
+                        let fence_len = block.find('\n').unwrap_or(block.len());
+                        other_lang = Some((lang.to_owned(), range.start..range.start + fence_len, lint));
+                    }
                 }
             },
-            End(CodeBlock(_)) => {
+            End(CodeBlock(ref kind)) => {
+                if let Some(code) = rust_code.take() {
+                    check_rust_code(code, edition, example_kind);
+                }
+                if let CodeBlockKind::Fenced(_) = kind {
+                    malformed_markdown::check_code_block(cx, range.clone(), fragments);
+                }
                 in_code = false;
                 is_rust = false;
-                ignore = false;
+                example_kind = ExampleKind::Run;
+                other_lang = None;
             },
             Start(Link(_, url, _)) => in_link = Some(url),
-            End(Link(..)) => in_link = None,
+            End(Link(kind, ..)) => {
+                in_link = None;
+                unknown_link_end = (kind == LinkType::ShortcutUnknown).then_some(range.end);
+            },
             Start(Heading(_, _, _) | Paragraph | Item) => {
-                if let Start(Heading(_, _, _)) = event {
+                if let Start(Heading(level, _, _)) = event {
+                    heading_jump::check(cx, heading_level, level, range.clone(), fragments);
+                    heading_level = Some(level);
                     in_heading = true;
+                    in_errors_section = false;
+                    in_arguments_section = false;
+                    empty_section = None;
+                }
+                if let Start(Item) = event {
+                    at_item_start = true;
                 }
                 ticks_unbalanced = false;
                 paragraph_range = range;
             },
             End(Heading(_, _, _) | Paragraph | Item) => {
+                if let End(Paragraph) = event {
+                    if let Some(span) = missing_summary_break::summary_line(cx, range.clone(), fragments) {
+                        headers.summary_line = Some(span);
+                    }
+                    malformed_table::check(cx, range.clone(), fragments);
+                }
+                at_item_start = false;
                 if let End(Heading(_, _, _)) = event {
                     in_heading = false;
+                    if let Some(name) = heading_section.take() {
+                        duplicate_section::check(cx, &mut sections, name, range.clone(), fragments);
+                    }
+                    if let Some(heading) = checked_heading.take()
+                        && let Some(section) = headers.section_mut(&heading)
+                    {
+                        if *section == Section::Absent {
+                            // Without a span to point at, the section is assumed to have content.
+                            *section = fragments
+                                .span(cx, paragraph_range.clone())
+                                .map_or(Section::Present, Section::Empty);
+                        }
+                        if *section != Section::Present {
+                            empty_section = Some(heading);
+                        }
+                    }
+                    if in_errors_section {
+                        headers.errors_section =
+                            fragments.span(cx, paragraph_range.clone()).map(|span| ErrorsSection {
+                                span,
+                                text: String::new(),
+                            });
+                    }
                 }
+                // The words of a paragraph with unbalanced backticks are not checked by `DOC_MARKDOWN`,
+                // they may be code that is missing its backticks.
                 if ticks_unbalanced && let Some(span) = fragments.span(cx, paragraph_range.clone()) {
                     span_lint_and_help(
                         cx,
-                        DOC_MARKDOWN,
+                        DOC_UNBALANCED_BACKTICKS,
                         span,
                         "backticks are unbalanced",
                         None,
                         "a backtick may be missing a pair",
                     );
                 } else {
-                    for (text, range) in text_to_check {
-                        if let Some(span) = fragments.span(cx, range) {
-                            markdown::check(cx, valid_idents, &text, span);
+                    for (text, range, in_link) in text_to_check {
+                        if let Some(span) = fragments.span(cx, range.clone()) {
+                            markdown::check(cx, valid_idents, tick_keywords, in_link, &text, span);
+                        } else if check_doc_attributes {
+                            markdown::check_doc_attribute(
+                                cx,
+                                valid_idents,
+                                tick_keywords,
+                                in_link,
+                                &text,
+                                range,
+                                fragments,
+                            );
                         }
                     }
                 }
@@ -615,10 +2080,48 @@ fn check_doc<'a, Events: Iterator<Item = (pulldown_cmark::Event<'a>, Range<usize
             },
             Start(_tag) | End(_tag) => (), // We don't care about other tags
             Html(_html) => (),             // HTML is weird, just ignore it
-            SoftBreak | HardBreak | TaskListMarker(_) | Code(_) | Rule => (),
+            Code(code) => {
+                if in_errors_section
+                    && !in_heading
+                    && let Some(section) = &mut headers.errors_section
+                {
+                    section.text.push_str(&code);
+                }
+                if !in_heading
+                    && unknown_param::is_param_like(cx, &code)
+                    && let Some(span) = fragments.span(cx, range.clone())
+                {
+                    if in_arguments_section && at_item_start {
+                        headers.documented_params.push((code.to_string(), span));
+                    } else {
+                        prev_param_like_code = Some((code.to_string(), span));
+                    }
+                    headers.param_like_code.push((code.to_string(), span));
+                }
+                at_item_start = false;
+                if !in_heading
+                    && matches!(&*code, "self" | "Self")
+                    && let Some(span) = fragments.span(cx, range.clone())
+                {
+                    headers.self_code.push((code.to_string(), span));
+                }
+                if irrelevant_self::is_method_path(&code) {
+                    headers.mentions_method = true;
+                }
+                nonsense_backticks::check(cx, &code, range, fragments);
+            },
+            SoftBreak | HardBreak | TaskListMarker(_) | Rule => (),
             FootnoteReference(text) | Text(text) => {
                 paragraph_range.end = range.end;
-                ticks_unbalanced |= text.contains('`') && !in_code;
+                if let Some(code) = param_like_code
+                    && let Some(word) = text.split_whitespace().next()
+                    && ["parameter", "argument"].contains(&word.trim_end_matches(|c: char| !c.is_alphabetic()))
+                {
+                    headers.documented_params.push(code);
+                }
+                at_item_start &= text.trim().is_empty();
+                ticks_unbalanced |=
+                    !in_code && text.contains('`') && has_unescaped_backtick(fragments.doc, range.clone());
                 if Some(&text) == in_link.as_ref() || ticks_unbalanced {
                     // Probably a link of the form `<http://example.com>`
                     // Which are represented as a link to "http://example.com" with
@@ -626,17 +2129,47 @@ fn check_doc<'a, Events: Iterator<Item = (pulldown_cmark::Event<'a>, Range<usize
                     continue;
                 }
                 let trimmed_text = text.trim();
-                headers.safety |= in_heading && trimmed_text == "Safety";
-                headers.safety |= in_heading && trimmed_text == "Implementation safety";
-                headers.safety |= in_heading && trimmed_text == "Implementation Safety";
-                headers.errors |= in_heading && trimmed_text == "Errors";
-                headers.panics |= in_heading && trimmed_text == "Panics";
+                if in_heading && headers.section_mut(trimmed_text).is_some() {
+                    checked_heading = Some(trimmed_text.to_owned());
+                }
+                headers.returns |= in_heading && trimmed_text == returns_heading;
+                headers.examples |= in_heading && trimmed_text == "Examples";
+                in_errors_section |= in_heading && trimmed_text == "Errors";
+                in_arguments_section |= in_heading && ARGUMENTS_HEADINGS.contains(&trimmed_text);
+                if in_heading
+                    && (SECTION_HEADINGS.contains(&trimmed_text)
+                        || ARGUMENTS_HEADINGS.contains(&trimmed_text)
+                        || trimmed_text == returns_heading
+                        || required_sections.iter().any(|name| name == trimmed_text))
+                {
+                    heading_section = Some(trimmed_text.to_owned());
+                }
+                if in_errors_section
+                    && !in_heading
+                    && let Some(section) = &mut headers.errors_section
+                {
+                    section.text.push_str(&text);
+                }
                 if in_code {
-                    if is_rust && !no_test {
-                        let edition = edition.unwrap_or_else(|| cx.tcx.sess.edition());
-                        needless_doctest_main::check(cx, &text, edition, range.clone(), fragments, ignore);
+                    // `ignore` examples are only checked by `DOC_EXAMPLE_STD_IN_NO_STD`, if configured to.
+                    let checked = is_rust || (example_kind == ExampleKind::Ignore && check_not_run_examples);
+                    if checked && !no_test {
+                        // The text of a code block is split into several events where indentation
+                        // is removed. The parts are checked together, with the indentation.
+                        if let Some(code) = &mut rust_code
+                            && fragments.doc[code.end..range.start].trim().is_empty()
+                        {
+                            code.end = range.end;
+                        } else if let Some(code) = rust_code.replace(range.clone()) {
+                            check_rust_code(code, edition, example_kind);
+                        }
+                    }
+                    if let Some((lang, fence, lint)) = &other_lang {
+                        mistagged_rust::check(cx, lint, &text, lang, fence.clone(), fragments);
                     }
                 } else {
+                    malformed_markdown::check_text(cx, &text, range.clone(), fragments, unknown_link_end);
+                    literal_newline_escape::check(cx, range.clone(), fragments);
                     if in_link.is_some() {
                         link_with_quotes::check(cx, trimmed_text, range.clone(), fragments);
                     }
@@ -647,7 +2180,7 @@ fn check_doc<'a, Events: Iterator<Item = (pulldown_cmark::Event<'a>, Range<usize
                         // Don't check the text associated with external URLs
                         continue;
                     }
-                    text_to_check.push((text, range));
+                    text_to_check.push((text, range, in_link.is_some()));
                 }
             },
         }
@@ -657,23 +2190,72 @@ fn check_doc<'a, Events: Iterator<Item = (pulldown_cmark::Event<'a>, Range<usize
 
 struct FindPanicUnwrap<'a, 'tcx> {
     cx: &'a LateContext<'tcx>,
-    panic_span: Option<Span>,
+    panic: Option<Panic>,
+    /// Whether the visited code only runs with debug assertions enabled.
+    in_debug_only: bool,
     typeck_results: &'tcx ty::TypeckResults<'tcx>,
+    /// The spans of the reachable MIR of each body visited so far. `None` if panics are not
+    /// checked for reachability.
+    reachable_spans: Option<FxHashMap<LocalDefId, Vec<Span>>>,
 }
 
 impl<'a, 'tcx> FindPanicUnwrap<'a, 'tcx> {
-    pub fn find_span(
+    pub fn find(
         cx: &'a LateContext<'tcx>,
         typeck_results: &'tcx ty::TypeckResults<'tcx>,
         body: impl Visitable<'tcx>,
-    ) -> Option<Span> {
+        ignore_unreachable: bool,
+    ) -> Option<Panic> {
         let mut vis = Self {
             cx,
-            panic_span: None,
+            panic: None,
+            in_debug_only: false,
             typeck_results,
+            reachable_spans: ignore_unreachable.then(FxHashMap::default),
         };
         body.visit(&mut vis);
-        vis.panic_span
+        vis.panic
+    }
+
+    /// Records the panic at `span`, unless it is debug-only and another panic was already found.
+    fn found(&mut self, span: Span, debug_only: bool) {
+        if !(debug_only && self.panic.is_some()) {
+            self.panic = Some(Panic { span, debug_only });
+        }
+    }
+
+    /// Whether the code with the given `HirId` is under `#[cfg(debug_assertions)]`.
+    fn is_cfg_debug_assertions(&self, id: HirId) -> bool {
+        self.cx.tcx.hir().attrs(id).iter().any(|attr| {
+            attr.has_name(sym::cfg)
+                && attr
+                    .meta_item_list()
+                    .is_some_and(|items| matches!(&*items, [item] if item.has_name(sym::debug_assertions)))
+        })
+    }
+
+    /// Whether the panic at `span` in `expr` can be reached according to the MIR of the body
+    /// containing it, i.e. whether any reachable statement or terminator originates from it.
+    fn is_reachable(&mut self, expr: &Expr<'_>, span: Span) -> bool {
+        let Some(reachable_spans) = &mut self.reachable_spans else {
+            return true;
+        };
+        let tcx = self.cx.tcx;
+        let owner = tcx.hir().enclosing_body_owner(expr.hir_id);
+        reachable_spans
+            .entry(owner)
+            .or_insert_with(|| {
+                traversal::reachable(tcx.optimized_mir(owner))
+                    .flat_map(|(_, data)| {
+                        data.statements
+                            .iter()
+                            .map(|stmt| stmt.source_info.span)
+                            .chain(iter::once(data.terminator().source_info.span))
+                    })
+                    .collect()
+            })
+            .iter()
+            .any(|&mir_span| span.contains(mir_span) || span.contains(mir_span.source_callsite()))
     }
 }
 
@@ -681,33 +2263,47 @@ impl<'a, 'tcx> Visitor<'tcx> for FindPanicUnwrap<'a, 'tcx> {
     type NestedFilter = nested_filter::OnlyBodies;
 
     fn visit_expr(&mut self, expr: &'tcx Expr<'_>) {
-        if self.panic_span.is_some() {
+        if self.panic.is_some_and(|panic| !panic.debug_only) {
             return;
         }
 
+        let mut debug_only = self.in_debug_only || self.is_cfg_debug_assertions(expr.hir_id);
         if let Some(macro_call) = root_macro_call_first_node(self.cx, expr) {
-            if is_panic(self.cx, macro_call.def_id)
-                || matches!(
-                    self.cx.tcx.item_name(macro_call.def_id).as_str(),
-                    "assert" | "assert_eq" | "assert_ne"
-                )
+            let name = self.cx.tcx.item_name(macro_call.def_id);
+            // The assertions of `debug_assert!` are only checked with debug assertions enabled.
+            let is_debug_assert = matches!(name.as_str(), "debug_assert" | "debug_assert_eq" | "debug_assert_ne");
+            debug_only |= is_debug_assert;
+            if (is_panic(self.cx, macro_call.def_id)
+                || is_debug_assert
+                || matches!(name.as_str(), "assert" | "assert_eq" | "assert_ne"))
+                && self.is_reachable(expr, macro_call.span)
             {
-                self.panic_span = Some(macro_call.span);
+                self.found(macro_call.span, debug_only);
             }
         }
 
         // check for `unwrap` and `expect` for both `Option` and `Result`
         if let Some(arglists) = method_chain_args(expr, &["unwrap"]).or(method_chain_args(expr, &["expect"])) {
             let receiver_ty = self.typeck_results.expr_ty(arglists[0].0).peel_refs();
-            if is_type_diagnostic_item(self.cx, receiver_ty, sym::Option)
-                || is_type_diagnostic_item(self.cx, receiver_ty, sym::Result)
+            if (is_type_diagnostic_item(self.cx, receiver_ty, sym::Option)
+                || is_type_diagnostic_item(self.cx, receiver_ty, sym::Result))
+                && self.is_reachable(expr, expr.span)
             {
-                self.panic_span = Some(expr.span);
+                self.found(expr.span, debug_only);
             }
         }
 
         // and check sub-expressions
+        let in_debug_only = mem::replace(&mut self.in_debug_only, debug_only);
         intravisit::walk_expr(self, expr);
+        self.in_debug_only = in_debug_only;
+    }
+
+    fn visit_local(&mut self, local: &'tcx Local<'tcx>) {
+        let debug_only = self.in_debug_only || self.is_cfg_debug_assertions(local.hir_id);
+        let in_debug_only = mem::replace(&mut self.in_debug_only, debug_only);
+        intravisit::walk_local(self, local);
+        self.in_debug_only = in_debug_only;
     }
 
     // Panics in const blocks will cause compilation to fail.
@@ -717,3 +2313,52 @@ fn nested_visit_map(&mut self) -> Self::Map {
         self.cx.tcx.hir()
     }
 }
+
+/// Standard library methods that panic on some of their arguments, like an out of bounds index.
+const PANICKING_STD_METHODS: &[&[&str]] = &[
+    &["alloc", "collections", "vec_deque", "VecDeque", "drain"],
+    &["alloc", "collections", "vec_deque", "VecDeque", "insert"],
+    &["alloc", "collections", "vec_deque", "VecDeque", "split_off"],
+    &["alloc", "collections", "vec_deque", "VecDeque", "swap"],
+    &["alloc", "string", "String", "drain"],
+    &["alloc", "string", "String", "insert"],
+    &["alloc", "string", "String", "insert_str"],
+    &["alloc", "string", "String", "remove"],
+    &["alloc", "string", "String", "split_off"],
+    &["alloc", "string", "String", "truncate"],
+    &["alloc", "vec", "Vec", "drain"],
+    &["alloc", "vec", "Vec", "insert"],
+    &["alloc", "vec", "Vec", "remove"],
+    &["alloc", "vec", "Vec", "split_off"],
+    &["alloc", "vec", "Vec", "swap_remove"],
+    &["core", "slice", "<impl [T]>", "chunks"],
+    &["core", "slice", "<impl [T]>", "chunks_exact"],
+    &["core", "slice", "<impl [T]>", "chunks_mut"],
+    &["core", "slice", "<impl [T]>", "clone_from_slice"],
+    &["core", "slice", "<impl [T]>", "copy_from_slice"],
+    &["core", "slice", "<impl [T]>", "copy_within"],
+    &["core", "slice", "<impl [T]>", "rotate_left"],
+    &["core", "slice", "<impl [T]>", "rotate_right"],
+    &["core", "slice", "<impl [T]>", "split_at"],
+    &["core", "slice", "<impl [T]>", "split_at_mut"],
+    &["core", "slice", "<impl [T]>", "swap"],
+    &["core", "slice", "<impl [T]>", "windows"],
+    &["core", "str", "<impl str>", "split_at"],
+    &["core", "str", "<impl str>", "split_at_mut"],
+];
+
+/// Returns the span of the body of a function that only forwards to one of the
+/// `PANICKING_STD_METHODS`. Such a function panics on the same arguments, so it needs the same
+/// documentation.
+fn find_forwarded_panic(cx: &LateContext<'_>, typeck_results: &ty::TypeckResults<'_>, body: &Expr<'_>) -> Option<Span> {
+    let expr = peel_blocks(body);
+    let def_id = match expr.kind {
+        ExprKind::MethodCall(..) => typeck_results.type_dependent_def_id(expr.hir_id)?,
+        ExprKind::Call(func, _) => match func.kind {
+            ExprKind::Path(ref qpath) => typeck_results.qpath_res(qpath, func.hir_id).opt_def_id()?,
+            _ => return None,
+        },
+        _ => return None,
+    };
+    match_any_def_paths(cx, def_id, PANICKING_STD_METHODS).map(|_| expr.span)
+}
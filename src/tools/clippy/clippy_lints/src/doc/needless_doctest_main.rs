@@ -3,19 +3,30 @@
 
 use crate::doc::{NEEDLESS_DOCTEST_MAIN, TEST_ATTR_IN_DOCTEST};
 use clippy_utils::diagnostics::span_lint;
-use rustc_ast::{CoroutineKind, Fn, FnRetTy, Item, ItemKind};
+use clippy_utils::is_no_std_crate;
+use clippy_utils::source::snippet_opt;
+use rustc_ast::tokenstream::TokenStream;
+use rustc_ast::{
+    AngleBracketedArg, Block, CoroutineKind, ExprKind, Fn, FnRetTy, GenericArg, GenericArgs, Item, ItemKind, StmtKind,
+    Ty, TyKind,
+};
 use rustc_data_structures::sync::Lrc;
 use rustc_errors::emitter::HumanEmitter;
 use rustc_errors::{DiagCtxt, DiagnosticBuilder};
+use rustc_hir::def_id::LOCAL_CRATE;
 use rustc_lint::LateContext;
 use rustc_parse::maybe_new_parser_from_source_str;
 use rustc_parse::parser::ForceCollect;
+use rustc_resolve::rustdoc::DocFragmentKind;
 use rustc_session::parse::ParseSess;
 use rustc_span::edition::Edition;
 use rustc_span::source_map::{FilePathMapping, SourceMap};
 use rustc_span::{sym, FileName, Pos};
 
-use super::Fragments;
+use super::{
+    example_assert_order, example_deprecated, example_no_assertion, example_static_mut, example_std_in_no_std,
+    example_stderr, example_unexplained_unsafe, example_wrong_crate_name, hidden_line_error, ExampleKind, Fragments,
+};
 
 fn get_test_spans(item: &Item, test_attr_spans: &mut Vec<Range<usize>>) {
     test_attr_spans.extend(
@@ -26,43 +37,132 @@ fn get_test_spans(item: &Item, test_attr_spans: &mut Vec<Range<usize>>) {
     );
 }
 
-pub fn check(
-    cx: &LateContext<'_>,
-    text: &str,
-    edition: Edition,
-    range: Range<usize>,
-    fragments: Fragments<'_>,
-    ignore: bool,
-) {
-    // return whether the code contains a needless `fn main` plus a vector of byte position ranges
-    // of all `#[test]` attributes in not ignored code examples
-    fn check_code_sample(code: String, edition: Edition, ignore: bool) -> (bool, Vec<Range<usize>>) {
-        rustc_driver::catch_fatal_errors(|| {
-            rustc_span::create_session_globals_then(edition, || {
-                let mut test_attr_spans = vec![];
-                let filename = FileName::anon_source_code(&code);
-
-                let fallback_bundle =
-                    rustc_errors::fallback_fluent_bundle(rustc_driver::DEFAULT_LOCALE_RESOURCES.to_vec(), false);
-                let emitter = HumanEmitter::new(Box::new(io::sink()), fallback_bundle);
-                let dcx = DiagCtxt::with_emitter(Box::new(emitter)).disable_warnings();
-                #[expect(clippy::arc_with_non_send_sync)] // `Lrc` is expected by with_dcx
-                let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
-                let sess = ParseSess::with_dcx(dcx, sm);
-
-                let mut parser = match maybe_new_parser_from_source_str(&sess, filename, code) {
-                    Ok(p) => p,
-                    Err(errs) => {
-                        errs.into_iter().for_each(DiagnosticBuilder::cancel);
-                        return (false, test_attr_spans);
-                    },
-                };
+/// Returns whether `ty` is written as `Result<(), E>`, with an error type that can be written in
+/// the `Ok::<(), E>(())` rustdoc needs to infer the return type of the wrapping function. Aliases
+/// like `io::Result<()>` are not recognized.
+fn is_unit_result(ty: &Ty) -> bool {
+    if let TyKind::Path(None, path) = &ty.kind
+        && let Some(segment) = path.segments.last()
+        && segment.ident.name == sym::Result
+        && let Some(GenericArgs::AngleBracketed(args)) = segment.args.as_deref()
+        && let [
+            AngleBracketedArg::Arg(GenericArg::Type(ok)),
+            AngleBracketedArg::Arg(GenericArg::Type(err)),
+        ] = &args.args[..]
+    {
+        ok.kind.is_unit() && !matches!(err.kind, TyKind::Infer | TyKind::ImplTrait(..))
+    } else {
+        false
+    }
+}
+
+/// Returns whether the block ends with `Ok(())` and has other statements.
+fn ends_with_ok_unit(block: &Block) -> bool {
+    if let [_, .., last] = &block.stmts[..]
+        && let StmtKind::Expr(expr) = &last.kind
+        && let ExprKind::Call(func, args) = &expr.kind
+        && let ExprKind::Path(None, path) = &func.kind
+        && let [segment] = &path.segments[..]
+        && segment.ident.name == sym::Ok
+        && segment.args.is_none()
+        && let [arg] = &args[..]
+    {
+        matches!(&arg.kind, ExprKind::Tup(elems) if elems.is_empty())
+    } else {
+        false
+    }
+}
+
+/// What the code of an example was found to contain.
+#[derive(Default)]
+struct CodeSample {
+    /// Whether the code contains a needless `fn main`.
+    has_main: bool,
+    /// The byte position ranges of all `#[test]` attributes, if the example isn't ignored.
+    test_attr_spans: Vec<Range<usize>>,
+    crate_names: example_wrong_crate_name::CrateNames,
+    /// The `assert_eq!` and `assert_ne!` calls with swapped arguments, if the example isn't
+    /// ignored.
+    swapped_asserts: Vec<example_assert_order::SwappedAssert>,
+    /// The byte position range of the hidden line with a syntax error, if the example isn't
+    /// ignored.
+    hidden_line_error: Option<Range<usize>>,
+    /// The byte position ranges of the paths starting with `std`, if they are looked for.
+    std_paths: Vec<Range<usize>>,
+    /// The byte position range of the first `let` statement binding a computed value, if the
+    /// example has no assertion and isn't ignored.
+    no_assertion: Option<Range<usize>>,
+    /// The paths starting with the name of the documented crate, if the example isn't ignored.
+    crate_paths: Vec<example_deprecated::CratePath>,
+    /// The byte position ranges of the `unsafe` keywords of the blocks without a comment next to
+    /// them, if the example isn't ignored.
+    unexplained_unsafe: Vec<Range<usize>>,
+    /// The byte position ranges of the `static mut` items, if the example isn't ignored.
+    static_muts: Vec<Range<usize>>,
+    /// The byte position ranges of the writes to stderr, if the example isn't ignored.
+    stderr_writes: Vec<Range<usize>>,
+}
+
+impl CodeSample {
+    /// Finds what the lints looking at the tokens of the example check for.
+    fn find_in_tokens(
+        &mut self,
+        sess: &ParseSess,
+        tokens: &TokenStream,
+        code: &str,
+        ignore: bool,
+        check_std: bool,
+        crate_name: &str,
+    ) {
+        if check_std {
+            example_std_in_no_std::find(sess, tokens, &mut self.std_paths);
+        }
+        if !ignore {
+            example_assert_order::find(sess, tokens, &mut self.swapped_asserts);
+            example_deprecated::find(sess, tokens, crate_name, &mut self.crate_paths);
+            example_unexplained_unsafe::find(sess, tokens, code, &mut self.unexplained_unsafe);
+            example_static_mut::find(sess, tokens, &mut self.static_muts);
+            example_stderr::find(sess, tokens, &mut self.stderr_writes);
+        }
+    }
+}
+
+fn check_code_sample(code: &str, edition: Edition, ignore: bool, check_std: bool, crate_name: &str) -> CodeSample {
+    rustc_driver::catch_fatal_errors(|| {
+        rustc_span::create_session_globals_then(edition, || {
+            let hidden_line_error = if ignore { None } else { hidden_line_error::find(code) };
+            let no_assertion = if ignore { None } else { example_no_assertion::find(code) };
+            let mut test_attr_spans = vec![];
+            let mut crate_names = example_wrong_crate_name::CrateNames::default();
+            let filename = FileName::anon_source_code(code);
 
-                let mut relevant_main_found = false;
-                let mut eligible = true;
-                loop {
-                    match parser.parse_item(ForceCollect::No) {
-                        Ok(Some(item)) => match &item.kind {
+            let fallback_bundle =
+                rustc_errors::fallback_fluent_bundle(rustc_driver::DEFAULT_LOCALE_RESOURCES.to_vec(), false);
+            let emitter = HumanEmitter::new(Box::new(io::sink()), fallback_bundle);
+            let dcx = DiagCtxt::with_emitter(Box::new(emitter)).disable_warnings();
+            #[expect(clippy::arc_with_non_send_sync)] // `Lrc` is expected by with_dcx
+            let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+            let sess = ParseSess::with_dcx(dcx, sm);
+
+            let mut parser = match maybe_new_parser_from_source_str(&sess, filename.clone(), code.to_owned()) {
+                Ok(p) => p,
+                Err(errs) => {
+                    errs.into_iter().for_each(DiagnosticBuilder::cancel);
+                    return CodeSample {
+                        hidden_line_error,
+                        no_assertion,
+                        ..CodeSample::default()
+                    };
+                },
+            };
+
+            let mut relevant_main_found = false;
+            let mut eligible = true;
+            loop {
+                match parser.parse_item(ForceCollect::No) {
+                    Ok(Some(item)) => {
+                        crate_names.collect(&item);
+                        match &item.kind {
                             ItemKind::Fn(box Fn {
                                 sig, body: Some(block), ..
                             }) if item.ident.name == sym::main => {
@@ -70,13 +170,15 @@ fn check_code_sample(code: String, edition: Edition, ignore: bool) -> (bool, Vec
                                     get_test_spans(&item, &mut test_attr_spans);
                                 }
                                 let is_async = matches!(sig.header.coroutine_kind, Some(CoroutineKind::Async { .. }));
-                                let returns_nothing = match &sig.decl.output {
-                                    FnRetTy::Default(..) => true,
-                                    FnRetTy::Ty(ty) if ty.kind.is_unit() => true,
-                                    FnRetTy::Ty(_) => false,
+                                let is_needless = match &sig.decl.output {
+                                    FnRetTy::Default(..) => !block.stmts.is_empty(),
+                                    FnRetTy::Ty(ty) if ty.kind.is_unit() => !block.stmts.is_empty(),
+                                    // Rustdoc wraps an example ending in `Ok::<(), E>(())` in a function
+                                    // returning `Result<(), E>` as well.
+                                    FnRetTy::Ty(ty) => is_unit_result(ty) && ends_with_ok_unit(block),
                                 };
 
-                                if returns_nothing && !is_async && !block.stmts.is_empty() {
+                                if is_needless && !is_async {
                                     // This main function should be linted, but only if there are no other functions
                                     relevant_main_found = true;
                                 } else {
@@ -99,37 +201,132 @@ fn check_code_sample(code: String, edition: Edition, ignore: bool) -> (bool, Vec
                                 eligible = false;
                             },
                             _ => {},
-                        },
-                        Ok(None) => break,
-                        Err(e) => {
-                            e.cancel();
-                            return (false, test_attr_spans);
-                        },
-                    }
+                        }
+                    },
+                    Ok(None) => break,
+                    Err(e) => {
+                        e.cancel();
+                        eligible = false;
+                        break;
+                    },
                 }
+            }
 
-                (relevant_main_found & eligible, test_attr_spans)
-            })
+            let mut sample = CodeSample {
+                has_main: relevant_main_found & eligible,
+                test_attr_spans,
+                crate_names,
+                hidden_line_error,
+                no_assertion,
+                ..CodeSample::default()
+            };
+            if let Ok(mut parser) = maybe_new_parser_from_source_str(&sess, filename, code.to_owned()) {
+                sample.find_in_tokens(&sess, &parser.parse_tokens(), code, ignore, check_std, crate_name);
+            }
+            sample
         })
-        .ok()
-        .unwrap_or_default()
-    }
+    })
+    .ok()
+    .unwrap_or_default()
+}
 
+pub fn check(
+    cx: &LateContext<'_>,
+    text: &str,
+    edition: Edition,
+    range: Range<usize>,
+    fragments: Fragments<'_>,
+    kind: ExampleKind,
+    check_not_run: bool,
+) {
     let trailing_whitespace = text.len() - text.trim_end().len();
+    let ignore = kind != ExampleKind::Run;
+    // A `compile_fail` example may fail to compile because of its use of `std`.
+    let check_std = is_no_std_crate(cx)
+        && match kind {
+            ExampleKind::Run => true,
+            ExampleKind::NoRun | ExampleKind::Ignore => check_not_run,
+            ExampleKind::CompileFail => false,
+        };
 
     // Because of the global session, we need to create a new session in a different thread with
     // the edition we need.
-    let text = text.to_owned();
-    let (has_main, test_attr_spans) = thread::spawn(move || check_code_sample(text, edition, ignore))
+    let code = unstar_block_comment_code(cx, text, range.start, fragments);
+    let crate_name = cx.tcx.crate_name(LOCAL_CRATE).to_string();
+    let sample = thread::spawn(move || check_code_sample(&code, edition, ignore, check_std, &crate_name))
         .join()
         .expect("thread::spawn failed");
-    if has_main && let Some(span) = fragments.span(cx, range.start..range.end - trailing_whitespace) {
+    example_std_in_no_std::check(cx, &sample.std_paths, range.clone(), fragments);
+    // The other lints don't look at the examples that are not compiled.
+    if kind == ExampleKind::Ignore {
+        return;
+    }
+    // A `compile_fail` example may need its `fn main` to show the intended error.
+    if sample.has_main
+        && kind != ExampleKind::CompileFail
+        && let Some(span) = fragments.span(cx, range.start..range.end - trailing_whitespace)
+    {
         span_lint(cx, NEEDLESS_DOCTEST_MAIN, span, "needless `fn main` in doctest");
     }
-    for span in test_attr_spans {
+    for span in sample.test_attr_spans {
         let span = (range.start + span.start)..(range.start + span.end);
         if let Some(span) = fragments.span(cx, span) {
             span_lint(cx, TEST_ATTR_IN_DOCTEST, span, "unit tests in doctest are not executed");
         }
     }
+    if let Some(error_line) = sample.hidden_line_error {
+        hidden_line_error::check(cx, error_line, range.clone(), fragments);
+    }
+    if let Some(binding) = sample.no_assertion {
+        example_no_assertion::check(cx, binding, range.clone(), fragments);
+    }
+    example_deprecated::check(cx, &sample.crate_paths, range.clone(), fragments);
+    example_unexplained_unsafe::check(cx, &sample.unexplained_unsafe, range.clone(), fragments);
+    example_static_mut::check(cx, &sample.static_muts, range.clone(), fragments);
+    example_stderr::check(cx, &sample.stderr_writes, range.clone(), fragments);
+    example_wrong_crate_name::check(cx, &sample.crate_names, range.clone(), fragments);
+    example_assert_order::check(cx, text, &sample.swapped_asserts, range, fragments);
+}
+
+/// Replaces the star at the start of the lines of `text` with a space where it decorates a line of
+/// a block doc comment with a star at the start of each line. rustc only removes such a star when a
+/// space or another star follows it, so code written right after the stars keeps them. Replacing
+/// them keeps the byte positions in the code the same as in `text`, which starts at `start` in the
+/// documentation.
+fn unstar_block_comment_code(cx: &LateContext<'_>, text: &str, start: usize, fragments: Fragments<'_>) -> String {
+    let mut pos = start;
+    text.split_inclusive('\n')
+        .map(|line| {
+            let line_start = pos;
+            pos += line.len();
+            match line.strip_prefix('*') {
+                Some(rest) if is_block_comment_star(cx, line_start, fragments) => format!(" {rest}"),
+                _ => line.to_owned(),
+            }
+        })
+        .collect()
+}
+
+/// Returns whether the star at `pos` in the documentation is the first character of its line in a
+/// block doc comment with a star at the start of each line. A star rustc left after removing the
+/// decoration, like the second star of `**x = 1;`, is not.
+fn is_block_comment_star(cx: &LateContext<'_>, pos: usize, fragments: Fragments<'_>) -> bool {
+    let Some(span) = fragments.span(cx, pos..pos + 1) else {
+        return false;
+    };
+    let in_starred_block_comment = fragments.fragments.iter().any(|fragment| {
+        // Only the fragment of a block doc comment can span several lines.
+        matches!(fragment.kind, DocFragmentKind::SugaredDoc)
+            && fragment.doc.as_str().contains('\n')
+            && fragment.span.contains(span)
+            && snippet_opt(cx, fragment.span)
+                .is_some_and(|source| source.lines().skip(1).all(|line| line.trim_start().starts_with('*')))
+    });
+    in_starred_block_comment
+        && cx
+            .tcx
+            .sess
+            .source_map()
+            .span_to_prev_source(span)
+            .is_ok_and(|prev| prev.rsplit('\n').next().unwrap_or_default().trim().is_empty())
 }
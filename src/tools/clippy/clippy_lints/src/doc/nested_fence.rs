@@ -0,0 +1,62 @@
+use std::ops::Range;
+
+use clippy_utils::diagnostics::span_lint_and_help;
+use rustc_lint::LateContext;
+
+use super::{Fragments, DOC_NESTED_FENCE};
+
+/// Checks a fenced code block spanning `range` of the markdown. `after_container` is set if the
+/// code block immediately follows the end of a list or block quote.
+pub fn check(cx: &LateContext<'_>, after_container: bool, range: Range<usize>, fragments: Fragments<'_>) {
+    let block = &fragments.doc[range.clone()];
+    let Some(fence_char) = block.chars().next().filter(|&c| c == '`' || c == '~') else {
+        return;
+    };
+    let fence_len = fence_run(block, fence_char);
+    let first_line_len = block.find('\n').unwrap_or(block.len());
+
+    // A fence interrupting a list item or block quote without a blank line in between was most
+    // likely meant to be part of it, but it isn't indented enough to continue the container.
+    if after_container
+        && let Some(before) = fragments.doc[..range.start].trim_end_matches(' ').strip_suffix('\n')
+        && before.rsplit('\n').next().is_some_and(|line| !line.trim().is_empty())
+        && let Some(span) = fragments.span(cx, range.start..range.start + first_line_len)
+    {
+        span_lint_and_help(
+            cx,
+            DOC_NESTED_FENCE,
+            span,
+            "this code fence is not part of the preceding list item or block quote",
+            None,
+            "indent the code block to line up with the content of the list item or block quote",
+        );
+    }
+
+    // An inner fence that is at least as long as the enclosing one, and thus can only be an opening
+    // fence because of its info string. Its closing counterpart will end the enclosing block early.
+    let mut offset = range.start + first_line_len;
+    for line in block[first_line_len..].split_inclusive('\n') {
+        let trimmed = line.trim_start_matches(' ');
+        let run = fence_run(trimmed, fence_char);
+        if run >= fence_len && !trimmed[run..].trim().is_empty() {
+            let start = offset + (line.len() - trimmed.len());
+            let end = offset + line.trim_end().len();
+            if let Some(span) = fragments.span(cx, start..end) {
+                span_lint_and_help(
+                    cx,
+                    DOC_NESTED_FENCE,
+                    span,
+                    "code fence nested in a code block that uses a fence of the same length",
+                    None,
+                    "use a longer fence for the enclosing code block",
+                );
+            }
+            return;
+        }
+        offset += line.len();
+    }
+}
+
+pub(super) fn fence_run(line: &str, fence_char: char) -> usize {
+    line.len() - line.trim_start_matches(fence_char).len()
+}
@@ -0,0 +1,37 @@
+use std::ops::Range;
+
+use clippy_utils::diagnostics::span_lint_and_help;
+use rustc_lint::LateContext;
+use rustc_span::Symbol;
+
+use super::{Fragments, DOC_NONSENSE_BACKTICKS};
+
+/// Checks the inline code span `code` at `range` of the markdown. Two identifiers that are not
+/// keywords are never next to each other in an expression, type, path or pattern, so a run of
+/// plain words can't be Rust. To leave shell commands like `cargo build` alone, only runs of at
+/// least four words, or of at least two words ending like a sentence, are linted.
+pub fn check(cx: &LateContext<'_>, code: &str, range: Range<usize>, fragments: Fragments<'_>) {
+    let edition = cx.tcx.sess.edition();
+    let is_word = |word: &str| {
+        let word = word.trim_end_matches(['.', ',', ';', ':', '!', '?']);
+        word.starts_with(char::is_alphabetic)
+            && word.chars().all(|c| c.is_alphabetic() || c == '\'')
+            && !Symbol::intern(word).is_reserved(|| edition)
+    };
+
+    let words = code.split_whitespace().count();
+    let sentence_end = code.trim_end().ends_with(['.', '!', '?']);
+    if (words >= 4 || (words >= 2 && sentence_end))
+        && code.split_whitespace().all(is_word)
+        && let Some(span) = fragments.span(cx, range)
+    {
+        span_lint_and_help(
+            cx,
+            DOC_NONSENSE_BACKTICKS,
+            span,
+            "this text in backticks does not look like code",
+            None,
+            "remove the backticks",
+        );
+    }
+}
@@ -0,0 +1,25 @@
+use clippy_utils::diagnostics::span_lint_and_help;
+use rustc_lint::LateContext;
+use rustc_resolve::rustdoc::DocFragment;
+
+use super::PLACEHOLDER_DOC;
+
+/// Checks whether `doc`, the combined text of the doc comments of an item, is only one of the
+/// placeholder `markers`, ignoring case and trailing punctuation.
+pub fn check(cx: &LateContext<'_>, fragments: &[DocFragment], doc: &str, markers: &[String]) {
+    let text = doc.trim().trim_end_matches(|c: char| c.is_ascii_punctuation());
+    if let (Some(first), Some(last)) = (fragments.first(), fragments.last())
+        && !text.is_empty()
+        && markers.iter().any(|marker| marker.eq_ignore_ascii_case(text))
+        && !first.span.from_expansion()
+    {
+        span_lint_and_help(
+            cx,
+            PLACEHOLDER_DOC,
+            first.span.to(last.span),
+            "doc comment only consists of a placeholder",
+            None,
+            "document the item, or remove the doc comment",
+        );
+    }
+}
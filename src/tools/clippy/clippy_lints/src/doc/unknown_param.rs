@@ -0,0 +1,46 @@
+use clippy_utils::diagnostics::span_lint_and_sugg;
+use rustc_errors::Applicability;
+use rustc_hir::OwnerId;
+use rustc_lint::LateContext;
+use rustc_span::edit_distance::edit_distance;
+use rustc_span::symbol::kw;
+use rustc_span::Symbol;
+
+use super::{DocHeaders, DOC_UNKNOWN_PARAM};
+
+/// Returns whether the inline code span `code` could be the name of a parameter. Capitalized
+/// identifiers are types, traits or constants, and short ones are too likely to be one edit away
+/// from an unrelated parameter by chance.
+pub(super) fn is_param_like(cx: &LateContext<'_>, code: &str) -> bool {
+    code.len() > 2
+        && code.starts_with(|c: char| c.is_ascii_lowercase() || c == '_')
+        && code.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && !Symbol::intern(code).is_reserved(|| cx.tcx.sess.edition())
+}
+
+pub fn check(cx: &LateContext<'_>, owner_id: OwnerId, headers: &DocHeaders) {
+    let params = cx.tcx.fn_arg_names(owner_id.to_def_id());
+    let is_param = |name: &str| params.iter().any(|param| param.name.as_str() == name);
+    // The name of the function itself, e.g. in "`widen` returns...", is not a misspelled parameter.
+    let fn_name = cx.tcx.item_name(owner_id.to_def_id());
+
+    for (code, span) in &headers.param_like_code {
+        if is_param(code) || fn_name.as_str() == code {
+            continue;
+        }
+        if let Some(param) = params.iter().find(|param| {
+            !matches!(param.name, kw::Empty | kw::Underscore | kw::SelfLower)
+                && edit_distance(code, param.name.as_str(), 1) == Some(1)
+        }) {
+            span_lint_and_sugg(
+                cx,
+                DOC_UNKNOWN_PARAM,
+                *span,
+                &format!("the function has no parameter named `{code}`"),
+                "a parameter with a similar name exists",
+                format!("`{}`", param.name),
+                Applicability::MaybeIncorrect,
+            );
+        }
+    }
+}
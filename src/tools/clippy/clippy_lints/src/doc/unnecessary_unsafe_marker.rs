@@ -0,0 +1,95 @@
+use clippy_utils::diagnostics::span_lint_and_help;
+use rustc_hir::def::{DefKind, Res};
+use rustc_hir::def_id::DefId;
+use rustc_hir::intravisit::{walk_block, walk_expr, Visitor};
+use rustc_hir::{Block, BlockCheckMode, BodyId, Expr, ExprKind, FnSig, OwnerId, UnOp, Unsafety};
+use rustc_lint::LateContext;
+use rustc_middle::hir::nested_filter;
+use rustc_middle::ty;
+
+use super::UNNECESSARY_UNSAFE_MARKER;
+
+pub fn check(cx: &LateContext<'_>, owner_id: OwnerId, sig: &FnSig<'_>, body_id: BodyId) {
+    if sig.header.unsafety != Unsafety::Unsafe || !cx.effective_visibilities.is_exported(owner_id.def_id) {
+        return;
+    }
+
+    let mut vis = FindUnsafeOperation {
+        cx,
+        typeck_results: cx.tcx.typeck_body(body_id),
+        found: false,
+    };
+    vis.visit_nested_body(body_id);
+    if !vis.found {
+        span_lint_and_help(
+            cx,
+            UNNECESSARY_UNSAFE_MARKER,
+            cx.tcx.def_span(owner_id),
+            "this function is marked `unsafe`, but does not perform any unsafe operation",
+            None,
+            "if the function has no safety requirements, remove the `unsafe` marker",
+        );
+    }
+}
+
+/// Looks for an unsafe block or an operation that requires an unsafe context.
+struct FindUnsafeOperation<'a, 'tcx> {
+    cx: &'a LateContext<'tcx>,
+    typeck_results: &'tcx ty::TypeckResults<'tcx>,
+    found: bool,
+}
+
+impl<'a, 'tcx> FindUnsafeOperation<'a, 'tcx> {
+    fn is_unsafe_fn(&self, def_id: DefId) -> bool {
+        self.cx.tcx.fn_sig(def_id).skip_binder().unsafety() == Unsafety::Unsafe
+    }
+}
+
+impl<'a, 'tcx> Visitor<'tcx> for FindUnsafeOperation<'a, 'tcx> {
+    type NestedFilter = nested_filter::OnlyBodies;
+
+    fn visit_expr(&mut self, expr: &'tcx Expr<'_>) {
+        if self.found {
+            return;
+        }
+
+        self.found = match expr.kind {
+            ExprKind::Unary(UnOp::Deref, inner) => self.typeck_results.expr_ty(inner).is_unsafe_ptr(),
+            ExprKind::MethodCall(..) => self
+                .typeck_results
+                .type_dependent_def_id(expr.hir_id)
+                .is_some_and(|def_id| self.is_unsafe_fn(def_id)),
+            ExprKind::Call(callee, _) => match *self.typeck_results.expr_ty(callee).peel_refs().kind() {
+                ty::FnDef(def_id, _) => self.is_unsafe_fn(def_id),
+                ty::FnPtr(sig) => sig.unsafety() == Unsafety::Unsafe,
+                _ => false,
+            },
+            ExprKind::Path(ref qpath) => match self.typeck_results.qpath_res(qpath, expr.hir_id) {
+                Res::Def(DefKind::Static(_), def_id) => {
+                    self.cx.tcx.is_mutable_static(def_id) || self.cx.tcx.is_foreign_item(def_id)
+                },
+                _ => false,
+            },
+            // Reading a union field is unsafe, writing one is not, but it's not worth telling them apart.
+            ExprKind::Field(base, _) => self.typeck_results.expr_ty_adjusted(base).is_union(),
+            ExprKind::InlineAsm(_) => true,
+            _ => false,
+        };
+        if !self.found {
+            walk_expr(self, expr);
+        }
+    }
+
+    fn visit_block(&mut self, block: &'tcx Block<'_>) {
+        if matches!(block.rules, BlockCheckMode::UnsafeBlock(_)) {
+            self.found = true;
+        }
+        if !self.found {
+            walk_block(self, block);
+        }
+    }
+
+    fn nested_visit_map(&mut self) -> Self::Map {
+        self.cx.tcx.hir()
+    }
+}
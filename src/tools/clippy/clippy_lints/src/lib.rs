@@ -573,6 +573,15 @@ pub fn register_lints(store: &mut rustc_lint::LintStore, conf: &'static Conf) {
         verbose_bit_mask_threshold,
         warn_on_all_wildcard_imports,
         check_private_items,
+        ignore_unreachable_panics,
+        ignore_debug_only_panics,
+        doc_comment_style,
+        check_doc_attributes,
+        doc_tick_keywords,
+        ref returns_none_doc_heading,
+        ref doc_placeholder_markers,
+        ref required_doc_sections,
+        check_not_run_examples,
         pub_underscore_fields_behavior,
         ref allowed_duplicate_crates,
 
@@ -759,7 +768,21 @@ pub fn register_lints(store: &mut rustc_lint::LintStore, conf: &'static Conf) {
             avoid_breaking_exported_api,
         ))
     });
-    store.register_late_pass(move |_| Box::new(doc::Documentation::new(doc_valid_idents, check_private_items)));
+    store.register_late_pass(move |_| {
+        Box::new(doc::Documentation::new(
+            doc_valid_idents,
+            check_private_items,
+            ignore_unreachable_panics,
+            ignore_debug_only_panics,
+            doc_comment_style,
+            check_doc_attributes,
+            doc_tick_keywords,
+            returns_none_doc_heading,
+            doc_placeholder_markers,
+            required_doc_sections,
+            check_not_run_examples,
+        ))
+    });
     store.register_late_pass(|_| Box::new(neg_multiply::NegMultiply));
     store.register_late_pass(|_| Box::new(let_if_seq::LetIfSeq));
     store.register_late_pass(|_| Box::new(mixed_read_write_in_expression::EvalOrderDependence));
@@ -138,6 +138,7 @@ fn exit_lint_attrs(&mut self, cx: &rustc_lint::$context<'_>, attrs: &[rustc_ast:
 }
 
 /// If the given expression is a local binding, find the initializer expression.
+///
 /// If that initializer expression is another local binding, find its initializer again.
 /// This process repeats as long as possible (but usually no more than once). Initializer
 /// expressions with adjustments are ignored. If this is not desired, use [`find_binding_init`]
@@ -467,6 +468,7 @@ pub fn path_to_local(expr: &Expr<'_>) -> Option<HirId> {
 }
 
 /// Returns true if the expression is a path to a local with the specified `HirId`.
+///
 /// Use this function to see if an expression matches a function argument or a match binding.
 pub fn path_to_local_id(expr: &Expr<'_>, id: HirId) -> bool {
     path_to_local(expr) == Some(id)
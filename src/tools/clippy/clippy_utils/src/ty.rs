@@ -201,6 +201,7 @@ pub fn has_iter_method(cx: &LateContext<'_>, probably_ref_ty: Ty<'_>) -> Option<
 }
 
 /// Checks whether a type implements a trait.
+///
 /// The function returns false in case the type contains an inference variable.
 ///
 /// See:
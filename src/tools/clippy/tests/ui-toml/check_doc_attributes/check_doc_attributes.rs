@@ -0,0 +1,17 @@
+#![warn(clippy::doc_markdown)]
+
+#[doc = "use the foo_bar param"]
+//~^ ERROR: item in documentation is missing backticks
+//~| NOTE: `-D clippy::doc-markdown` implied by `-D warnings`
+pub fn foo(foo_bar: u32) {}
+
+#[doc = "the \"first\" and the qux\u{5f}quux params"]
+//~^ ERROR: item in documentation is missing backticks
+#[doc = "  and the `baz_qux` param"]
+pub fn bar(first: u32, qux_quux: u32, baz_qux: u32) {}
+
+#[doc = r#"the "raw" and the raw_str params"#]
+//~^ ERROR: item in documentation is missing backticks
+pub fn baz(raw: u32, raw_str: u32) {}
+
+fn main() {}
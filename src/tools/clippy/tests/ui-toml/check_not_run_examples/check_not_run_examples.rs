@@ -0,0 +1,34 @@
+#![feature(lang_items, start)]
+#![warn(clippy::doc_example_std_in_no_std)]
+#![no_std]
+
+/// ```no_run
+/// use std::vec::Vec;
+//~^ ERROR: this example of a `no_std` crate uses `std`
+//~| NOTE: `-D clippy::doc-example-std-in-no-std` implied by `-D warnings`
+/// ```
+pub fn no_run() {}
+
+/// ```ignore
+/// let len = std::mem::size_of_val(&0u8);
+//~^ ERROR: this example of a `no_std` crate uses `std`
+/// ```
+pub fn ignore() {}
+
+/// ```compile_fail
+/// use std::vec::Vec;
+/// ```
+pub fn compile_fail() {}
+
+#[start]
+fn main(_argc: isize, _argv: *const *const u8) -> isize {
+    0
+}
+
+#[panic_handler]
+fn panic(_info: &core::panic::PanicInfo) -> ! {
+    loop {}
+}
+
+#[lang = "eh_personality"]
+extern "C" fn eh_personality() {}
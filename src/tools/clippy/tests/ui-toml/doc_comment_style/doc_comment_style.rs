@@ -0,0 +1,22 @@
+#![warn(clippy::doc_comment_style)]
+
+fn main() {}
+
+/** Returns the answer. */
+//~^ ERROR: this documentation uses block doc comments
+//~| NOTE: `-D clippy::doc-comment-style` implied by `-D warnings`
+pub fn block() -> u32 {
+    42
+}
+
+pub mod inner_block {
+    /*! Answers. */
+    //~^ ERROR: this documentation uses block doc comments
+}
+
+/// Returns the answer.
+///
+/// Always 42.
+pub fn line() -> u32 {
+    42
+}
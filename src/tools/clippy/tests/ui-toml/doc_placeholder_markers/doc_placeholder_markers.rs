@@ -0,0 +1,11 @@
+#![warn(clippy::placeholder_doc)]
+
+/// TBD
+//~^ ERROR: doc comment only consists of a placeholder
+//~| NOTE: `-D clippy::placeholder-doc` implied by `-D warnings`
+pub fn tbd() {}
+
+/// TODO
+pub fn todo() {}
+
+fn main() {}
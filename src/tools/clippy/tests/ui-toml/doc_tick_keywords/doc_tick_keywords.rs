@@ -0,0 +1,8 @@
+#![warn(clippy::doc_markdown)]
+
+/// Can be used with async() blocks.
+//~^ ERROR: item in documentation is missing backticks
+//~| NOTE: `-D clippy::doc-markdown` implied by `-D warnings`
+pub fn foo() {}
+
+fn main() {}
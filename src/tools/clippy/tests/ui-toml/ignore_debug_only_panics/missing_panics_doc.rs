@@ -0,0 +1,35 @@
+//@compile-flags: -C debug-assertions
+#![warn(clippy::missing_panics_doc)]
+
+fn main() {}
+
+/// This needs to be documented, even though it only panics in debug builds
+pub fn debug_assert(x: u32) {
+    //~^ ERROR: docs for function which may panic missing `# Panics` section
+    debug_assert!(x != 0);
+}
+
+/// This too
+pub fn cfg_debug_assertions(x: Option<u32>) {
+    //~^ ERROR: docs for function which may panic missing `# Panics` section
+    #[cfg(debug_assertions)]
+    {
+        x.unwrap();
+    }
+}
+
+/// This panics in release builds as well, which is what gets reported
+pub fn debug_assert_and_unwrap(x: Option<u32>) -> u32 {
+    //~^ ERROR: docs for function which may panic missing `# Panics` section
+    debug_assert!(x.is_some());
+    x.unwrap()
+}
+
+/// This is documented
+///
+/// # Panics
+///
+/// Panics in debug builds if `x` is zero.
+pub fn documented(x: u32) {
+    debug_assert_ne!(x, 0);
+}
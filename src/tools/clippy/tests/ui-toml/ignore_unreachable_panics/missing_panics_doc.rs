@@ -0,0 +1,28 @@
+#![warn(clippy::missing_panics_doc)]
+#![allow(unreachable_patterns, unreachable_code)]
+
+fn main() {}
+
+/// The catch-all arm can never be reached, so this doesn't need a `# Panics` section.
+pub fn exhaustive_match(x: bool) -> u8 {
+    match x {
+        true => 1,
+        false => 0,
+        _ => panic!("cannot happen"),
+    }
+}
+
+/// Neither can the code after a match in which every arm returns.
+pub fn after_diverging_match(x: bool) -> u8 {
+    match x {
+        true => return 1,
+        false => return 0,
+    }
+    panic!("cannot happen")
+}
+
+/// This needs to be documented
+pub fn reachable(x: Option<u8>) -> u8 {
+    //~^ ERROR: docs for function which may panic missing `# Panics` section
+    x.unwrap()
+}
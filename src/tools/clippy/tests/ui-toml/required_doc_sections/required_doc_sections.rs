@@ -0,0 +1,45 @@
+#![warn(clippy::missing_required_doc_section)]
+
+fn main() {}
+
+/// Sorts the slice.
+pub fn missing_both(v: &mut [u32]) {
+    //~^ ERROR: docs for function missing `# Complexity` section
+    //~| NOTE: `-D clippy::missing-required-doc-section` implied by `-D warnings`
+    //~| ERROR: docs for function missing `# Deadlocks` section
+    v.sort();
+}
+
+/// Sorts the slice.
+///
+/// # Complexity
+///
+/// O(n * log(n)) in the length of the slice.
+///
+/// # Deadlocks
+pub fn empty_section(v: &mut [u32]) {
+    //~^ ERROR: docs for function have an empty `# Deadlocks` section
+    v.sort();
+}
+
+/// Sorts the slice.
+///
+/// # Complexity
+///
+/// O(n * log(n)) in the length of the slice.
+///
+/// # Deadlocks
+///
+/// Never, as no lock is taken.
+pub fn documented(v: &mut [u32]) {
+    v.sort();
+}
+
+/// Private functions are not checked.
+fn private(v: &mut [u32]) {
+    v.sort();
+}
+
+pub fn undocumented(v: &mut [u32]) {
+    private(v);
+}
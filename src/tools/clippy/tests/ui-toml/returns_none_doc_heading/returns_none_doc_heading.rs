@@ -0,0 +1,23 @@
+#![warn(clippy::missing_returns_none_doc)]
+
+/// Finds the first even number.
+///
+/// # Returns
+///
+/// `None` if there is no even number in `numbers`.
+pub fn first_even(numbers: &[u32]) -> Option<u32> {
+    //~^ ERROR: docs for function returning `Option` do not describe when `None` is returned
+    //~| NOTE: `-D clippy::missing-returns-none-doc` implied by `-D warnings`
+    numbers.iter().copied().find(|n| n % 2 == 0)
+}
+
+/// Finds the first odd number.
+///
+/// # None
+///
+/// If there is no odd number in `numbers`.
+pub fn first_odd(numbers: &[u32]) -> Option<u32> {
+    numbers.iter().copied().find(|n| n % 2 == 1)
+}
+
+fn main() {}
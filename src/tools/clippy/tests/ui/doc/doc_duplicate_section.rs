@@ -0,0 +1,42 @@
+#![warn(clippy::doc_duplicate_section)]
+
+/// Divides `a` by `b`.
+///
+/// # Panics
+///
+/// Panics if `b` is zero.
+///
+/// # Panics
+//~^ ERROR: the documentation already has a `Panics` section
+//~| NOTE: `-D clippy::doc-duplicate-section` implied by `-D warnings`
+///
+/// Panics if the division overflows.
+pub fn div(a: i32, b: i32) -> i32 {
+    a / b
+}
+
+/// Divides `a` by `b`.
+///
+/// # Panics
+///
+/// Panics if `b` is zero or if the division overflows.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(my_crate::div(4, 2), 2);
+/// ```
+pub fn checked_div(a: i32, b: i32) -> i32 {
+    a / b
+}
+
+/// A heading that isn't a known section may appear more than once.
+///
+/// ## Notes
+///
+/// # Usage
+///
+/// ## Notes
+pub fn notes() {}
+
+fn main() {}
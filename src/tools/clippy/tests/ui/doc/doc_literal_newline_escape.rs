@@ -0,0 +1,23 @@
+#![warn(clippy::doc_literal_newline_escape)]
+
+/// Frobnicates the frobnicator.\nReturns the number of frobnications.
+//~^ ERROR: `\n` in documentation is not a newline
+//~| NOTE: `-D clippy::doc-literal-newline-escape` implied by `-D warnings`
+pub fn prose() -> u32 {
+    0
+}
+
+/// Splits the text at `\n`, or at \\n when escaped.
+pub fn code_span_and_escaped_backslash() {}
+
+/// ```
+/// println!("one\ntwo");
+/// ```
+pub fn code_block() {}
+
+/// # Examples\n
+//~^ ERROR: `\n` in documentation is not a newline
+/// Nothing to see here.
+pub fn heading() {}
+
+fn main() {}
@@ -0,0 +1,12 @@
+#![warn(clippy::doc_markdown)]
+
+/// Ok: <http://www.unicode.org/reports/tr9/#Reordering_Resolved_Levels>
+///
+/// Not ok: http://www.unicode.org
+//~^ ERROR: you should put bare URLs between `<`/`>` or make a proper Markdown link
+//~| NOTE: `-D clippy::doc-markdown` implied by `-D warnings`
+/// Not ok: (see https://www.unicode.org/reports).
+//~^ ERROR: you should put bare URLs between `<`/`>` or make a proper Markdown link
+fn bare_urls() {}
+
+fn main() {}
@@ -0,0 +1,13 @@
+#![warn(clippy::doc_markdown)]
+
+/// Returns a [std::vec::Vec] or a [`crate::Foo`], see [the docs][crate::Foo].
+pub fn intra_doc_links() {}
+
+/// Returns a std::vec::Vec.
+//~^ ERROR: item in documentation is missing backticks
+//~| NOTE: `-D clippy::doc-markdown` implied by `-D warnings`
+pub fn path_in_prose() {}
+
+pub struct Foo;
+
+fn main() {}
@@ -0,0 +1,12 @@
+#![warn(clippy::doc_markdown)]
+
+/// Returns a future, so this is an async function. The crate can be used
+/// with async() blocks, and crate:: paths resolve from the crate root.
+pub fn keywords() {}
+
+/// An async_trait is not a keyword though.
+//~^ ERROR: item in documentation is missing backticks
+//~| NOTE: `-D clippy::doc-markdown` implied by `-D warnings`
+pub fn not_a_keyword() {}
+
+fn main() {}
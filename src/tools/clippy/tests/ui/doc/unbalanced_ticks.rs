@@ -1,5 +1,5 @@
-//! This file tests for the `DOC_MARKDOWN` lint, specifically cases
-//! where ticks are unbalanced (see issue #6753).
+//! This file tests for the `DOC_UNBALANCED_BACKTICKS` lint, and for the `DOC_MARKDOWN` lint in
+//! paragraphs where ticks are unbalanced (see issue #6753).
 //@no-rustfix
 #![allow(dead_code)]
 #![warn(clippy::doc_markdown)]
@@ -49,3 +49,8 @@ fn other_markdown() {}
 ///   pub struct Struct;
 ///   ```
 fn issue_7421() {}
+
+/// Escaped backticks like \` are rendered as text, so `this` is balanced.
+///
+/// Even \`twice\` in a row.
+fn escaped_ticks() {}
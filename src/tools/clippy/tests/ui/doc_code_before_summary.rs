@@ -0,0 +1,31 @@
+#![warn(clippy::doc_code_before_summary)]
+
+/// ```
+//~^ ERROR: documentation starts with a code block instead of a summary
+//~| NOTE: `-D clippy::doc-code-before-summary` implied by `-D warnings`
+/// let _ = code_first();
+/// ```
+///
+/// Returns the answer.
+pub fn code_first() -> u32 {
+    42
+}
+
+/// Returns the answer.
+///
+/// ```
+/// let _ = summary_first();
+/// ```
+pub fn summary_first() -> u32 {
+    42
+}
+
+/// Example:
+/// ```
+/// let _ = short_sentence_first();
+/// ```
+pub fn short_sentence_first() -> u32 {
+    42
+}
+
+fn main() {}
@@ -141,3 +141,28 @@ pub trait DocHidden {
 fn main() -> Result<(), ()> {
     Ok(())
 }
+
+/// # Errors
+pub fn pub_fn_empty_errors_header() -> Result<(), ()> {
+    //~^ ERROR: docs for function returning `Result` have an empty `# Errors` section
+    unimplemented!();
+}
+
+/// # Errors
+///
+/// # Examples
+///
+/// The `# Errors` section ends at the next heading.
+pub fn pub_fn_errors_header_followed_by_heading() -> Result<(), ()> {
+    //~^ ERROR: docs for function returning `Result` have an empty `# Errors` section
+    unimplemented!();
+}
+
+/// # Errors
+///
+/// # Errors
+///
+/// A description of the errors under a repeated heading.
+pub fn pub_fn_repeated_errors_header() -> Result<(), ()> {
+    unimplemented!();
+}
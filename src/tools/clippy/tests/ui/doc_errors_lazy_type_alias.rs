@@ -0,0 +1,29 @@
+#![feature(lazy_type_alias)]
+#![allow(incomplete_features)]
+#![warn(clippy::missing_errors_doc)]
+
+pub struct MyErr;
+
+pub type MyResult<T> = Result<T, MyErr>;
+
+pub type NestedResult<T> = MyResult<T>;
+
+/// This is not sufficiently documented.
+pub fn lazy_alias() -> MyResult<()> {
+    //~^ ERROR: docs for function returning `Result` missing `# Errors` section
+    //~| NOTE: `-D clippy::missing-errors-doc` implied by `-D warnings`
+    unimplemented!();
+}
+
+/// # Errors
+/// A description of the errors goes here.
+pub fn lazy_alias_with_errors_header() -> MyResult<()> {
+    unimplemented!();
+}
+
+/// Only one level of lazy type aliases is looked through.
+pub fn nested_lazy_alias() -> NestedResult<()> {
+    unimplemented!();
+}
+
+fn main() {}
@@ -0,0 +1,32 @@
+#![warn(clippy::doc_example_assert_order)]
+
+/// ```
+/// # fn compute() -> u32 { 5 }
+/// assert_eq!(5, compute());
+//~^ ERROR: the expected value is passed before the value under test
+//~| NOTE: `-D clippy::doc-example-assert-order` implied by `-D warnings`
+/// assert_ne!(-1, compute() as i32);
+//~^ ERROR: the expected value is passed before the value under test
+/// ```
+pub fn swapped() {}
+
+/// ```
+/// # fn compute() -> u32 { 5 }
+/// let value = compute();
+/// assert_eq!(compute(), 5);
+/// assert_ne!(value, 4, "{value} should not be 4");
+/// assert_eq!(1, 1);
+/// ```
+pub fn ordered() {}
+
+/// ```compile_fail
+/// let value: u32 = "5";
+/// assert_eq!(5, value);
+/// ```
+///
+/// ```ignore
+/// assert_eq!(5, compute());
+/// ```
+pub fn not_compiled() {}
+
+fn main() {}
@@ -0,0 +1,44 @@
+#![warn(clippy::doc_example_deprecated)]
+#![allow(deprecated, clippy::new_without_default)]
+
+/// ```
+/// doc_example_deprecated::old_frobnicate();
+//~^ ERROR: this example uses a deprecated item of the crate
+//~| NOTE: `-D clippy::doc-example-deprecated` implied by `-D warnings`
+/// use doc_example_deprecated::Frobnicator;
+/// let frobnicator = Frobnicator::old_new();
+/// doc_example_deprecated::Frobnicator::old_new();
+//~^ ERROR: this example uses a deprecated item of the crate
+/// ```
+#[deprecated = "use `frobnicate` instead"]
+pub fn old_frobnicate() {}
+
+/// ```
+/// doc_example_deprecated::frobnicate();
+/// ```
+pub fn frobnicate() {}
+
+pub struct Frobnicator;
+
+impl Frobnicator {
+    /// ```
+    /// let frobnicator = doc_example_deprecated::Frobnicator::new();
+    /// ```
+    pub fn new() -> Self {
+        Self
+    }
+
+    #[deprecated = "use `new` instead"]
+    pub fn old_new() -> Self {
+        Self
+    }
+}
+
+/// Examples that are not run are not checked.
+///
+/// ```no_run
+/// doc_example_deprecated::old_frobnicate();
+/// ```
+pub fn not_run() {}
+
+fn main() {}
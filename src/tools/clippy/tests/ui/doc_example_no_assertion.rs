@@ -0,0 +1,30 @@
+#![warn(clippy::doc_example_no_assertion)]
+
+/// ```
+/// # fn add(a: u32, b: u32) -> u32 { a + b }
+/// let sum = add(2, 2);
+//~^ ERROR: this example computes a value, but never checks it
+//~| NOTE: `-D clippy::doc-example-no-assertion` implied by `-D warnings`
+/// ```
+pub fn unchecked() {}
+
+/// ```
+/// # fn add(a: u32, b: u32) -> u32 { a + b }
+/// let sum = add(2, 2);
+/// assert_eq!(sum, 4);
+/// ```
+pub fn checked() {}
+
+/// ```
+/// let mut names = Vec::new();
+/// names.push("Ferris");
+/// ```
+pub fn constructs() {}
+
+/// ```no_run
+/// # fn add(a: u32, b: u32) -> u32 { a + b }
+/// let sum = add(2, 2);
+/// ```
+pub fn no_run() {}
+
+fn main() {}
@@ -0,0 +1,38 @@
+#![warn(clippy::doc_example_static_mut)]
+
+/// ```
+/// static mut COUNT: u32 = 0;
+//~^ ERROR: this example declares a `static mut`
+//~| NOTE: `-D clippy::doc-example-static-mut` implied by `-D warnings`
+/// unsafe { COUNT += 1 };
+/// ```
+pub fn static_mut() {}
+
+/// ```
+/// fn next_id() -> u32 {
+///     static mut NEXT: u32 = 0;
+//~^ ERROR: this example declares a `static mut`
+///     unsafe {
+///         NEXT += 1;
+///         NEXT
+///     }
+/// }
+/// ```
+pub fn nested() {}
+
+/// ```
+/// use std::sync::atomic::{AtomicU32, Ordering};
+/// static COUNT: AtomicU32 = AtomicU32::new(0);
+/// COUNT.fetch_add(1, Ordering::Relaxed);
+/// fn last(items: &'static mut [u32]) -> &'static mut u32 {
+///     items.last_mut().unwrap()
+/// }
+/// ```
+pub fn regular_static() {}
+
+/// ```ignore
+/// static mut COUNT: u32 = 0;
+/// ```
+pub fn ignored() {}
+
+fn main() {}
@@ -0,0 +1,44 @@
+#![feature(lang_items, start)]
+#![warn(clippy::doc_example_std_in_no_std)]
+#![no_std]
+
+/// ```
+/// use std::vec::Vec;
+//~^ ERROR: this example of a `no_std` crate uses `std`
+//~| NOTE: `-D clippy::doc-example-std-in-no-std` implied by `-D warnings`
+/// let bytes: Vec<u8> = Vec::new();
+/// let len = std::mem::size_of_val(&bytes);
+//~^ ERROR: this example of a `no_std` crate uses `std`
+/// ```
+pub fn uses_std() {}
+
+/// ```
+/// extern crate alloc;
+/// use alloc::vec::Vec;
+/// let bytes: Vec<u8> = Vec::new();
+/// let len = core::mem::size_of_val(&bytes);
+/// ```
+pub fn uses_core_and_alloc() {}
+
+/// ```no_run
+/// use std::vec::Vec;
+/// ```
+pub fn no_run() {}
+
+/// ```ignore
+/// use std::vec::Vec;
+/// ```
+pub fn ignore() {}
+
+#[start]
+fn main(_argc: isize, _argv: *const *const u8) -> isize {
+    0
+}
+
+#[panic_handler]
+fn panic(_info: &core::panic::PanicInfo) -> ! {
+    loop {}
+}
+
+#[lang = "eh_personality"]
+extern "C" fn eh_personality() {}
@@ -0,0 +1,33 @@
+#![warn(clippy::doc_example_stderr)]
+
+/// ```
+/// eprintln!("{}", 1 + 1);
+//~^ ERROR: this example writes to stderr
+//~| NOTE: `-D clippy::doc-example-stderr` implied by `-D warnings`
+/// ```
+pub fn eprintln() {}
+
+/// ```
+/// use std::io::Write;
+/// writeln!(std::io::stderr(), "{}", 1 + 1).unwrap();
+//~^ ERROR: this example writes to stderr
+/// ```
+pub fn stderr() {}
+
+/// ```
+/// println!("{}", 1 + 1);
+/// ```
+pub fn println() {}
+
+/// ```
+/// let output = std::process::Command::new("ls").output().unwrap();
+/// println!("{}", output.stderr.len());
+/// ```
+pub fn stderr_field() {}
+
+/// ```no_run
+/// eprintln!("{}", 1 + 1);
+/// ```
+pub fn no_run() {}
+
+fn main() {}
@@ -0,0 +1,31 @@
+#![warn(clippy::doc_example_unexplained_unsafe)]
+
+/// ```
+/// let bytes = b"hello";
+/// let s = unsafe { std::str::from_utf8_unchecked(bytes) };
+//~^ ERROR: this example uses `unsafe` without explaining why
+//~| NOTE: `-D clippy::doc-example-unexplained-unsafe` implied by `-D warnings`
+/// assert_eq!(s, "hello");
+/// ```
+pub fn bare() {}
+
+/// ```
+/// let bytes = b"hello";
+/// // SAFETY: the bytes are ASCII, which is valid UTF-8.
+/// let s = unsafe { std::str::from_utf8_unchecked(bytes) };
+/// assert_eq!(s, "hello");
+/// ```
+pub fn explained() {}
+
+/// ```
+/// unsafe trait Marker {}
+/// unsafe impl Marker for u8 {}
+/// ```
+pub fn unsafe_items() {}
+
+/// ```ignore
+/// let s = unsafe { std::str::from_utf8_unchecked(b"hello") };
+/// ```
+pub fn ignored() {}
+
+fn main() {}
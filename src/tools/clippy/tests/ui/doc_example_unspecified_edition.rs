@@ -0,0 +1,29 @@
+#![warn(clippy::doc_example_unspecified_edition)]
+
+/// ```rust
+//~^ ERROR: this example does not specify the edition it is compiled with
+//~| NOTE: `-D clippy::doc-example-unspecified-edition` implied by `-D warnings`
+/// let answer = 42;
+/// assert_eq!(answer, 42);
+/// ```
+pub fn untagged() {}
+
+/// ```
+//~^ ERROR: this example does not specify the edition it is compiled with
+/// let answer = 42;
+/// assert_eq!(answer, 42);
+/// ```
+pub fn bare_fence() {}
+
+/// ```rust,edition2021
+/// let answer = 42;
+/// assert_eq!(answer, 42);
+/// ```
+pub fn tagged() {}
+
+/// ```text
+/// not Rust
+/// ```
+pub fn text() {}
+
+fn main() {}
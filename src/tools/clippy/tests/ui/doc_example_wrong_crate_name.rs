@@ -0,0 +1,20 @@
+//@compile-flags: --crate-name foo
+// The crate name would be passed twice when checking the fixed file.
+//@no-rustfix
+
+#![warn(clippy::doc_example_wrong_crate_name)]
+
+/// ```
+/// use fooo::Bar;
+//~^ ERROR: this example refers to the crate under a wrong name
+//~| NOTE: `-D clippy::doc-example-wrong-crate-name` implied by `-D warnings`
+/// ```
+pub struct Bar;
+
+/// ```
+/// use foo::Baz;
+/// use std::collections::HashMap;
+/// ```
+pub struct Baz;
+
+fn main() {}
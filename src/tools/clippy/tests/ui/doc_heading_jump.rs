@@ -0,0 +1,28 @@
+#![warn(clippy::doc_heading_jump)]
+
+/// Does something.
+///
+/// # Examples
+///
+/// ### Basic usage
+//~^ ERROR: this heading of level 3 follows a heading of level 1
+//~| NOTE: `-D clippy::doc-heading-jump` implied by `-D warnings`
+pub fn jump() {}
+
+/// Does something.
+///
+/// # Examples
+///
+/// ## Basic usage
+///
+/// # Panics
+///
+/// Never.
+pub fn nested() {}
+
+/// Does something.
+///
+/// ### Examples
+pub fn first_heading() {}
+
+fn main() {}
@@ -0,0 +1,31 @@
+#![warn(clippy::doc_hidden_line_error)]
+
+/// ```
+/// # let x = ;
+//~^ ERROR: this hidden line of the example has a syntax error
+//~| NOTE: `-D clippy::doc-hidden-line-error` implied by `-D warnings`
+/// assert_eq!(x, 1);
+/// ```
+pub fn hidden_error() {}
+
+/// ```
+/// # let x = 1;
+/// let y = ;
+/// assert_eq!(x, y);
+/// ```
+pub fn visible_error() {}
+
+/// ```
+/// # fn setup() -> u32 { 1 }
+/// ## [derive(Debug)]
+/// struct S;
+/// assert_eq!(setup(), 1);
+/// ```
+pub fn no_error() {}
+
+/// ```ignore
+/// # let x = ;
+/// ```
+pub fn ignored() {}
+
+fn main() {}
@@ -0,0 +1,16 @@
+#![warn(clippy::doc_invalid_alias)]
+
+#[doc(alias = "bell\u{7}")]
+//~^ ERROR: this documentation alias contains a control character
+//~| NOTE: `-D clippy::doc-invalid-alias` implied by `-D warnings`
+pub fn string_form() {}
+
+#[doc(alias("append", "push\u{8}back"))]
+//~^ ERROR: this documentation alias contains a control character
+pub fn list_form() {}
+
+#[doc(alias = "push back")]
+#[doc(alias("append", "[T; N]"))]
+pub fn valid() {}
+
+fn main() {}
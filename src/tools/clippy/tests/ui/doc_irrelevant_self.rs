@@ -0,0 +1,40 @@
+#![warn(clippy::doc_irrelevant_self)]
+
+/// Returns the length of `self`.
+//~^ ERROR: the documentation of a free function refers to `self`
+//~| NOTE: `-D clippy::doc-irrelevant-self` implied by `-D warnings`
+pub fn len(s: &str) -> usize {
+    s.len()
+}
+
+/// Creates a new `Self`.
+//~^ ERROR: the documentation of a free function refers to `Self`
+pub fn new_wrapper() -> Wrapper {
+    Wrapper(0)
+}
+
+/// Returns the `self` argument of a call to `Vec::reserve`.
+pub fn reserve_receiver(call: &str) -> &str {
+    call
+}
+
+pub struct Wrapper(u32);
+
+impl Wrapper {
+    /// Returns the value of `self`.
+    pub fn get(&self) -> u32 {
+        self.0
+    }
+
+    /// Creates a new `Self` holding `value`.
+    pub fn new(value: u32) -> Self {
+        Self(value)
+    }
+}
+
+pub trait Get {
+    /// Returns the value of `self`.
+    fn get(&self) -> u32;
+}
+
+fn main() {}
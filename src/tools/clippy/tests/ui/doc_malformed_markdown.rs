@@ -0,0 +1,31 @@
+#![warn(clippy::doc_malformed_markdown)]
+
+/// #Examples
+//~^ ERROR: this looks like a heading, but is missing the space after the `#`
+//~| NOTE: `-D clippy::doc-malformed-markdown` implied by `-D warnings`
+///
+/// ```
+//~^ ERROR: this code block is never closed
+/// let _ = 1;
+pub fn unclosed_fence() {}
+
+/// See [the docs](https://example.com for more.
+//~^ ERROR: the destination of this link is never closed
+pub fn unclosed_link() {}
+
+/// # Examples
+///
+/// ```
+/// let _ = 1;
+/// ```
+///
+/// #[derive] is not a heading, and neither is #1.
+/// See [the docs](https://example.com).
+pub fn well_formed() {}
+
+/// > ```
+/// > let _ = 1;
+/// > ```
+pub fn closed_in_quote() {}
+
+fn main() {}
@@ -0,0 +1,19 @@
+#![warn(clippy::doc_malformed_table)]
+
+/// | Name | Value |
+/// | a    | 1     |
+/// | b    | 2     |
+//~^^^ ERROR: this looks like a table, but is missing the separator row after the header
+//~| NOTE: `-D clippy::doc-malformed-table` implied by `-D warnings`
+pub fn missing_separator() {}
+
+/// | Name | Value |
+/// | ---- | ----- |
+/// | a    | 1     |
+///
+/// A single row like the following isn't a table either way.
+///
+/// | a | 1 |
+pub fn table() {}
+
+fn main() {}
@@ -0,0 +1,51 @@
+#![warn(clippy::doc_missing_summary_break)]
+
+/// Returns the answer.
+//~^ ERROR: the summary of this documentation is not followed by a blank line
+//~| NOTE: `-D clippy::doc-missing-summary-break` implied by `-D warnings`
+/// It is computed very slowly, so cache it.
+pub fn run_on() -> u32 {
+    42
+}
+
+/// Returns the answer.
+///
+/// It is computed very slowly, so cache it.
+pub fn broken() -> u32 {
+    42
+}
+
+/// Returns the answer, computed from the inputs of the question, the
+/// state of the universe, and so on.
+pub fn wrapped_sentence() -> u32 {
+    42
+}
+
+/// Returns the answer of the supported questions, e.g.
+/// Life, the Universe and Everything.
+pub fn abbreviation() -> u32 {
+    42
+}
+
+/// Returns the answer to the question of life, the universe and everything, computed slowly.
+/// It is cached.
+pub fn sentence_ends_at_wrap() -> u32 {
+    42
+}
+
+/// Returns the answer.
+/// The supported questions are:
+/// - Life, the Universe and Everything.
+pub fn followed_by_list() -> u32 {
+    42
+}
+
+/// Private items are not checked.
+/// They are not listed in the documentation of the crate.
+fn private() -> u32 {
+    42
+}
+
+fn main() {
+    let _ = private();
+}
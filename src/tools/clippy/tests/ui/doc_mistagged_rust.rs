@@ -0,0 +1,60 @@
+#![warn(clippy::doc_mistagged_rust)]
+
+/// Adds two numbers.
+///
+/// ```python
+//~^ ERROR: this code block is tagged as `python`, but contains valid Rust code
+//~| NOTE: `-D clippy::doc-mistagged-rust` implied by `-D warnings`
+/// let sum = add(1, 2);
+/// ```
+pub fn rust_tagged_as_python(a: u32, b: u32) -> u32 {
+    a + b
+}
+
+/// Adds two numbers.
+///
+/// ```python
+/// def add(a, b):
+///     return a + b
+/// ```
+pub fn python(a: u32, b: u32) -> u32 {
+    a + b
+}
+
+/// Adds two numbers.
+///
+/// ```python
+/// total = add(1, 2)
+/// ```
+pub fn python_expression(a: u32, b: u32) -> u32 {
+    a + b
+}
+
+/// Adds two numbers.
+///
+/// ```text
+/// let sum = add(1, 2);
+/// ```
+pub fn text(a: u32, b: u32) -> u32 {
+    a + b
+}
+
+/// Adds two numbers.
+///
+/// ```rust,ignore
+/// let sum = add(1, 2);
+/// ```
+pub fn ignored(a: u32, b: u32) -> u32 {
+    a + b
+}
+
+/// Adds two numbers.
+///
+/// ```rs
+/// let sum = add(1, 2);
+/// ```
+pub fn rs(a: u32, b: u32) -> u32 {
+    a + b
+}
+
+fn main() {}
@@ -0,0 +1,49 @@
+#![warn(clippy::doc_nested_fence)]
+
+/// - Call it like this:
+/// ```
+//~^ ERROR: this code fence is not part of the preceding list item or block quote
+//~| NOTE: `-D clippy::doc-nested-fence` implied by `-D warnings`
+/// let _ = 1;
+/// ```
+pub fn unindented_in_list() {}
+
+/// > Quoted:
+/// ```
+//~^ ERROR: this code fence is not part of the preceding list item or block quote
+/// let _ = 1;
+/// ```
+pub fn unindented_in_quote() {}
+
+/// ```markdown
+/// Example:
+/// ```rust
+//~^ ERROR: code fence nested in a code block that uses a fence of the same length
+/// let _ = 1;
+/// ```
+/// ```
+pub fn same_length_nested() {}
+
+/// - Call it like this:
+///
+///   ```
+///   let _ = 1;
+///   ```
+pub fn indented_in_list() {}
+
+/// - A list.
+///
+/// ```
+/// let _ = 1;
+/// ```
+pub fn separated_from_list() {}
+
+/// ````markdown
+/// Example:
+/// ```rust
+/// let _ = 1;
+/// ```
+/// ````
+pub fn longer_outer_fence() {}
+
+fn main() {}
@@ -0,0 +1,31 @@
+#![warn(clippy::doc_nonexistent_param)]
+#![allow(clippy::doc_unknown_param)]
+
+/// Resizes the window.
+///
+/// # Arguments
+///
+/// * `size` - the new width in pixels
+//~^ ERROR: `size` is documented as a parameter, but the function has no parameter with that name
+//~| NOTE: `-D clippy::doc-nonexistent-param` implied by `-D warnings`
+/// * `height` - the new height in pixels, see `min_height`
+pub fn resize(width: u32, height: u32) {}
+
+/// Moves the window by `offst` pixels, the `speed` argument is the speed of the animation.
+//~^ ERROR: `speed` is documented as a parameter, but the function has no parameter with that name
+pub fn shift(offset: u32) {}
+
+/// Moves the window by `offset` pixels, with `speed` pixels per frame.
+///
+/// The `offset` parameter may be zero.
+pub fn shift_animated(offset: u32, speed: u32) {}
+
+/// Scales the window.
+///
+/// # Parameters
+///
+/// - `facter`: the scale factor
+//~^ ERROR: `facter` is documented as a parameter, but the function has no parameter with that name
+pub fn scale(factor: f32) {}
+
+fn main() {}
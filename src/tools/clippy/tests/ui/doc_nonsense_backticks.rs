@@ -0,0 +1,17 @@
+#![warn(clippy::doc_nonsense_backticks)]
+
+/// Returns `a full sentence here`
+//~^ ERROR: this text in backticks does not look like code
+//~| NOTE: `-D clippy::doc-nonsense-backticks` implied by `-D warnings`
+pub fn sentence() {}
+
+/// Panics if `this is not set.`
+//~^ ERROR: this text in backticks does not look like code
+pub fn sentence_end() {}
+
+/// Returns a `Vec<u8>` of the bytes, see `impl Trait for Type` and `T: Clone`.
+///
+/// Build it with `cargo build` or `cargo build --release`, then call `foo(bar)`.
+pub fn code() {}
+
+fn main() {}
@@ -0,0 +1,62 @@
+#![warn(clippy::doc_rust_in_text_block)]
+
+/// Adds two numbers.
+///
+/// ```text
+//~^ ERROR: this code block is tagged as `text`, but contains valid Rust code
+//~| NOTE: `-D clippy::doc-rust-in-text-block` implied by `-D warnings`
+/// let sum = add(1, 2);
+/// ```
+pub fn rust_tagged_as_text(a: u32, b: u32) -> u32 {
+    a + b
+}
+
+/// Adds two numbers.
+///
+/// ```plain
+//~^ ERROR: this code block is tagged as `plain`, but contains valid Rust code
+/// fn double(x: u32) -> u32 {
+///     add(x, x)
+/// }
+/// ```
+pub fn rust_tagged_as_plain(a: u32, b: u32) -> u32 {
+    a + b
+}
+
+/// Adds two numbers, printing:
+///
+/// ```text
+/// sum = 3
+/// ```
+pub fn output(a: u32, b: u32) -> u32 {
+    a + b
+}
+
+/// Adds two numbers.
+///
+/// ```text
+/// $ cargo run --example add -- 1 2
+/// ```
+pub fn shell(a: u32, b: u32) -> u32 {
+    a + b
+}
+
+/// Adds two numbers.
+///
+/// ```text
+/// add(1, 2)
+/// ```
+pub fn expression(a: u32, b: u32) -> u32 {
+    a + b
+}
+
+/// Adds two numbers.
+///
+/// ```console
+/// let sum = add(1, 2);
+/// ```
+pub fn console(a: u32, b: u32) -> u32 {
+    a + b
+}
+
+fn main() {}
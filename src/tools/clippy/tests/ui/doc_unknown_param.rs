@@ -0,0 +1,21 @@
+#![warn(clippy::doc_unknown_param)]
+
+/// Resizes the window to be `widht` pixels wide.
+//~^ ERROR: the function has no parameter named `widht`
+//~| NOTE: `-D clippy::doc-unknown-param` implied by `-D warnings`
+pub fn resize(width: u32) {}
+
+/// Resizes the window to be `width` pixels wide and `height` pixels high.
+///
+/// See also `resize`, `Window` and `u32`.
+pub fn resize_both(width: u32, height: u32) {}
+
+pub struct Window;
+
+impl Window {
+    /// Moves the window by `offste` pixels.
+    //~^ ERROR: the function has no parameter named `offste`
+    pub fn shift(&self, offset: u32) {}
+}
+
+fn main() {}
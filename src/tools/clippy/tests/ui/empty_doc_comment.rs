@@ -0,0 +1,22 @@
+#![warn(clippy::empty_doc_comment)]
+
+///
+//~^ ERROR: empty doc comment
+//~| NOTE: `-D clippy::empty-doc-comment` implied by `-D warnings`
+pub fn lone_empty_line() {}
+
+///
+///
+//~^^ ERROR: empty doc comment
+pub fn several_empty_lines() {}
+
+#[doc = "   "]
+//~^ ERROR: empty doc comment
+pub fn blank_attribute() {}
+
+/// Documented.
+pub fn documented() {}
+
+pub fn undocumented() {}
+
+fn main() {}
@@ -0,0 +1,56 @@
+#![warn(clippy::missing_examples_doc)]
+
+/// Adds one to the number.
+pub fn add_one(x: u32) -> u32 {
+    //~^ ERROR: docs for function missing `# Examples` section
+    //~| NOTE: `-D clippy::missing-examples-doc` implied by `-D warnings`
+    x + 1
+}
+
+/// Adds two to the number.
+///
+/// # Examples
+///
+/// Adding two to one gives three.
+pub fn add_two(x: u32) -> u32 {
+    x + 2
+}
+
+/// Adds three to the number.
+///
+/// ```
+/// assert_eq!(add_three(1), 4);
+/// ```
+pub fn add_three(x: u32) -> u32 {
+    x + 3
+}
+
+/// A counter.
+pub struct Counter(u32);
+
+impl Counter {
+    /// Increments the counter.
+    pub fn increment(&mut self) {
+        //~^ ERROR: docs for function missing `# Examples` section
+        self.0 += 1;
+    }
+}
+
+/// Hidden functions are not checked.
+#[doc(hidden)]
+pub fn hidden(x: u32) -> u32 {
+    x
+}
+
+pub fn undocumented(x: u32) -> u32 {
+    x
+}
+
+/// Private functions are not checked.
+fn private(x: u32) -> u32 {
+    x
+}
+
+fn main() {
+    let _ = private(0);
+}
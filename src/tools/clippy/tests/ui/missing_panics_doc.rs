@@ -191,3 +191,88 @@ fn from_declared_macro_should_lint_at_macrosite() {
     // Not here.
     some_macro_that_panics!()
 }
+
+/// This needs to be documented, as it panics when called at runtime
+pub const fn const_fn_assert(x: u32) -> u32 {
+    assert!(x != 0);
+    100 / x
+}
+
+/// This is documented
+///
+/// # Panics
+///
+/// Panics if `x` is zero, or fails to compile if it is evaluated at compile time
+pub const fn const_fn_assert_documented(x: u32) -> u32 {
+    assert!(x != 0);
+    100 / x
+}
+
+/// This only asserts at compile time, so it doesn't need to be documented
+pub const fn const_item_assert() -> u32 {
+    const CHECK: () = assert!(u32::BITS == 32);
+    u32::BITS
+}
+
+/// This needs to be documented, as the conversion panics if `big` doesn't fit in a `u8`
+pub fn try_into_unwrap(big: u32) -> u8 {
+    let n: u8 = big.try_into().unwrap();
+    n / 2
+}
+
+/// This needs to be documented, as it only forwards to a parse that panics on invalid input
+pub fn forward_parse_unwrap(s: &str) -> u32 {
+    s.parse().unwrap()
+}
+
+/// This needs to be documented, as `Vec::remove` panics if `index` is out of bounds
+pub fn forward_remove(v: &mut Vec<u32>, index: usize) -> u32 {
+    v.remove(index)
+}
+
+/// This needs to be documented, as `str::split_at` panics if `mid` is out of bounds
+pub fn forward_split_at(s: &str, mid: usize) -> (&str, &str) {
+    s.split_at(mid)
+}
+
+/// This needs to be documented, as `<[T]>::swap` panics if `a` or `b` is out of bounds
+pub fn forward_swap_path(s: &mut [u8], a: usize, b: usize) {
+    <[u8]>::swap(s, a, b)
+}
+
+/// This is documented
+///
+/// # Panics
+///
+/// Panics if `index` is out of bounds
+pub fn forward_remove_documented(v: &mut Vec<u32>, index: usize) -> u32 {
+    v.remove(index)
+}
+
+/// This does more than forwarding to `Vec::remove`, so it isn't checked
+pub fn checked_remove(v: &mut Vec<u32>, index: usize) -> Option<u32> {
+    if index < v.len() {
+        Some(v.remove(index))
+    } else {
+        None
+    }
+}
+
+/// This only forwards to `Vec::pop`, which doesn't panic
+pub fn forward_pop(v: &mut Vec<u32>) -> Option<u32> {
+    v.pop()
+}
+
+/// # Panics
+pub fn unwrap_empty_panics(v: &[u32], index: usize) -> u32 {
+    *v.get(index).unwrap()
+}
+
+/// # Panics
+///
+/// ```
+/// assert!(std::panic::catch_unwind(|| panic!()).is_err());
+/// ```
+pub fn panics_with_example() {
+    panic!()
+}
@@ -0,0 +1,50 @@
+#![warn(clippy::missing_returns_none_doc)]
+
+/// Finds the first even number.
+pub fn first_even(numbers: &[u32]) -> Option<u32> {
+    //~^ ERROR: docs for function returning `Option` do not describe when `None` is returned
+    //~| NOTE: `-D clippy::missing-returns-none-doc` implied by `-D warnings`
+    numbers.iter().copied().find(|n| n % 2 == 0)
+}
+
+/// Finds the first odd number.
+///
+/// # Returns
+///
+/// `None` if there is no odd number in `numbers`.
+pub fn first_odd(numbers: &[u32]) -> Option<u32> {
+    numbers.iter().copied().find(|n| n % 2 == 1)
+}
+
+pub struct Numbers(Vec<u32>);
+
+impl Numbers {
+    /// Returns the largest number.
+    pub fn max(&self) -> Option<u32> {
+        //~^ ERROR: docs for function returning `Option` do not describe when `None` is returned
+        self.0.iter().copied().max()
+    }
+
+    /// Returns the smallest number.
+    ///
+    /// # Returns
+    ///
+    /// `None` if there are no numbers.
+    pub fn min(&self) -> Option<u32> {
+        self.0.iter().copied().min()
+    }
+}
+
+/// Private functions are not checked.
+fn private_first_even(numbers: &[u32]) -> Option<u32> {
+    numbers.iter().copied().find(|n| n % 2 == 0)
+}
+
+/// Functions not returning an `Option` are not checked.
+pub fn count(numbers: &[u32]) -> usize {
+    numbers.len()
+}
+
+fn main() {
+    let _ = private_first_even(&[]);
+}
@@ -35,8 +35,52 @@
 ///     unimplemented!();
 /// }
 /// ```
+///
+/// As should a `main` returning `Result<(), E>`, rustdoc supports `Ok::<(), E>(())` instead
+/// ```
+/// fn main() -> Result<(), std::num::ParseIntError> {
+//~^ ERROR: needless `fn main` in doctest
+///     let _answer: i32 = "42".parse()?;
+///     Ok(())
+/// }
+/// ```
 fn bad_doctests() {}
 
+/**
+ * This should lint too, even though the code starts right after the stars:
+ * ```
+ *fn main() {
+ *    unimplemented!();
+ *}
+ * ```
+ */
+//~^^^^^ ERROR: needless `fn main` in doctest
+fn bad_block_doctest() {}
+
+/**
+ * This should lint, the deref at the start of the line keeps its star:
+ * ```
+ *fn main() {
+ *    let y = 2
+ **x;
+ *}
+ * ```
+ */
+//~^^^^^^ ERROR: needless `fn main` in doctest
+fn bad_block_doctest_deref() {}
+
+/**
+ * The stars of this comment don't apply to the code below.
+ */
+/// ```
+/// fn main() {
+///     let y = 2
+/// *x;
+/// }
+/// ```
+//~^^^^^ ERROR: needless `fn main` in doctest
+fn bad_doctest_after_block_comment() {}
+
 /// # Examples
 ///
 /// This shouldn't lint, because the `main` is empty:
@@ -58,6 +102,21 @@ fn bad_doctests() {}
 /// }
 /// ```
 ///
+/// Nor here, because the return type is not written as `Result<(), E>`:
+/// ```
+/// fn main() -> std::io::Result<()> {
+///     std::fs::write("answer.txt", "42")?;
+///     Ok(())
+/// }
+/// ```
+///
+/// Nor here, because the body doesn't end in `Ok(())`:
+/// ```
+/// fn main() -> Result<(), std::num::ParseIntError> {
+///     "42".parse::<i32>().map(drop)
+/// }
+/// ```
+///
 /// This shouldn't lint either, because there's a `static`:
 /// ```
 /// static ANSWER: i32 = 42;
@@ -119,6 +178,13 @@ fn bad_doctests() {}
 /// let _inception = 42;
 /// ```
 ///
+/// Nor `compile_fail` examples, whose `main` may be part of what fails to compile:
+/// ```compile_fail
+/// fn main() {
+///     let _x: i32 = "42";
+/// }
+/// ```
+///
 /// We should not lint ignored examples:
 /// ```rust,ignore
 /// fn main() {
@@ -0,0 +1,18 @@
+#![warn(clippy::placeholder_doc)]
+
+/// TODO
+//~^ ERROR: doc comment only consists of a placeholder
+//~| NOTE: `-D clippy::placeholder-doc` implied by `-D warnings`
+pub fn todo() {}
+
+/// fixme.
+//~^ ERROR: doc comment only consists of a placeholder
+pub fn fixme() {}
+
+/// TODO: real description here
+pub fn described() {}
+
+/// Documented.
+pub fn documented() {}
+
+fn main() {}
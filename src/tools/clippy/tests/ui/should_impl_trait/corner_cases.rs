@@ -9,7 +9,8 @@
     clippy::wrong_self_convention,
     clippy::missing_panics_doc,
     clippy::return_self_not_must_use,
-    clippy::unused_async
+    clippy::unused_async,
+    clippy::unnecessary_unsafe_marker
 )]
 
 use std::ops::Mul;
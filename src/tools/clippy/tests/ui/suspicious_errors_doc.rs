@@ -0,0 +1,62 @@
+#![warn(clippy::suspicious_errors_doc)]
+
+pub enum Parse {
+    Empty,
+    NotANumber,
+}
+
+/// Parses a number.
+///
+/// # Errors
+//~^ ERROR: this `# Errors` section does not describe any error
+//~| NOTE: `-D clippy::suspicious-errors-doc` implied by `-D warnings`
+///
+/// The input is trimmed before parsing.
+pub fn no_error_condition(input: &str) -> Result<u32, Parse> {
+    input.trim().parse().map_err(|_| Parse::NotANumber)
+}
+
+/// Parses a number.
+///
+/// # Errors
+///
+/// Returns an error if the input is not a number.
+pub fn mentions_error(input: &str) -> Result<u32, Parse> {
+    input.parse().map_err(|_| Parse::NotANumber)
+}
+
+/// Parses a number.
+///
+/// # Errors
+///
+/// Returns `Err` if the input is not a number.
+pub fn mentions_err(input: &str) -> Result<u32, Parse> {
+    input.parse().map_err(|_| Parse::NotANumber)
+}
+
+/// Parses a number.
+///
+/// # Errors
+///
+/// Fails if the input is not a number.
+pub fn mentions_failure(input: &str) -> Result<u32, Parse> {
+    input.parse().map_err(|_| Parse::NotANumber)
+}
+
+/// Parses a number.
+///
+/// # Errors
+///
+/// [`Parse::Empty`] if the input is empty.
+///
+/// # Examples
+///
+/// The input is not trimmed.
+pub fn mentions_variant(input: &str) -> Result<u32, Parse> {
+    if input.is_empty() {
+        return Err(Parse::Empty);
+    }
+    input.parse().map_err(|_| Parse::NotANumber)
+}
+
+fn main() {}
@@ -0,0 +1,35 @@
+#![warn(clippy::unnecessary_unsafe_marker)]
+#![allow(clippy::missing_safety_doc)]
+
+pub unsafe fn safe_body(a: u32, b: u32) -> u32 {
+    //~^ ERROR: this function is marked `unsafe`, but does not perform any unsafe operation
+    //~| NOTE: `-D clippy::unnecessary-unsafe-marker` implied by `-D warnings`
+    a / b
+}
+
+pub unsafe fn deref_raw_pointer(ptr: *const u32) -> u32 {
+    *ptr
+}
+
+pub unsafe fn call_unsafe_fn(ptr: *const u32) -> u32 {
+    deref_raw_pointer(ptr)
+}
+
+pub unsafe fn unsafe_block(ptr: *const u32) -> u32 {
+    unsafe { *ptr }
+}
+
+pub struct S;
+
+impl S {
+    pub unsafe fn safe_method(&self) -> u32 {
+        //~^ ERROR: this function is marked `unsafe`, but does not perform any unsafe operation
+        42
+    }
+}
+
+unsafe fn private_safe_body() {}
+
+fn main() {
+    unsafe { private_safe_body() };
+}
@@ -993,24 +993,25 @@ fn collect_macro_call<T, U>(
             }
         };
         if record_diagnostics {
-            match &res.err {
-                Some(ExpandError::UnresolvedProcMacro(krate)) => {
-                    self.source_map.diagnostics.push(BodyDiagnostic::UnresolvedProcMacro {
-                        node: InFile::new(outer_file, syntax_ptr),
-                        krate: *krate,
-                    });
-                }
-                Some(ExpandError::RecursionOverflowPoisoned) => {
-                    // Recursion limit has been reached in the macro expansion tree, but not in
-                    // this very macro call. Don't add diagnostics to avoid duplication.
-                }
-                Some(err) => {
-                    self.source_map.diagnostics.push(BodyDiagnostic::MacroError {
-                        node: InFile::new(outer_file, syntax_ptr),
-                        message: err.to_string(),
-                    });
+            for err in res.err.iter().flat_map(ExpandError::errors) {
+                match err {
+                    ExpandError::UnresolvedProcMacro(krate) => {
+                        self.source_map.diagnostics.push(BodyDiagnostic::UnresolvedProcMacro {
+                            node: InFile::new(outer_file, syntax_ptr),
+                            krate: *krate,
+                        });
+                    }
+                    ExpandError::RecursionOverflowPoisoned => {
+                        // Recursion limit has been reached in the macro expansion tree, but not in
+                        // this very macro call. Don't add diagnostics to avoid duplication.
+                    }
+                    err => {
+                        self.source_map.diagnostics.push(BodyDiagnostic::MacroError {
+                            node: InFile::new(outer_file, syntax_ptr),
+                            message: err.to_string(),
+                        });
+                    }
                 }
-                None => {}
             }
         }
 
@@ -1,9 +1,12 @@
 //! Defines database & queries for name resolution.
 use base_db::{salsa, CrateId, SourceDatabase, Upcast};
 use either::Either;
-use hir_expand::{db::ExpandDatabase, HirFileId, MacroDefId};
+use hir_expand::{
+    db::ExpandDatabase, name::Name, tt, ExpandResult, HirFileId, MacroCallId, MacroDefId,
+};
 use intern::Interned;
 use la_arena::ArenaMap;
+use rustc_hash::FxHashSet;
 use syntax::{ast, AstPtr};
 use triomphe::Arc;
 
@@ -25,9 +28,9 @@
     EnumId, EnumLoc, EnumVariantId, EnumVariantLoc, ExternBlockId, ExternBlockLoc, ExternCrateId,
     ExternCrateLoc, FunctionId, FunctionLoc, GenericDefId, ImplId, ImplLoc, InTypeConstId,
     InTypeConstLoc, LocalFieldId, Macro2Id, Macro2Loc, MacroId, MacroRulesId, MacroRulesLoc,
-    MacroRulesLocFlags, ProcMacroId, ProcMacroLoc, StaticId, StaticLoc, StructId, StructLoc,
-    TraitAliasId, TraitAliasLoc, TraitId, TraitLoc, TypeAliasId, TypeAliasLoc, UnionId, UnionLoc,
-    UseId, UseLoc, VariantId,
+    MacroRulesLocFlags, ModuleDefId, ProcMacroId, ProcMacroLoc, StaticId, StaticLoc, StructId,
+    StructLoc, TraitAliasId, TraitAliasLoc, TraitId, TraitLoc, TypeAliasId, TypeAliasLoc, UnionId,
+    UnionLoc, UseId, UseLoc, VariantId,
 };
 
 #[salsa::query_group(InternDatabaseStorage)]
@@ -101,6 +104,34 @@ pub trait DefDatabase: InternDatabase + ExpandDatabase + Upcast<dyn ExpandDataba
 
     fn macro_def(&self, m: MacroId) -> MacroDefId;
 
+    /// Returns the macros defined in the crate, outside of blocks, sorted by name.
+    fn all_macro_defs_in_crate(&self, krate: CrateId) -> Arc<[(Name, MacroDefId)]>;
+
+    /// Returns the macro calls in the expansion of the macro call, in source order. These are the
+    /// attribute, derive and function-like macro calls name resolution collected from the
+    /// expansion, and the function-like macro calls it does not collect, like the ones in function
+    /// bodies, resolved in the scope of their module.
+    #[salsa::invoke(crate::nameres::expansion_tree::nested_macro_calls_query)]
+    fn nested_macro_calls(&self, macro_call: MacroCallId) -> Arc<[MacroCallId]>;
+
+    /// Returns the largest number of nested expansions needed to produce the output of the macro
+    /// call: 1 if its expansion contains no macro calls, 2 if the calls in it expand to no further
+    /// macro calls, and so on.
+    #[salsa::transparent]
+    #[salsa::invoke(crate::nameres::expansion_tree::macro_expansion_depth_query)]
+    fn macro_expansion_depth(&self, macro_call: MacroCallId) -> usize;
+
+    /// Expands the macro call and the function-like macro calls in its expansion, up to `depth`
+    /// levels deep. Calls below that are left unexpanded, so a depth of 0 returns the macro call
+    /// itself and a depth of 1 its expansion.
+    // This query is LRU cached
+    #[salsa::invoke(crate::nameres::expansion_tree::macro_expand_to_depth_query)]
+    fn macro_expand_to_depth(
+        &self,
+        macro_call: MacroCallId,
+        depth: u32,
+    ) -> ExpandResult<Arc<tt::Subtree>>;
+
     // region:data
 
     #[salsa::transparent]
@@ -274,6 +305,35 @@ fn crate_supports_no_std(db: &dyn DefDatabase, crate_id: CrateId) -> bool {
     false
 }
 
+fn all_macro_defs_in_crate(db: &dyn DefDatabase, krate: CrateId) -> Arc<[(Name, MacroDefId)]> {
+    use crate::HasModule;
+
+    let def_map = db.crate_def_map(krate);
+    let mut seen = FxHashSet::default();
+    let mut macros = Vec::new();
+    for (_, module) in def_map.modules() {
+        let declared = module.scope.declarations().filter_map(|def| match def {
+            ModuleDefId::MacroId(it) => Some(it),
+            _ => None,
+        });
+        // Textually scoped macros are visible in, but not declared by, the modules that follow
+        // them, so they need to be deduplicated.
+        let textual = module.scope.legacy_macros().flat_map(|(_, it)| it.iter().copied());
+        for id in declared.chain(textual) {
+            if id.module(db).krate() == krate && seen.insert(id) {
+                let name = match id {
+                    MacroId::Macro2Id(it) => db.macro2_data(it).name.clone(),
+                    MacroId::MacroRulesId(it) => db.macro_rules_data(it).name.clone(),
+                    MacroId::ProcMacroId(it) => db.proc_macro_data(it).name.clone(),
+                };
+                macros.push((name, db.macro_def(id)));
+            }
+        }
+    }
+    macros.sort_by(|(a, _), (b, _)| a.cmp(b));
+    macros.into()
+}
+
 fn macro_def(db: &dyn DefDatabase, id: MacroId) -> MacroDefId {
     use hir_expand::InFile;
 
@@ -336,6 +336,12 @@ pub(crate) fn resolutions(&self) -> impl Iterator<Item = (Option<Name>, PerNs)>
     pub(crate) fn macro_invoc(&self, call: AstId<ast::MacroCall>) -> Option<MacroCallId> {
         self.macro_invocations.get(&call).copied()
     }
+
+    pub(crate) fn macro_invocs(
+        &self,
+    ) -> impl Iterator<Item = (AstId<ast::MacroCall>, MacroCallId)> + '_ {
+        self.macro_invocations.iter().map(|(k, v)| (*k, *v))
+    }
 }
 
 impl ItemScope {
@@ -1,8 +1,10 @@
 //! Tests for `builtin_fn_macro.rs` from `hir_expand`.
 
 use expect_test::expect;
+use hir_expand::db::ExpandDatabase;
+use syntax::{AstNode, SyntaxKind::IDENT};
 
-use crate::macro_expansion_tests::check;
+use crate::macro_expansion_tests::{check, resolve_macro_calls};
 
 #[test]
 fn test_column_expand() {
@@ -105,6 +107,42 @@ fn main() {
     );
 }
 
+#[test]
+fn test_stringify_ident_map() {
+    let (db, calls) = resolve_macro_calls(
+        r#"
+#[rustc_builtin_macro]
+macro_rules! stringify {() => {}}
+
+fn main() {
+    stringify!(a + my_var);
+}
+"#,
+    );
+    let [(call, id)] = &calls[..] else { panic!("expected a single macro call") };
+    let map = db.stringify_ident_map(*id).unwrap();
+    let stringified = "a + my_var";
+    let mapped = map
+        .iter()
+        .map(|&(range, span)| {
+            let anchor_offset = db
+                .ast_id_map(span.anchor.file_id.into())
+                .get_erased(span.anchor.ast_id)
+                .text_range()
+                .start();
+            (&stringified[range], span.range + anchor_offset)
+        })
+        .collect::<Vec<_>>();
+
+    let my_var = call
+        .syntax()
+        .descendants_with_tokens()
+        .find(|it| it.kind() == IDENT && it.to_string() == "my_var")
+        .unwrap();
+    assert_eq!(mapped.len(), 2, "punctuation must not be mapped");
+    assert_eq!(mapped[1], ("my_var", my_var.text_range()));
+}
+
 #[test]
 fn test_env_expand() {
     check(
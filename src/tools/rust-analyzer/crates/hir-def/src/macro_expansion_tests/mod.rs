@@ -13,6 +13,7 @@
 mod builtin_fn_macro;
 mod builtin_derive_macro;
 mod proc_macros;
+mod queries;
 
 use std::{iter, ops::Range, sync};
 
@@ -22,7 +23,7 @@
     db::ExpandDatabase,
     proc_macro::{ProcMacro, ProcMacroExpander, ProcMacroExpansionError, ProcMacroKind},
     span_map::SpanMapRef,
-    InFile, MacroFileId, MacroFileIdExt,
+    InFile, MacroCallId, MacroFileId, MacroFileIdExt,
 };
 use span::Span;
 use stdx::format_to;
@@ -220,6 +221,43 @@ pub fn identity_when_valid(_attr: TokenStream, item: TokenStream) -> TokenStream
     expect.assert_eq(&expanded_text);
 }
 
+/// Sets up a database for the fixture and resolves all fn-like macro calls in the crate root, in
+/// source order.
+#[track_caller]
+fn resolve_macro_calls(ra_fixture: &str) -> (TestDB, Vec<(ast::MacroCall, MacroCallId)>) {
+    let db = TestDB::with_files(ra_fixture);
+    let calls = resolve_macro_calls_in(&db);
+    (db, calls)
+}
+
+/// Resolves all fn-like macro calls in the crate root of `db`, in source order.
+#[track_caller]
+fn resolve_macro_calls_in(db: &TestDB) -> Vec<(ast::MacroCall, MacroCallId)> {
+    let krate = db.crate_graph().iter().next().unwrap();
+    let def_map = db.crate_def_map(krate);
+    let resolver = def_map.module_id(DefMap::ROOT).resolver(db);
+    let source = def_map[DefMap::ROOT].definition_source(db);
+    let ModuleSource::SourceFile(source_file) = source.value else { panic!() };
+
+    source_file
+        .syntax()
+        .descendants()
+        .filter_map(ast::MacroCall::cast)
+        .map(|macro_call| {
+            let id = InFile::new(source.file_id, &macro_call)
+                .as_call_id_with_errors(db, krate, |path| {
+                    resolver
+                        .resolve_path_as_macro(db, &path, Some(MacroSubNs::Bang))
+                        .map(|(it, _)| db.macro_def(it))
+                })
+                .unwrap()
+                .value
+                .unwrap();
+            (macro_call, id)
+        })
+        .collect()
+}
+
 fn reindent(indent: IndentLevel, pp: String) -> String {
     if !pp.contains('\n') {
         return pp;
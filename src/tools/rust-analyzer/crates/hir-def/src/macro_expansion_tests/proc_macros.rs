@@ -3,9 +3,37 @@
 //! Note `//- proc_macros: identity` fixture metas in tests -- we don't use real
 //! proc-macros here, as that would be slow. Instead, we use several hard-coded
 //! in-memory macros.
+use std::sync::{
+    self,
+    atomic::{AtomicUsize, Ordering},
+};
+
+use base_db::{salsa::Durability, Env, SourceDatabase, SourceDatabaseExt};
 use expect_test::expect;
+use hir_expand::{
+    attrs::{build_attr_arg, collect_attrs},
+    db::{clear_proc_macro_cache, expand_macro_with_limit, ExpandDatabase},
+    proc_macro::{
+        ProcMacro, ProcMacroExpander, ProcMacroExpansionDedup, ProcMacroExpansionError,
+        ProcMacroKind,
+    },
+    span_map::SpanMapRef,
+    tt::{self, DelimiterKind},
+    HirFileIdExt, MacroCallKind, MacroFileId,
+};
+use limit::Limit;
+use rustc_hash::FxHashMap;
+use span::Span;
+use syntax::AstNode;
+use test_fixture::WithFixture;
+use triomphe::Arc;
 
-use crate::macro_expansion_tests::check;
+use crate::{
+    db::DefDatabase,
+    macro_expansion_tests::{check, resolve_macro_calls_in},
+    nameres::DefMap,
+    test_db::TestDB,
+};
 
 #[test]
 fn attribute_macro_attr_censoring() {
@@ -186,3 +214,430 @@ fn foo(&self) {
 }#0:1@76..77#0#"#]],
     );
 }
+
+#[test]
+fn attribute_macro_arg_matches_build_attr_arg() {
+    let db = TestDB::with_files(
+        r#"
+//- proc_macros: identity
+#[proc_macros::identity(a, b = "c")]
+fn foo() {}
+"#,
+    );
+    let krate = db.crate_graph().iter().next().unwrap();
+    let def_map = db.crate_def_map(krate);
+    let (ast_id, call) = def_map[DefMap::ROOT].scope.attr_macro_invocs().next().unwrap();
+    let loc = db.lookup_intern_macro_call(call);
+    let MacroCallKind::Attr { attr_args: Some(attr_args), invoc_attr_index, .. } = &loc.kind else {
+        panic!("expected an attribute macro call with arguments, got {:?}", loc.kind);
+    };
+
+    let item = ast_id.to_node(&db);
+    let attr = collect_attrs(&item)
+        .nth(invoc_attr_index.ast_index())
+        .and_then(|(_, attr)| attr.left())
+        .unwrap();
+    let span_map = db.real_span_map(ast_id.file_id.file_id().unwrap());
+    let built = build_attr_arg(&attr, SpanMapRef::RealSpanMap(&span_map), loc.call_site).unwrap();
+
+    assert_eq!(built.delimiter.kind, DelimiterKind::Invisible);
+    assert_eq!(built.token_trees.len(), 5);
+    assert_eq!(built, **attr_args);
+}
+
+#[test]
+fn macro_call_crates() {
+    let db = TestDB::with_files(
+        r#"
+//- proc_macros: mirror
+macro_rules! local { () => {} }
+local!();
+proc_macros::mirror! {}
+"#,
+    );
+    let krate = db.crate_graph().iter().next().unwrap();
+    let calls = resolve_macro_calls_in(&db);
+
+    assert_eq!(db.macro_call_crates(calls[0].1), (krate, krate));
+    let (invoking, defining) = db.macro_call_crates(calls[1].1);
+    assert_eq!(invoking, krate);
+    assert_ne!(defining, krate);
+    let defining_name = db.crate_graph()[defining].display_name.clone().unwrap();
+    assert_eq!(defining_name.crate_name().as_smol_str(), "proc_macros");
+}
+
+#[test]
+fn macro_def_file() {
+    let db = TestDB::with_files(
+        r#"
+//- proc_macros: mirror
+//- minicore: concat
+//- /main.rs crate:main deps:macros
+mod m;
+crate::local!();
+concat!();
+proc_macros::mirror! {}
+macros::unloaded! {}
+//- /m.rs
+#[macro_export]
+macro_rules! local { () => {} }
+//- /macros.rs crate:macros
+#![crate_type="proc-macro"]
+pub struct TokenStream;
+#[proc_macro]
+pub fn unloaded(args: TokenStream) -> TokenStream {
+    args
+}
+"#,
+    );
+    let def_files = resolve_macro_calls_in(&db)
+        .into_iter()
+        .map(|(_, call)| db.macro_def_file(call).map(|it| db.file_text(it.original_file(&db))))
+        .collect::<Vec<_>>();
+    assert!(def_files[0].as_ref().unwrap().contains("macro_rules! local"));
+    assert!(def_files[1].as_ref().unwrap().contains("macro_rules! concat"));
+    assert!(def_files[2].as_ref().unwrap().contains("pub fn mirror"));
+    assert_eq!(def_files[3], None);
+}
+
+#[test]
+fn clear_proc_macro_cache_recomputes_proc_macro_expansions() {
+    let mut db = TestDB::with_files(
+        r#"
+//- proc_macros: mirror
+macro_rules! local { () => {} }
+local!();
+proc_macros::mirror! {}
+"#,
+    );
+    let calls = resolve_macro_calls_in(&db);
+    let macro_files = calls.iter().map(|&(_, id)| MacroFileId { macro_call_id: id });
+    let expand_all = |db: &TestDB| {
+        for macro_file in macro_files.clone() {
+            db.parse_macro_expansion(macro_file);
+        }
+    };
+    expand_all(&db);
+
+    clear_proc_macro_cache(&mut db);
+    let executed = db.log_executed(|| expand_all(&db));
+    assert_eq!(executed.len(), 1, "{executed:?}");
+    assert!(executed[0].contains("expand_proc_macro"), "{executed:?}");
+}
+
+#[test]
+fn dedup_proc_macro_expansions() {
+    let fixture = (0..200).fold(String::from("//- proc_macros: mirror\n"), |mut acc, i| {
+        acc += &format!("proc_macros::mirror! {{ {i} ; [{i}] fn f{i}() {{}} }}\n");
+        acc
+    });
+    let expand_all = |db: &TestDB| {
+        resolve_macro_calls_in(db)
+            .into_iter()
+            .map(|(_, call)| db.expand_proc_macro(call).value)
+            .collect::<Vec<_>>()
+    };
+    let plain_expansions = expand_all(&TestDB::with_files(&fixture));
+
+    let mut db = TestDB::with_files(&fixture);
+    let dedup = Arc::new(ProcMacroExpansionDedup::default());
+    db.set_proc_macro_expansion_dedup_with_durability(Some(dedup.clone()), Durability::HIGH);
+    let expansions = expand_all(&db);
+    assert_eq!(dedup.len(), expansions.len());
+    assert_eq!(expansions, plain_expansions);
+
+    clear_proc_macro_cache(&mut db);
+    assert!(dedup.is_empty());
+    let reexpansions = expand_all(&db);
+    assert_eq!(dedup.len(), expansions.len());
+    assert_eq!(reexpansions, plain_expansions);
+
+    // Changing the token limit recomputes the expansions, which are then shared with the
+    // previous ones.
+    let krate = db.crate_graph().iter().next().unwrap();
+    db.set_macro_token_limits_with_durability(
+        Arc::new(FxHashMap::from_iter([(krate, 1_000)])),
+        Durability::HIGH,
+    );
+    let limited_expansions = expand_all(&db);
+    assert_eq!(dedup.len(), expansions.len());
+    assert!(limited_expansions.iter().zip(&reexpansions).all(|(a, b)| Arc::ptr_eq(a, b)));
+}
+
+#[test]
+fn identity_attribute_macro_is_noop() {
+    let db = TestDB::with_files(
+        r#"
+//- proc_macros: identity
+#[proc_macros::identity]
+fn foo() {}
+"#,
+    );
+    let krate = db.crate_graph().iter().next().unwrap();
+    let def_map = db.crate_def_map(krate);
+    let (_, call) = def_map[DefMap::ROOT].scope.attr_macro_invocs().next().unwrap();
+    assert!(db.is_noop_expansion(call));
+}
+
+#[test]
+fn attr_macro_removed_item() {
+    let db = TestDB::with_files(
+        r#"
+//- proc_macros: input_replace
+#[proc_macros::input_replace()]
+fn removed() {}
+#[proc_macros::input_replace(fn replacement() {})]
+fn replaced() {}
+"#,
+    );
+    let krate = db.crate_graph().iter().next().unwrap();
+    let def_map = db.crate_def_map(krate);
+    let mut removed = def_map[DefMap::ROOT]
+        .scope
+        .attr_macro_invocs()
+        .map(|(ast_id, call)| {
+            (ast_id.to_node(&db).syntax().text_range().start(), db.attr_macro_removed_item(call))
+        })
+        .collect::<Vec<_>>();
+    removed.sort();
+    let removed = removed.into_iter().map(|(_, removed)| removed).collect::<Vec<_>>();
+    assert_eq!(removed, [true, false]);
+}
+
+#[test]
+fn proc_macro_registered_kind() {
+    let db = TestDB::with_files(
+        r#"
+//- proc_macros: derive_identity, mirror
+//- minicore: derive
+macro_rules! local { () => {} }
+#[derive(proc_macros::DeriveIdentity)]
+struct S;
+local!();
+proc_macros::mirror! {}
+"#,
+    );
+    let krate = db.crate_graph().iter().next().unwrap();
+    let def_map = db.crate_def_map(krate);
+    let (_, mut invocs) = def_map[DefMap::ROOT].scope.derive_macro_invocs().next().unwrap();
+    let (_, _, derive_calls) = invocs.next().unwrap();
+    let derive = db.lookup_intern_macro_call(derive_calls[0].unwrap()).def;
+    assert_eq!(db.proc_macro_registered_kind(derive), Some(ProcMacroKind::CustomDerive));
+
+    let kinds = resolve_macro_calls_in(&db)
+        .into_iter()
+        .map(|(_, call)| db.proc_macro_registered_kind(db.lookup_intern_macro_call(call).def))
+        .collect::<Vec<_>>();
+    assert_eq!(kinds, [None, Some(ProcMacroKind::FuncLike)]);
+}
+
+#[test]
+fn token_limit_exempt_macros() {
+    let extra_proc_macros =
+        ["huge", "huge_exempt"].map(|name| fn_like_proc_macro(name, HugeProcMacroExpander)).into();
+    let mut db = TestDB::with_files_extra_proc_macros(
+        r#"
+//- proc_macros: huge, huge_exempt
+fn f() {
+    proc_macros::huge!();
+    proc_macros::huge_exempt!();
+}
+"#,
+        extra_proc_macros,
+    );
+    let krate = db.crate_graph().iter().next().unwrap();
+    db.set_token_limit_exempt_macros_with_durability(
+        Arc::new(FxHashMap::from_iter([(krate, ["proc_macros::huge_exempt".to_owned()].into())])),
+        Durability::HIGH,
+    );
+
+    let calls = resolve_macro_calls_in(&db);
+    let huge = db.expand_proc_macro(calls[0].1);
+    let huge_exempt = db.expand_proc_macro(calls[1].1);
+    assert!(huge.value.token_trees.is_empty());
+    assert!(huge.err.unwrap().to_string().contains("exceeds token limit"));
+    assert_eq!(huge_exempt.value.token_trees.len(), HUGE_EXPANSION_LEN);
+    assert!(huge_exempt.err.is_none());
+}
+
+#[test]
+fn expand_macro_with_raised_limit() {
+    let extra_proc_macros = vec![fn_like_proc_macro("huge", HugeProcMacroExpander)];
+    let db = TestDB::with_files_extra_proc_macros(
+        r#"
+//- proc_macros: huge
+fn f() {
+    proc_macros::huge!();
+}
+"#,
+        extra_proc_macros,
+    );
+
+    let calls = resolve_macro_calls_in(&db);
+    let limited = db.expand_proc_macro(calls[0].1);
+    assert!(limited.err.unwrap().to_string().contains("exceeds token limit"));
+
+    let raised = expand_macro_with_limit(&db, calls[0].1, &Limit::new(HUGE_EXPANSION_LEN));
+    assert_eq!(raised.value.token_trees.len(), HUGE_EXPANSION_LEN);
+    assert!(raised.err.is_none());
+
+    let lowered = expand_macro_with_limit(&db, calls[0].1, &Limit::new(HUGE_EXPANSION_LEN - 1));
+    assert!(lowered.value.token_trees.is_empty());
+    assert!(lowered.err.unwrap().to_string().contains("exceeds token limit"));
+}
+
+#[test]
+fn macro_token_limit_per_crate() {
+    let extra_proc_macros = vec![fn_like_proc_macro("huge", HugeProcMacroExpander)];
+    let mut db = TestDB::with_files_extra_proc_macros(
+        r#"
+//- proc_macros: huge
+fn f() {
+    proc_macros::huge!();
+}
+"#,
+        extra_proc_macros,
+    );
+    let krate = db.crate_graph().iter().next().unwrap();
+    let calls = resolve_macro_calls_in(&db);
+
+    let limited = db.expand_proc_macro(calls[0].1);
+    let err = limited.err.unwrap().to_string();
+    assert!(err.contains("exceeds token limit of crate `test`"), "{err}");
+
+    db.set_macro_token_limits_with_durability(
+        Arc::new(FxHashMap::from_iter([(krate, HUGE_EXPANSION_LEN)])),
+        Durability::HIGH,
+    );
+    assert_eq!(db.macro_token_limit(krate), HUGE_EXPANSION_LEN);
+    let raised = db.expand_proc_macro(calls[0].1);
+    assert_eq!(raised.value.token_trees.len(), HUGE_EXPANSION_LEN);
+    assert!(raised.err.is_none());
+}
+
+#[test]
+fn token_limit_multipliers() {
+    let mut db = TestDB::with_files(
+        r#"
+//- proc_macros: mirror, shorten
+fn f() {
+    proc_macros::mirror! { 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15 16 17 18 19 20 }
+    proc_macros::shorten! { 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15 16 17 18 19 20 }
+}
+"#,
+    );
+    let krate = db.crate_graph().iter().next().unwrap();
+    db.set_macro_token_limits_with_durability(
+        Arc::new(FxHashMap::from_iter([(krate, 10)])),
+        Durability::HIGH,
+    );
+    db.set_token_limit_multipliers_with_durability(
+        Arc::new(FxHashMap::from_iter([(krate, [("proc_macros::mirror".to_owned(), 4)].into())])),
+        Durability::HIGH,
+    );
+
+    let calls = resolve_macro_calls_in(&db);
+    let scaled = db.expand_proc_macro(calls[0].1);
+    assert_eq!(scaled.value.token_trees.len(), 20);
+    assert!(scaled.err.is_none());
+    let limited = db.expand_proc_macro(calls[1].1);
+    assert!(limited.value.token_trees.is_empty());
+    let err = limited.err.unwrap().to_string();
+    assert!(err.contains("produced 20 tokens, limit is 10"), "{err}");
+}
+
+#[test]
+fn macro_expand_token_count_exceeding_limit() {
+    let expansions = sync::Arc::new(AtomicUsize::new(0));
+    let expander =
+        CountingProcMacroExpander { inner: HugeProcMacroExpander, expansions: expansions.clone() };
+    let extra_proc_macros = vec![fn_like_proc_macro("huge", expander)];
+    let mut db = TestDB::with_files_extra_proc_macros(
+        r#"
+//- proc_macros: huge
+fn f() {
+    proc_macros::huge!();
+}
+"#,
+        extra_proc_macros,
+    );
+    let krate = db.crate_graph().iter().next().unwrap();
+    let calls = resolve_macro_calls_in(&db);
+
+    // The count is taken from the cached expansion, the proc-macro is not expanded again.
+    db.expand_proc_macro(calls[0].1);
+    let limited = db.macro_expand_token_count(calls[0].1);
+    assert_eq!(expansions.load(Ordering::Relaxed), 1);
+    assert_eq!(limited.value, HUGE_EXPANSION_LEN);
+    let err = limited.err.unwrap().to_string();
+    assert!(err.contains("exceeds token limit of crate `test`"), "{err}");
+
+    db.set_macro_token_limits_with_durability(
+        Arc::new(FxHashMap::from_iter([(krate, HUGE_EXPANSION_LEN)])),
+        Durability::HIGH,
+    );
+    let raised = db.macro_expand_token_count(calls[0].1);
+    assert_eq!(raised.value, HUGE_EXPANSION_LEN);
+    assert!(raised.err.is_none());
+}
+
+/// A function-like proc-macro named `name` for `TestDB::with_files_extra_proc_macros`, along with
+/// its declaration in the `proc_macros` crate.
+fn fn_like_proc_macro(
+    name: &str,
+    expander: impl ProcMacroExpander + 'static,
+) -> (String, ProcMacro) {
+    (
+        format!("#[proc_macro]\npub fn {name}(input: TokenStream) -> TokenStream {{ input }}\n"),
+        ProcMacro {
+            name: name.into(),
+            kind: ProcMacroKind::FuncLike,
+            expander: sync::Arc::new(expander),
+        },
+    )
+}
+
+/// Counts the expansions of the wrapped expander.
+#[derive(Debug)]
+struct CountingProcMacroExpander<E> {
+    inner: E,
+    expansions: sync::Arc<AtomicUsize>,
+}
+impl<E: ProcMacroExpander> ProcMacroExpander for CountingProcMacroExpander<E> {
+    fn expand(
+        &self,
+        subtree: &tt::Subtree,
+        attrs: Option<&tt::Subtree>,
+        env: &Env,
+        def_site: Span,
+        call_site: Span,
+        mixed_site: Span,
+    ) -> Result<tt::Subtree, ProcMacroExpansionError> {
+        self.expansions.fetch_add(1, Ordering::Relaxed);
+        self.inner.expand(subtree, attrs, env, def_site, call_site, mixed_site)
+    }
+}
+
+const HUGE_EXPANSION_LEN: usize = (1 << 20) + 1;
+
+/// Expands to one token more than the token limit allows.
+#[derive(Debug)]
+struct HugeProcMacroExpander;
+impl ProcMacroExpander for HugeProcMacroExpander {
+    fn expand(
+        &self,
+        _: &tt::Subtree,
+        _: Option<&tt::Subtree>,
+        _: &Env,
+        _: Span,
+        call_site: Span,
+        _: Span,
+    ) -> Result<tt::Subtree, ProcMacroExpansionError> {
+        let semi =
+            tt::Leaf::Punct(tt::Punct { char: ';', spacing: tt::Spacing::Alone, span: call_site });
+        Ok(tt::Subtree {
+            delimiter: tt::Delimiter::invisible_spanned(call_site),
+            token_trees: vec![semi.into(); HUGE_EXPANSION_LEN],
+        })
+    }
+}
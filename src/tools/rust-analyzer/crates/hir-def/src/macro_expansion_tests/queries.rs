@@ -0,0 +1,799 @@
+//! Tests for `ExpandDatabase` queries that report facts about macro calls and their expansions.
+
+use base_db::{salsa::Durability, SourceDatabase, SourceDatabaseExt, DEFAULT_PARSE_LRU_CAP};
+use hir_expand::{
+    db::{ExpandDatabase, ExpandDeclarativeQuery, ParseMacroExpansionQuery},
+    tt,
+    tt::DelimiterKind,
+    ExpandError, ExpandResult, FragmentSpec, MacroCallDiagnostic, MacroCallDiagnosticKind,
+    MacroCallKind, MacroDefKind, MacroFileId,
+};
+use rustc_hash::FxHashMap;
+use syntax::{ast, AstNode, SourceFile, TextRange, TextSize};
+use test_fixture::WithFixture;
+use test_utils::{bench, skip_slow_tests};
+use triomphe::Arc;
+
+use crate::{
+    db::DefDatabase,
+    macro_expansion_tests::{resolve_macro_calls, resolve_macro_calls_in},
+    nameres::DefMap,
+    test_db::TestDB,
+    AdtId, Lookup, ModuleDefId,
+};
+
+#[test]
+fn macro_call_delimiter() {
+    let (db, calls) = resolve_macro_calls(
+        r#"
+macro_rules! foo { () => {} }
+
+foo!{}
+foo!();
+foo![];
+"#,
+    );
+    let delimiters = calls.iter().map(|&(_, id)| db.macro_call_delimiter(id)).collect::<Vec<_>>();
+    assert_eq!(
+        delimiters,
+        [
+            Some(DelimiterKind::Brace),
+            Some(DelimiterKind::Parenthesis),
+            Some(DelimiterKind::Bracket)
+        ]
+    );
+}
+
+#[test]
+fn expansion_produces_items() {
+    let (db, calls) = resolve_macro_calls(
+        r#"
+macro_rules! foo { () => {} }
+
+foo!{}
+fn f() {
+    foo!();
+    let _ = foo!();
+}
+"#,
+    );
+    let produces_items =
+        calls.iter().map(|&(_, id)| db.expansion_produces_items(id)).collect::<Vec<_>>();
+    assert_eq!(produces_items, [true, true, false]);
+}
+
+#[test]
+fn is_noop_expansion() {
+    let (db, calls) = resolve_macro_calls(
+        r#"
+macro_rules! identity { ($($t:tt)*) => { $($t)* } }
+macro_rules! twice { ($($t:tt)*) => { $($t)* $($t)* } }
+
+identity!(fn f(a: u8) -> u8 { a + 1 });
+twice!(struct S;);
+"#,
+    );
+    let noop = calls.iter().map(|&(_, id)| db.is_noop_expansion(id)).collect::<Vec<_>>();
+    assert_eq!(noop, [true, false]);
+}
+
+#[test]
+fn macro_expansion_depth() {
+    let db = TestDB::with_files(
+        r#"
+macro_rules! inner { () => { struct S; } }
+macro_rules! outer { () => { inner!(); } }
+outer!();
+"#,
+    );
+    let krate = db.crate_graph().iter().next().unwrap();
+    let def_map = db.crate_def_map(krate);
+    let Some(ModuleDefId::AdtId(AdtId::StructId(s))) =
+        def_map[DefMap::ROOT].scope.declarations().next()
+    else {
+        panic!("expected the struct expanded from `inner!`");
+    };
+    let inner = s.lookup(&db).id.file_id().macro_file().unwrap().macro_call_id;
+    let outer = inner.lookup(&db).kind.file_id().macro_file().unwrap().macro_call_id;
+    assert_eq!(db.macro_expansion_depth(outer), 2);
+    assert_eq!(db.macro_expansion_depth(inner), 1);
+
+    let (db, calls) = resolve_macro_calls(
+        r#"
+macro_rules! inner { () => { struct S; } }
+macro_rules! outer { () => { inner!(); } }
+macro_rules! both { () => { inner!(); fn f() { outer!(); } } }
+both!();
+"#,
+    );
+    // The deepest nested call counts, even in a body name resolution does not collect.
+    assert_eq!(db.macro_expansion_depth(calls[0].1), 3);
+}
+
+#[test]
+fn synthesized_token_ranges() {
+    let db = TestDB::with_files(
+        r#"
+//- minicore: derive, clone
+#[derive(Clone)]
+struct Foo;
+"#,
+    );
+    let krate = db.crate_graph().iter().next().unwrap();
+    let def_map = db.crate_def_map(krate);
+    let (_, mut invocs) = def_map[DefMap::ROOT].scope.derive_macro_invocs().next().unwrap();
+    let (_, _, derive_calls) = invocs.next().unwrap();
+    let call = derive_calls[0].unwrap();
+
+    let expansion = db.parse_or_expand(MacroFileId { macro_call_id: call }.into()).to_string();
+    let synthesized = db.synthesized_token_ranges(call);
+    let is_synthesized = |text: &str, offset: usize| {
+        synthesized.iter().any(|&range| {
+            range.start() == TextSize::from(offset as u32) && &expansion[range] == text
+        })
+    };
+    assert!(is_synthesized("impl", expansion.find("impl").unwrap()));
+    assert!(is_synthesized("fn", expansion.find("fn").unwrap()));
+    // The name of the struct in `impl Clone for Foo` is copied from the input.
+    let name = expansion.find("Foo").unwrap();
+    assert!(synthesized.iter().all(|range| !range.contains(TextSize::from(name as u32))));
+}
+
+#[test]
+fn expansion_spans_cover() {
+    let (db, calls) = resolve_macro_calls(
+        r#"
+macro_rules! twice { ($e:expr) => { fn f() { $e; $e; } } }
+
+twice!(1 + 2);
+"#,
+    );
+    let (macro_call, call) = &calls[0];
+    let file_text = macro_call.syntax().ancestors().last().unwrap().to_string();
+    let selection = file_text.find("+ 2").unwrap();
+    let source_range = TextRange::at(TextSize::from(selection as u32), TextSize::from(3));
+
+    let expansion = db.parse_or_expand(MacroFileId { macro_call_id: *call }.into()).to_string();
+    let covered = db.expansion_spans_cover(*call, source_range);
+    let covered = covered.iter().map(|&range| &expansion[range]).collect::<Vec<_>>();
+    assert_eq!(covered, ["+", "2", "+", "2"]);
+}
+
+#[test]
+fn expansion_source_len() {
+    let (db, calls) = resolve_macro_calls(
+        r#"
+macro_rules! foo { () => { struct Foo { field: u32 } } }
+
+foo!();
+"#,
+    );
+    let call = calls[0].1;
+    let expansion = db.parse_or_expand(MacroFileId { macro_call_id: call }.into()).to_string();
+    assert_eq!(db.expansion_source_len(call), expansion.len());
+}
+
+#[test]
+fn expansion_for_display() {
+    let (db, calls) = resolve_macro_calls(
+        r#"
+macro_rules! items { () => { struct Foo { field: u32 } fn foo() { let x = 1; x; } } }
+macro_rules! broken { () => { fn } }
+
+items!();
+broken!();
+"#,
+    );
+    let formatted = db.expansion_for_display(calls[0].1);
+    assert!(formatted.err.is_none());
+    assert_eq!(formatted.value, "struct Foo {\n  field:u32\n}\nfn foo(){\n  let x = 1;\n  x;\n}");
+
+    let broken = db.expansion_for_display(calls[1].1);
+    assert!(broken.err.is_some());
+    assert_eq!(broken.value, "fn");
+}
+
+#[test]
+fn macro_call_kind() {
+    let (db, calls) = resolve_macro_calls(
+        r#"
+macro_rules! foo { () => {} }
+
+foo!();
+"#,
+    );
+    let call = calls[0].1;
+    let kind = db.macro_call_kind(call);
+    assert!(matches!(kind, MacroCallKind::FnLike { .. }));
+    assert_eq!(kind, call.lookup(&db).kind);
+}
+
+#[test]
+fn speculative_expand_derive() {
+    let db = TestDB::with_files(
+        r#"
+//- minicore: derive, clone
+#[derive(Clone)]
+struct Foo { a: u32 }
+"#,
+    );
+    let krate = db.crate_graph().iter().next().unwrap();
+    let def_map = db.crate_def_map(krate);
+    let (_, mut invocs) = def_map[DefMap::ROOT].scope.derive_macro_invocs().next().unwrap();
+    let (_, _, derive_calls) = invocs.next().unwrap();
+    let call = derive_calls[0].unwrap();
+
+    let file = SourceFile::parse("#[derive(Clone)]\nstruct Foo { a: u32, b: u64 }").tree();
+    let adt = file.syntax().descendants().find_map(ast::Adt::cast).unwrap();
+    let expansion = hir_expand::db::speculative_expand_derive(&db, call, &adt).unwrap();
+    let expansion = expansion.to_string();
+    assert!(expansion.contains("b:b.clone()"), "{expansion}");
+
+    let actual = db.parse_or_expand(MacroFileId { macro_call_id: call }.into()).to_string();
+    assert!(!actual.contains("b:b.clone()"), "{actual}");
+}
+
+#[test]
+fn expanded_fn_signatures() {
+    let (db, calls) = resolve_macro_calls(
+        r#"
+macro_rules! routes {
+    ($($name:ident),*) => { $(#[inline] pub fn $name(req: &str) -> Option<u32> { None })* };
+}
+macro_rules! expr { () => { 1 } }
+
+routes!(index, about);
+const _: u32 = expr!();
+"#,
+    );
+    let signatures = db.expanded_fn_signatures(calls[0].1);
+    let signatures = signatures
+        .iter()
+        .map(|(name, signature)| (name.display(&db).to_string(), signature.as_str()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        signatures,
+        [
+            ("index".to_owned(), "pub fn index(req: &str) -> Option<u32>"),
+            ("about".to_owned(), "pub fn about(req: &str) -> Option<u32>"),
+        ]
+    );
+    assert!(db.expanded_fn_signatures(calls[1].1).is_empty());
+}
+
+#[test]
+fn is_doctest_expansion() {
+    let mut db = TestDB::with_files(
+        r#"
+//- /lib.rs crate:lib
+macro_rules! item { () => { pub struct S; } }
+item!();
+//- /doctest.rs crate:doctest
+macro_rules! item { () => { struct S; } }
+item!();
+"#,
+    );
+    let crate_graph = db.crate_graph();
+    let calls = crate_graph
+        .iter()
+        .map(|krate| {
+            let def_map = db.crate_def_map(krate);
+            let Some(ModuleDefId::AdtId(AdtId::StructId(s))) =
+                def_map[DefMap::ROOT].scope.declarations().next()
+            else {
+                panic!("expected the struct expanded from `item!`");
+            };
+            let call = s.lookup(&db).id.file_id().macro_file().unwrap().macro_call_id;
+            let krate = &crate_graph[krate];
+            (krate.display_name.as_ref().unwrap().to_string(), krate.root_file_id, call)
+        })
+        .collect::<Vec<_>>();
+    let doctest_file = calls.iter().find(|(name, ..)| name == "doctest").unwrap().1;
+    db.set_doctest_files_with_durability(
+        Arc::new([doctest_file].into_iter().collect()),
+        Durability::HIGH,
+    );
+
+    for (name, _, call) in calls {
+        assert_eq!(db.is_doctest_expansion(call), name == "doctest", "{name}");
+    }
+}
+
+#[test]
+fn expand_and_collect_diagnostics() {
+    let (db, calls) = resolve_macro_calls(
+        r#"
+macro_rules! broken { () => { fn } }
+macro_rules! m { ($($tt:tt)*) => { $($tt)* } }
+m!(struct S;);
+broken!();
+m!(
+"#,
+    );
+    let diagnostics =
+        calls.iter().map(|&(_, call)| db.expand_and_collect_diagnostics(call)).collect::<Vec<_>>();
+    let file_text = db.file_text(diagnostics[1][0].range.file_id);
+    let text_at = |diagnostic: &MacroCallDiagnostic| &file_text[diagnostic.range.range];
+
+    assert!(diagnostics[0].is_empty(), "{:?}", diagnostics[0]);
+
+    assert!(!diagnostics[1].is_empty());
+    for diagnostic in &*diagnostics[1] {
+        assert_eq!(diagnostic.kind, MacroCallDiagnosticKind::Parse);
+        assert!(text_at(diagnostic).starts_with("broken!"), "{diagnostic:?}");
+    }
+
+    let [input_error] = &*diagnostics[2] else {
+        panic!("expected a single diagnostic: {:?}", diagnostics[2]);
+    };
+    assert_eq!(input_error.kind, MacroCallDiagnosticKind::Input);
+    assert_eq!(input_error.message, "unbalanced token tree");
+    assert_eq!(text_at(input_error), "(");
+}
+
+#[test]
+fn eager_macro_errors_at_their_location() {
+    let (mut db, calls) = resolve_macro_calls(
+        r#"
+#[rustc_builtin_macro]
+macro_rules! concat {}
+const S: &str = concat!("a", unresolved!(), "b");
+"#,
+    );
+    let render = |db: &TestDB| {
+        let diagnostics = db.expand_and_collect_diagnostics(calls[0].1);
+        let file_text = db.file_text(diagnostics[0].range.file_id);
+        diagnostics
+            .iter()
+            .map(|it| format!("{:?} {:?} at {}", it.kind, it.message, &file_text[it.range.range]))
+            .collect::<Vec<_>>()
+    };
+    // `concat!` fails on the unexpanded `unresolved!()`, which is not reported again.
+    assert_eq!(
+        render(&db),
+        [r#"Input "unresolved macro unresolved" at ("a", unresolved!(), "b")"#]
+    );
+
+    // Exceeding the token limit does not stem from the failed input.
+    let krate = db.crate_graph().iter().next().unwrap();
+    db.set_macro_token_limits_with_durability(
+        Arc::new(FxHashMap::from_iter([(krate, 0)])),
+        Durability::HIGH,
+    );
+    assert_eq!(
+        render(&db),
+        [
+            r#"Input "unresolved macro unresolved" at ("a", unresolved!(), "b")"#,
+            r#"Expansion "macro invocation exceeds token limit of crate `test`: produced 1 tokens, limit is 0" at concat!("a", unresolved!(), "b")"#,
+            r#"Parse "expected expression" at concat!("a", unresolved!(), "b")"#,
+        ]
+    );
+}
+
+#[test]
+fn eager_macro_input() {
+    let (db, calls) = resolve_macro_calls(
+        r#"
+#[rustc_builtin_macro]
+macro_rules! concat {}
+#[rustc_builtin_macro]
+macro_rules! stringify {}
+macro_rules! m { () => {} }
+const S: &str = concat!(stringify!(a), "b");
+m!();
+"#,
+    );
+    let input = db.eager_macro_input(calls[0].1).expect("concat! is eager");
+    assert_eq!(input.to_string(), r#""a" , "b""#);
+    assert_eq!(db.eager_macro_input(calls[1].1), None);
+}
+
+#[test]
+fn attr_macro_arg() {
+    let (db, fn_like) = resolve_macro_calls(
+        r#"
+//- proc_macros: identity
+macro_rules! m { () => {} }
+m!();
+#[proc_macros::identity(key = "value")]
+fn foo() {}
+#[proc_macros::identity]
+fn bar() {}
+"#,
+    );
+    let krate = db.crate_graph().iter().next().unwrap();
+    let def_map = db.crate_def_map(krate);
+    let calls: Vec<_> =
+        def_map[DefMap::ROOT].scope.attr_macro_invocs().map(|(_, call)| call).collect();
+    assert_eq!(calls.len(), 2);
+
+    let args: Vec<_> =
+        calls.iter().map(|&call| db.attr_macro_arg(call).map(|it| it.to_string())).collect();
+    assert!(args.contains(&Some(r#"key = "value""#.to_owned())), "{args:?}");
+    assert!(args.contains(&None), "{args:?}");
+
+    assert_eq!(db.attr_macro_arg(fn_like[0].1), None);
+}
+
+#[test]
+fn is_identity_attr_macro() {
+    let (mut db, _) = resolve_macro_calls(
+        r#"
+//- proc_macros: identity, input_replace
+#[proc_macros::identity]
+fn foo() {}
+#[proc_macros::input_replace(fn bar() {})]
+fn baz() {}
+"#,
+    );
+    let krate = db.crate_graph().iter().next().unwrap();
+    let def_map = db.crate_def_map(krate);
+    let defs: Vec<_> = def_map[DefMap::ROOT]
+        .scope
+        .attr_macro_invocs()
+        .map(|(_, call)| db.lookup_intern_macro_call(call).def)
+        .collect();
+    assert_eq!(defs.len(), 2);
+    assert!(defs.iter().all(|&def| !db.is_identity_attr_macro(def)));
+
+    db.set_identity_attr_macro_detection_with_durability(true, Durability::HIGH);
+    let identity: Vec<_> = defs.iter().filter(|&&def| db.is_identity_attr_macro(def)).collect();
+    assert_eq!(identity.len(), 1);
+
+    // Name resolution no longer expands the identity attribute, `foo` is collected as written.
+    let def_map = db.crate_def_map(krate);
+    let calls: Vec<_> = def_map[DefMap::ROOT].scope.attr_macro_invocs().collect();
+    assert_eq!(calls.len(), 1);
+    assert_ne!(db.lookup_intern_macro_call(calls[0].1).def, *identity[0]);
+    let names: Vec<_> = def_map[DefMap::ROOT]
+        .scope
+        .entries()
+        .map(|(name, _)| name.display(&db).to_string())
+        .collect();
+    assert_eq!(names, ["bar", "foo"]);
+
+    // The observation is made once per macro, editing the crate does not redo it.
+    let file_id = db.crate_graph()[krate].root_file_id;
+    let text = format!("{}\nfn qux() {{}}\n", db.file_text(file_id));
+    db.set_file_text(file_id, Arc::from(text.as_str()));
+    let events = db.log_executed(|| {
+        db.crate_def_map(krate);
+    });
+    assert!(events.iter().any(|it| it.contains("crate_def_map")), "{events:#?}");
+    assert!(!events.iter().any(|it| it.contains("is_identity_attr_macro")), "{events:#?}");
+}
+
+#[test]
+fn macro_def_body_tt() {
+    let (db, calls) = resolve_macro_calls(
+        r#"
+#[rustc_builtin_macro]
+macro_rules! stringify {}
+macro_rules! m { ($x:ident) => { fn $x() {} } }
+m!(f);
+stringify!(f);
+"#,
+    );
+    let bodies = calls
+        .iter()
+        .map(|&(_, call)| db.macro_def_body_tt(db.lookup_intern_macro_call(call).def))
+        .collect::<Vec<_>>();
+
+    let body = bodies[0].as_ref().expect("macro_rules! has a body");
+    assert_eq!(body.delimiter.kind, DelimiterKind::Brace);
+    assert_eq!(body.to_string(), "{($ x : ident) => {fn $ x () {}}}");
+    assert!(bodies[1].is_none());
+}
+
+#[test]
+fn macro_expand_to_depth() {
+    let (db, calls) = resolve_macro_calls(
+        r#"
+macro_rules! inner { () => { struct S; } }
+macro_rules! middle { () => { inner!{} fn middle() {} } }
+macro_rules! outer { () => { middle!{} fn outer() {} } }
+outer!{}
+"#,
+    );
+    let outer = calls.iter().map(|&(_, call)| call).last().unwrap();
+    let expand = |depth| {
+        let ExpandResult { value, err } = db.macro_expand_to_depth(outer, depth);
+        assert!(err.is_none(), "{err:?}");
+        value.to_string()
+    };
+
+    assert_eq!(expand(0), "outer ! {}");
+    assert_eq!(expand(1), "middle ! {} fn outer () {}");
+    assert_eq!(expand(2), "inner ! {} fn middle () {} fn outer () {}");
+    assert_eq!(expand(3), "struct S ; fn middle () {} fn outer () {}");
+    assert_eq!(expand(4), expand(3));
+
+    let (db, calls) = resolve_macro_calls(
+        r#"
+macro_rules! one { () => { 1 } }
+macro_rules! m { () => { fn f() -> u32 { one!() } fn g() { missing!() } } }
+m!{}
+"#,
+    );
+    let ExpandResult { value, err } = db.macro_expand_to_depth(calls.last().unwrap().1, 2);
+    assert_eq!(value.to_string(), "fn f () -> u32 {1} fn g () {missing ! ()}");
+    assert_eq!(err.unwrap().to_string(), "unresolved macro missing");
+}
+
+#[test]
+fn fn_like_macro_path() {
+    let (db, calls) = resolve_macro_calls(
+        r#"
+mod foo {
+    pub mod bar {
+        pub macro baz() {}
+    }
+    pub macro baz() {}
+}
+use foo::baz;
+
+foo::bar::baz!();
+crate::foo::baz!();
+baz!();
+"#,
+    );
+    let paths = calls
+        .iter()
+        .map(|&(_, call)| db.fn_like_macro_path(call).unwrap().display(&db).to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(paths, ["foo::bar::baz", "crate::foo::baz", "baz"]);
+}
+
+#[test]
+fn macro_expand_to_items_parsed() {
+    let (db, calls) = resolve_macro_calls(
+        r#"
+macro_rules! m { () => { struct S; impl S { fn f() {} } } }
+macro_rules! e { () => { () } }
+m!();
+const _: () = e!();
+"#,
+    );
+    let ExpandResult { value: items, err } = db.macro_expand_to_items_parsed(calls[0].1);
+    assert!(err.is_none(), "{err:?}");
+    let root = db.parse_or_expand(MacroFileId { macro_call_id: calls[0].1 }.into());
+    let items = items.iter().map(|ptr| ptr.to_node(&root)).collect::<Vec<_>>();
+    assert!(matches!(items[..], [ast::Item::Struct(_), ast::Item::Impl(_)]), "{items:?}");
+
+    let ExpandResult { value: items, err } = db.macro_expand_to_items_parsed(calls[1].1);
+    assert!(items.is_empty());
+    assert_eq!(err.unwrap().to_string(), "macro call expands to Expr, not to items");
+}
+
+#[test]
+fn macro_expansion_diagnostics() {
+    let (db, calls) = resolve_macro_calls(
+        r#"
+macro_rules! body { ($($t:tt)*) => { fn f() { $($t)* } } }
+macro_rules! broken { () => { struct } }
+macro_rules! empty { () => {} }
+body!(let x = ;);
+broken!();
+empty!(x);
+"#,
+    );
+    let file_text = db.file_text(db.crate_graph()[db.test_crate()].root_file_id);
+    let diagnostics = calls
+        .iter()
+        .map(|&(_, call)| {
+            db.macro_expansion_diagnostics(call)
+                .iter()
+                .map(|(err, range)| format!("{err} at {:?}", &file_text[*range]))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        diagnostics,
+        [
+            // The error after the input is at the `}` of the macro definition.
+            vec![r#"expected expression at ";""#, r#"expected SEMICOLON at "body""#],
+            vec![r#"expected a name at "broken""#, r#"expected `;`, `{`, or `(` at "broken""#],
+            vec![r#"leftover tokens at "empty""#],
+        ]
+    );
+}
+
+#[test]
+fn decl_macro_fragment_specs() {
+    let (db, calls) = resolve_macro_calls(
+        r#"
+macro_rules! m {
+    ($e:expr) => {};
+    ($($t:ty),* ; $f:expr) => {};
+}
+m!(1);
+"#,
+    );
+    let def = db.lookup_intern_macro_call(calls[0].1).def;
+    assert_eq!(db.decl_macro_fragment_specs(def), [FragmentSpec::Expr, FragmentSpec::Ty]);
+}
+
+#[test]
+fn nested_macro_calls() {
+    let (db, calls) = resolve_macro_calls(
+        r#"
+macro_rules! inner { ($i:ident) => { struct $i; } }
+macro_rules! one { () => { 1 } }
+macro_rules! outer { () => { inner!{A} fn f() -> u32 { one!() } inner!{B} } }
+outer!{}
+"#,
+    );
+    let outer = calls.last().unwrap().1;
+    // The call in the body of `f` is not collected by name resolution, so it is resolved on demand.
+    let nested = db
+        .nested_macro_calls(outer)
+        .iter()
+        .map(|&call| db.macro_expand_to_depth(call, 0).value.to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(nested, ["inner ! {A}", "one ! ()", "inner ! {B}"]);
+
+    let inner = db.nested_macro_calls(outer)[0];
+    assert!(db.nested_macro_calls(inner).is_empty());
+}
+
+#[test]
+fn macro_recursion_limit() {
+    let mut db = TestDB::with_files(
+        r#"
+macro_rules! r { ($($t:tt)*) => { r!($($t)* x); } }
+r!();
+"#,
+    );
+    let krate = db.crate_graph().iter().next().unwrap();
+    db.set_macro_recursion_limits_with_durability(
+        Arc::new(FxHashMap::from_iter([(krate, 3)])),
+        Durability::HIGH,
+    );
+    assert_eq!(db.macro_recursion_limit(krate), 3);
+
+    let mut call = resolve_macro_calls_in(&db)[0].1;
+    let mut depth = 0;
+    while let [nested] = *db.nested_macro_calls(call) {
+        assert!(db.parse_macro_expansion(call.as_macro_file()).err.is_none());
+        call = nested;
+        depth += 1;
+    }
+    assert_eq!(depth, 4);
+    let err = db.parse_macro_expansion(call.as_macro_file()).err.unwrap();
+    assert_eq!(err.to_string(), "macro recursion limit reached (3)");
+}
+
+#[test]
+fn macro_expansion_hash() {
+    let (db, calls) = resolve_macro_calls(
+        r#"
+macro_rules! m { ($e:expr) => { fn f() -> u32 { $e } } }
+macro_rules! n { () => { fn f() -> u32 { 1 } } }
+m!(1);
+m!(2);
+n!();
+  m! { 1 }
+"#,
+    );
+    let hashes = calls.iter().map(|&(_, call)| db.macro_expansion_hash(call)).collect::<Vec<_>>();
+    assert_ne!(hashes[0], hashes[1]);
+    assert_eq!(hashes[0], hashes[2]);
+    assert_eq!(hashes[0], hashes[3]);
+}
+
+#[test]
+fn expand_with_rule_index() {
+    let (db, calls) = resolve_macro_calls(
+        r#"
+macro_rules! m {
+    (a) => {};
+    (b $i:ident) => {};
+    ($e:expr) => {};
+}
+m!(b x);
+m!(1 + 1);
+m!(b 1);
+"#,
+    );
+    let expand = |call| {
+        let loc = db.lookup_intern_macro_call(call);
+        let MacroDefKind::Declarative(id) = loc.def.kind else { unreachable!() };
+        let (arg, _) = db.macro_arg(call).value.unwrap();
+        let ExpandResult { value: (_, rule), err } =
+            db.decl_macro_expander(loc.def.krate, id).expand_with_rule_index(&db, &arg, call);
+        (rule, err)
+    };
+
+    assert_eq!(expand(calls[0].1), (Some(1), None));
+    assert_eq!(expand(calls[1].1), (Some(2), None));
+
+    let (rule, err) = expand(calls[2].1);
+    assert_eq!(rule, Some(1));
+    let Some(ExpandError::Mbe(mbe::ExpandError::FailedRules { best, errors })) = err else {
+        panic!("{err:?}")
+    };
+    assert_eq!(best, 1);
+    let errors = errors.iter().map(ToString::to_string).collect::<Vec<_>>();
+    assert_eq!(errors, ["unexpected token in input", "expected ident", "leftover tokens"]);
+}
+
+#[test]
+fn macro_arg_as_expr() {
+    let (db, calls) = resolve_macro_calls(
+        r#"
+macro_rules! m { ($($tt:tt)*) => {} }
+m!(a + b);
+m!(a; b);
+m!();
+"#,
+    );
+    let expr = db.macro_arg_as_expr(calls[0].1).expect("`a + b` is an expression");
+    assert!(matches!(expr, ast::Expr::BinExpr(_)), "{expr:?}");
+    assert_eq!(expr.to_string(), "a+b");
+    assert_eq!(db.macro_arg_as_expr(calls[1].1), None);
+    assert_eq!(db.macro_arg_as_expr(calls[2].1), None);
+}
+
+#[test]
+fn macro_arg_with_fixups() {
+    let (db, calls) = resolve_macro_calls(
+        r#"
+macro_rules! m { ($e:expr) => { fn f() { $e; } } }
+
+m!(a.);
+m!(a.b);
+"#,
+    );
+    let expand = |call, (arg, _): (Arc<tt::Subtree>, _)| {
+        let loc = db.lookup_intern_macro_call(call);
+        let MacroDefKind::Declarative(id) = loc.def.kind else { unreachable!() };
+        db.decl_macro_expander(loc.def.krate, id).expand(&db, &arg, call)
+    };
+
+    let incomplete = calls[0].1;
+    let res = expand(incomplete, db.macro_arg(incomplete).value.unwrap());
+    assert!(res.err.is_some());
+    let arg = db.macro_arg_with_fixups(incomplete);
+    assert!(arg.err.is_none());
+    let res = expand(incomplete, arg.value.unwrap());
+    assert_eq!(res.err, None);
+    assert_eq!(res.value.to_string(), "fn f () {(a . __ra_fixup) ;}");
+
+    let complete = calls[1].1;
+    assert_eq!(db.macro_arg_with_fixups(complete), db.macro_arg(complete));
+}
+
+#[test]
+fn benchmark_expand_declarative() {
+    if skip_slow_tests() {
+        return;
+    }
+    let mut fixture = String::from(
+        "macro_rules! item { ($name:ident = $e:expr) => { const $name: u32 = $e; } }\n",
+    );
+    for i in 0..10_000 {
+        fixture.push_str(&format!("item!(C{i} = {i} + 1);\n"));
+    }
+    // Collects the crate's items with the LRU capacities the IDE uses by default. A capacity of
+    // one for `expand_declarative` keeps it from caching anything, like before the query existed.
+    let collect = |expand_declarative_lru| {
+        let mut db = TestDB::with_files(&fixture);
+        ParseMacroExpansionQuery.in_db_mut(&mut db).set_lru_capacity(4 * DEFAULT_PARSE_LRU_CAP);
+        ExpandDeclarativeQuery.in_db_mut(&mut db).set_lru_capacity(expand_declarative_lru);
+        let krate = db.crate_graph().iter().next().unwrap();
+        move || db.crate_def_map(krate)[DefMap::ROOT].scope.declarations().count()
+    };
+
+    let uncached = collect(1);
+    let uncached = {
+        let _b = bench("collect 10k declarative macro calls, expansions not cached");
+        uncached()
+    };
+    let cached = collect(4 * DEFAULT_PARSE_LRU_CAP);
+    let cached = {
+        let _b = bench("collect 10k declarative macro calls, expansions cached");
+        cached()
+    };
+    assert_eq!(uncached, 10_000);
+    assert_eq!(cached, 10_000);
+}
@@ -51,6 +51,7 @@
 pub mod proc_macro;
 pub mod diagnostics;
 mod collector;
+pub(crate) mod expansion_tree;
 mod mod_resolution;
 mod path_resolution;
 
@@ -1350,6 +1350,13 @@ fn resolve_macros(&mut self) -> ReachedFixedPoint {
                         }
                     }
 
+                    // Skip attribute proc-macros that were observed to expand an item to itself,
+                    // their expansion would only duplicate the item. This is opt-in, see
+                    // `is_identity_attr_macro`.
+                    if self.db.is_identity_attr_macro(def) {
+                        return recollect_without(self);
+                    }
+
                     self.def_map.modules[directive.module_id]
                         .scope
                         .add_attr_macro_invoc(ast_id, call_id);
@@ -1393,16 +1400,22 @@ fn collect_macro_expansion(
         let ExpandResult { value, err } = self.db.parse_macro_expansion_error(macro_call_id);
         if let Some(err) = err {
             let loc: MacroCallLoc = self.db.lookup_intern_macro_call(macro_call_id);
-            let diag = match err {
-                // why is this reported here?
-                hir_expand::ExpandError::UnresolvedProcMacro(krate) => {
-                    always!(krate == loc.def.krate);
-                    DefDiagnostic::unresolved_proc_macro(module_id, loc.kind.clone(), loc.def.krate)
-                }
-                _ => DefDiagnostic::macro_error(module_id, loc.kind, err.to_string()),
-            };
+            for err in err.errors() {
+                let diag = match err {
+                    // why is this reported here?
+                    hir_expand::ExpandError::UnresolvedProcMacro(krate) => {
+                        always!(*krate == loc.def.krate);
+                        DefDiagnostic::unresolved_proc_macro(
+                            module_id,
+                            loc.kind.clone(),
+                            loc.def.krate,
+                        )
+                    }
+                    _ => DefDiagnostic::macro_error(module_id, loc.kind.clone(), err.to_string()),
+                };
 
-            self.def_map.diagnostics.push(diag);
+                self.def_map.diagnostics.push(diag);
+            }
         }
         if let errors @ [_, ..] = &*value {
             let loc: MacroCallLoc = self.db.lookup_intern_macro_call(macro_call_id);
@@ -0,0 +1,224 @@
+//! Queries over the tree formed by macro calls and the macro calls in their expansions.
+//!
+//! The macro calls in an expansion only get a [`MacroCallId`] once their macro is resolved, which
+//! needs name resolution, so these queries live here rather than in `hir-expand`.
+
+use hir_expand::{
+    db::expand_macro_with_limit, tt, ExpandError, ExpandResult, HirFileId, InFile, MacroCallId,
+    MacroCallKind,
+};
+use limit::Limit;
+use rustc_hash::{FxHashMap, FxHashSet};
+use syntax::{ast, AstNode, SmolStr, TextRange};
+use triomphe::Arc;
+
+use crate::{
+    db::DefDatabase,
+    nameres::{DefMap, MacroSubNs, ModuleSource},
+    resolver::HasResolver,
+    AsMacroCall, LocalModuleId,
+};
+
+/// The macro calls in the expansion of `macro_call`, in source order.
+pub(crate) fn nested_macro_calls_query(
+    db: &dyn DefDatabase,
+    macro_call: MacroCallId,
+) -> Arc<[MacroCallId]> {
+    let krate = db.lookup_intern_macro_call(macro_call).krate;
+    let def_map = db.crate_def_map(krate);
+    let file_id = macro_call.as_file();
+
+    // The calls name resolution collected when it collected the expansion.
+    let mut fn_like = FxHashMap::default();
+    let mut attr = FxHashMap::default();
+    let mut derive: FxHashMap<_, Vec<_>> = FxHashMap::default();
+    for (_, module) in def_map.modules() {
+        let scope = &module.scope;
+        fn_like.extend(
+            scope
+                .macro_invocs()
+                .filter(|(it, _)| it.file_id == file_id)
+                .map(|(it, call)| (it.value, call)),
+        );
+        attr.extend(
+            scope
+                .attr_macro_invocs()
+                .filter(|(it, _)| it.file_id == file_id)
+                .map(|(it, call)| (it.value.erase(), call)),
+        );
+        for (ast_id, invocs) in scope.derive_macro_invocs().filter(|(it, _)| it.file_id == file_id)
+        {
+            let calls = derive.entry(ast_id.value.erase()).or_default();
+            calls.extend(invocs.flat_map(|(attr_id, _, calls)| {
+                calls.iter().flatten().map(move |&call| (attr_id.ast_index(), call))
+            }));
+        }
+    }
+
+    let root = db.parse_or_expand(file_id);
+    let ast_id_map = db.ast_id_map(file_id);
+    let outer_module = macro_call_module(db, &def_map, macro_call);
+    let mut calls = Vec::new();
+    for node in root.descendants() {
+        if let Some(item) = ast::Item::cast(node.clone()) {
+            let ast_id = ast_id_map.ast_id(&item).erase();
+            calls.extend(attr.get(&ast_id));
+            if let Some(derives) = derive.get_mut(&ast_id) {
+                derives.sort_by_key(|&(attr_index, _)| attr_index);
+                calls.extend(derives.iter().map(|&(_, call)| call));
+            }
+        }
+        let Some(call) = ast::MacroCall::cast(node) else { continue };
+        if let Some(&id) = fn_like.get(&ast_id_map.ast_id(&call)) {
+            calls.push(id);
+            continue;
+        }
+        // Not collected by name resolution, like the calls in function bodies. Resolve the call in
+        // the scope of its module.
+        let Some(module) =
+            module_at(db, &def_map, file_id, call.syntax().text_range()).or(outer_module)
+        else {
+            continue;
+        };
+        let resolver = def_map.module_id(module).resolver(db);
+        let id = InFile::new(file_id, &call).as_call_id(db.upcast(), krate, |path| {
+            resolver
+                .resolve_path_as_macro(db, &path, Some(MacroSubNs::Bang))
+                .map(|(it, _)| db.macro_def(it))
+        });
+        calls.extend(id);
+    }
+    calls.into()
+}
+
+pub(crate) fn macro_expansion_depth_query(db: &dyn DefDatabase, macro_call: MacroCallId) -> usize {
+    let nested = db.nested_macro_calls(macro_call);
+    1 + nested.iter().map(|&call| db.macro_expansion_depth(call)).max().unwrap_or(0)
+}
+
+/// Returns the module of `def_map` the macro call is written in, looking through the macro calls
+/// whose expansions contain it.
+fn macro_call_module(
+    db: &dyn DefDatabase,
+    def_map: &DefMap,
+    macro_call: MacroCallId,
+) -> Option<LocalModuleId> {
+    let node = db.lookup_intern_macro_call(macro_call).to_node(db.upcast());
+    module_at(db, def_map, node.file_id, node.value.text_range()).or_else(|| {
+        let parent = node.file_id.macro_file()?.macro_call_id;
+        macro_call_module(db, def_map, parent)
+    })
+}
+
+/// Returns the innermost module of `def_map` defined in `file_id` that contains `range`.
+fn module_at(
+    db: &dyn DefDatabase,
+    def_map: &DefMap,
+    file_id: HirFileId,
+    range: TextRange,
+) -> Option<LocalModuleId> {
+    def_map
+        .modules()
+        .filter(|(_, module)| module.definition_source_file_id() == file_id)
+        .filter_map(|(id, module)| {
+            let module_range = match module.definition_source(db).value {
+                ModuleSource::SourceFile(it) => it.syntax().text_range(),
+                ModuleSource::Module(it) => it.syntax().text_range(),
+                ModuleSource::BlockExpr(it) => it.syntax().text_range(),
+            };
+            module_range.contains_range(range).then_some((module_range.len(), id))
+        })
+        .min_by_key(|&(len, _)| len)
+        .map(|(_, id)| id)
+}
+
+pub(crate) fn macro_expand_to_depth_query(
+    db: &dyn DefDatabase,
+    macro_call: MacroCallId,
+    depth: u32,
+) -> ExpandResult<Arc<tt::Subtree>> {
+    let loc = db.lookup_intern_macro_call(macro_call);
+    if depth == 0 {
+        let node = loc.to_node(db.upcast());
+        let span_map = db.span_map(node.file_id);
+        let tt = mbe::syntax_node_to_token_tree(&node.value, span_map.as_ref(), loc.call_site);
+        return ExpandResult::ok(Arc::new(tt));
+    }
+    let call_site = loc.call_site;
+    let limit = Limit::new(db.macro_token_limit(loc.krate));
+    let expansion = expand_macro_with_limit(db.upcast(), macro_call, &limit);
+    if depth == 1 {
+        return expansion;
+    }
+
+    let file_id = macro_call.as_file();
+    let root = db.parse_or_expand(file_id);
+    let ast_id_map = db.ast_id_map(file_id);
+    let nested_calls = db
+        .nested_macro_calls(macro_call)
+        .iter()
+        .filter_map(|&nested_call| match db.macro_call_kind(nested_call) {
+            MacroCallKind::FnLike { ast_id, .. } => Some((nested_call, ast_id)),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    // A call whose macro does not resolve stays unexpanded, report it instead of returning a
+    // shallower expansion than asked for without notice.
+    let ExpandResult { value: expansion, err } = expansion;
+    let mut err = err.or_else(|| {
+        let unresolved = root.descendants().filter_map(ast::MacroCall::cast).find(|call| {
+            let ast_id = ast_id_map.ast_id(call);
+            !nested_calls.iter().any(|(_, it)| it.value == ast_id)
+        })?;
+        Some(ExpandError::other(format!("unresolved macro {}", unresolved.path()?)))
+    });
+    if nested_calls.is_empty() {
+        return ExpandResult { value: expansion, err };
+    }
+
+    // Convert the expansion back to a token tree with a placeholder in place of each nested call,
+    // then replace the placeholders by the nested expansions.
+    let span_map = db.span_map(file_id);
+    let mut append = FxHashMap::default();
+    let mut remove = FxHashSet::default();
+    let mut nested_expansions = FxHashMap::default();
+    for (i, (nested_call, ast_id)) in nested_calls.into_iter().enumerate() {
+        let node = ast_id.to_ptr(db.upcast()).to_node(&root).syntax().clone();
+        let placeholder = SmolStr::from(format!("__ra_nested_expansion{i}"));
+        let ExpandResult { value, err: nested_err } =
+            db.macro_expand_to_depth(nested_call, depth - 1);
+        err = err.or(nested_err);
+        let mut subtree = (*value).clone();
+        subtree.delimiter.kind = tt::DelimiterKind::Invisible;
+        nested_expansions.insert(placeholder.clone(), subtree);
+        append.insert(
+            node.clone().into(),
+            vec![tt::Leaf::Ident(tt::Ident { text: placeholder, span: call_site })],
+        );
+        remove.insert(node);
+    }
+    let mut tt = mbe::syntax_node_to_token_tree_modified(
+        &root,
+        span_map.as_ref(),
+        append,
+        remove,
+        call_site,
+    );
+    replace_placeholders(&mut tt, &mut nested_expansions);
+    ExpandResult { value: Arc::new(tt), err }
+}
+
+/// Replaces the identifiers of `tt` that are keys of `replacements` by the corresponding subtree.
+fn replace_placeholders(tt: &mut tt::Subtree, replacements: &mut FxHashMap<SmolStr, tt::Subtree>) {
+    for tree in &mut tt.token_trees {
+        match tree {
+            tt::TokenTree::Leaf(tt::Leaf::Ident(ident)) => {
+                if let Some(subtree) = replacements.remove(&ident.text) {
+                    *tree = tt::TokenTree::Subtree(subtree);
+                }
+            }
+            tt::TokenTree::Subtree(subtree) => replace_placeholders(subtree, replacements),
+            tt::TokenTree::Leaf(_) => (),
+        }
+    }
+}
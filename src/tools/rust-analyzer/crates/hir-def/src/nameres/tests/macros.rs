@@ -1110,6 +1110,33 @@ pub fn derive_macro_2(_item: TokenStream) -> TokenStream {
     }
 }
 
+#[test]
+fn all_macro_defs_in_crate() {
+    let db = TestDB::with_files(
+        r#"
+#![crate_type="proc-macro"]
+struct TokenStream;
+
+macro_rules! first { () => {} }
+mod m {
+    macro_rules! second { () => {} }
+}
+
+#[proc_macro]
+pub fn function_like_macro(args: TokenStream) -> TokenStream {
+    args
+}
+"#,
+    );
+    let krate = db.crate_graph().iter().next().unwrap();
+    let macros = db.all_macro_defs_in_crate(krate);
+    let macros = macros
+        .iter()
+        .map(|(name, def)| format!("{}: {}", name.display(&db), def.is_proc_macro()))
+        .collect::<Vec<_>>();
+    assert_eq!(macros, ["first: false", "function_like_macro: true", "second: false"]);
+}
+
 #[test]
 fn resolve_macro_def() {
     check(
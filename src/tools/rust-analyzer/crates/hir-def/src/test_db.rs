@@ -35,6 +35,14 @@ fn default() -> Self {
         let mut this = Self { storage: Default::default(), events: Default::default() };
         this.setup_syntax_context_root();
         this.set_expand_proc_attr_macros_with_durability(true, Durability::HIGH);
+        this.set_token_limit_exempt_macros_with_durability(Default::default(), Durability::HIGH);
+        this.set_token_limit_multipliers_with_durability(Default::default(), Durability::HIGH);
+        this.set_macro_token_limits_with_durability(Default::default(), Durability::HIGH);
+        this.set_macro_recursion_limits_with_durability(Default::default(), Durability::HIGH);
+        this.set_doctest_files_with_durability(Default::default(), Durability::HIGH);
+        this.set_proc_macro_generation_with_durability(0, Durability::HIGH);
+        this.set_proc_macro_expansion_dedup_with_durability(None, Durability::HIGH);
+        this.set_identity_attr_macro_detection_with_durability(false, Durability::HIGH);
         this
     }
 }
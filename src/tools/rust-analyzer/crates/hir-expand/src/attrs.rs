@@ -312,6 +312,28 @@ pub fn cfg(&self) -> Option<CfgExpr> {
             None
         }
     }
+
+    /// The argument an attribute macro invoked by this attribute receives, see [`build_attr_arg`].
+    pub fn attr_macro_arg(&self) -> Option<Subtree> {
+        self.token_tree_value().map(|tt| into_attr_arg(tt.clone(), self.span))
+    }
+}
+
+/// Builds the argument an attribute macro invoked by `attr` receives, that is the token tree
+/// input of the attribute with its delimiter replaced by an invisible one. Returns `None` if the
+/// attribute has no token tree input.
+pub fn build_attr_arg(
+    attr: &ast::Attr,
+    span_map: SpanMapRef<'_>,
+    call_site: Span,
+) -> Option<Subtree> {
+    let tree = syntax_node_to_token_tree(attr.token_tree()?.syntax(), span_map, call_site);
+    Some(into_attr_arg(tree, call_site))
+}
+
+fn into_attr_arg(mut tree: Subtree, call_site: Span) -> Subtree {
+    tree.delimiter = tt::Delimiter::invisible_spanned(call_site);
+    tree
 }
 
 pub fn collect_attrs(
@@ -6,7 +6,10 @@
 use itertools::Itertools;
 use mbe::{parse_exprs_with_sep, parse_to_token_tree};
 use span::{Span, SpanAnchor, SyntaxContextId, ROOT_ERASED_FILE_AST_ID};
-use syntax::ast::{self, AstToken};
+use syntax::{
+    ast::{self, AstToken},
+    TextRange,
+};
 
 use crate::{
     db::ExpandDatabase,
@@ -195,6 +198,15 @@ fn stringify_expand(
     ExpandResult::ok(expanded)
 }
 
+/// Computes the ranges of the identifiers in the string produced by `stringify!` for the given
+/// input, together with the spans of the tokens they were stringified from.
+///
+/// The ranges are relative to the unescaped value of the resulting string literal. Punctuation and
+/// literals are not mapped.
+pub fn stringify_ident_ranges(tt: &tt::Subtree) -> Vec<(TextRange, Span)> {
+    ::tt::pretty_with_ident_ranges(&tt.token_trees).1
+}
+
 fn assert_expand(
     db: &dyn ExpandDatabase,
     id: MacroCallId,
@@ -4,12 +4,25 @@
 use span::FileId;
 use triomphe::Arc;
 
-use crate::{db::ExpandDatabase, proc_macro::ProcMacros};
+use crate::{
+    db::{
+        DoctestFiles, ExpandDatabase, MacroRecursionLimits, MacroTokenLimits,
+        TokenLimitExemptMacros, TokenLimitMultipliers,
+    },
+    proc_macro::{ProcMacroExpansionDedup, ProcMacros},
+};
 
 #[derive(Debug, Default)]
 pub struct Change {
     pub source_change: FileChange,
     pub proc_macros: Option<ProcMacros>,
+    pub token_limit_exempt_macros: Option<TokenLimitExemptMacros>,
+    pub token_limit_multipliers: Option<TokenLimitMultipliers>,
+    pub macro_token_limits: Option<MacroTokenLimits>,
+    pub macro_recursion_limits: Option<MacroRecursionLimits>,
+    pub doctest_files: Option<DoctestFiles>,
+    pub dedup_proc_macro_expansions: Option<bool>,
+    pub detect_identity_attr_macros: Option<bool>,
 }
 
 impl Change {
@@ -22,6 +35,33 @@ pub fn apply(self, db: &mut (impl ExpandDatabase + SourceDatabaseExt)) {
         if let Some(proc_macros) = self.proc_macros {
             db.set_proc_macros_with_durability(Arc::new(proc_macros), Durability::HIGH);
         }
+        if let Some(exempt_macros) = self.token_limit_exempt_macros {
+            db.set_token_limit_exempt_macros_with_durability(
+                Arc::new(exempt_macros),
+                Durability::HIGH,
+            );
+        }
+        if let Some(multipliers) = self.token_limit_multipliers {
+            db.set_token_limit_multipliers_with_durability(Arc::new(multipliers), Durability::HIGH);
+        }
+        if let Some(limits) = self.macro_token_limits {
+            db.set_macro_token_limits_with_durability(Arc::new(limits), Durability::HIGH);
+        }
+        if let Some(limits) = self.macro_recursion_limits {
+            db.set_macro_recursion_limits_with_durability(Arc::new(limits), Durability::HIGH);
+        }
+        if let Some(doctest_files) = self.doctest_files {
+            db.set_doctest_files_with_durability(Arc::new(doctest_files), Durability::HIGH);
+        }
+        if let Some(dedup) = self.dedup_proc_macro_expansions {
+            db.set_proc_macro_expansion_dedup_with_durability(
+                dedup.then(|| Arc::new(ProcMacroExpansionDedup::default())),
+                Durability::HIGH,
+            );
+        }
+        if let Some(detect) = self.detect_identity_attr_macros {
+            db.set_identity_attr_macro_detection_with_durability(detect, Durability::HIGH);
+        }
     }
 
     pub fn change_file(&mut self, file_id: FileId, new_text: Option<Arc<str>>) {
@@ -36,6 +76,36 @@ pub fn set_proc_macros(&mut self, proc_macros: ProcMacros) {
         self.proc_macros = Some(proc_macros);
     }
 
+    pub fn set_token_limit_exempt_macros(&mut self, exempt_macros: TokenLimitExemptMacros) {
+        self.token_limit_exempt_macros = Some(exempt_macros);
+    }
+
+    pub fn set_token_limit_multipliers(&mut self, multipliers: TokenLimitMultipliers) {
+        self.token_limit_multipliers = Some(multipliers);
+    }
+
+    pub fn set_macro_token_limits(&mut self, limits: MacroTokenLimits) {
+        self.macro_token_limits = Some(limits);
+    }
+
+    pub fn set_macro_recursion_limits(&mut self, limits: MacroRecursionLimits) {
+        self.macro_recursion_limits = Some(limits);
+    }
+
+    pub fn set_doctest_files(&mut self, doctest_files: DoctestFiles) {
+        self.doctest_files = Some(doctest_files);
+    }
+
+    /// Sets whether identical proc-macro expansions share their allocation, see
+    /// [`ProcMacroExpansionDedup`].
+    pub fn set_dedup_proc_macro_expansions(&mut self, dedup: bool) {
+        self.dedup_proc_macro_expansions = Some(dedup);
+    }
+
+    pub fn set_detect_identity_attr_macros(&mut self, detect: bool) {
+        self.detect_identity_attr_macros = Some(detect);
+    }
+
     pub fn set_roots(&mut self, roots: Vec<SourceRoot>) {
         self.source_change.set_roots(roots)
     }
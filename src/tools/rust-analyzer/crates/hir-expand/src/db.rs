@@ -1,47 +1,82 @@
 //! Defines database & queries for macro expansion.
 
-use std::sync::OnceLock;
+use std::{
+    hash::{Hash, Hasher},
+    sync::OnceLock,
+};
 
 use base_db::{
-    salsa::{self, debug::DebugQueryTable},
+    salsa::{self, debug::DebugQueryTable, Durability},
     CrateId, Edition, FileId, SourceDatabase, VersionReq,
 };
 use either::Either;
 use limit::Limit;
-use mbe::{syntax_node_to_token_tree, ValueResult};
-use rustc_hash::FxHashSet;
-use span::{Span, SyntaxContextId};
+use mbe::ValueResult;
+use rustc_hash::{FxHashMap, FxHashSet, FxHasher};
+use span::{Span, SpanAnchor, SyntaxContextId};
 use syntax::{
-    ast::{self, HasAttrs},
-    AstNode, Parse, SyntaxError, SyntaxNode, SyntaxToken, T,
+    ast::{self, HasAttrs, HasName},
+    AstNode, AstPtr, Parse, SmolStr, SyntaxError, SyntaxKind, SyntaxNode, SyntaxToken, TextRange,
+    TextSize, T,
 };
 use triomphe::Arc;
 
 use crate::{
     ast_id_map::AstIdMap,
-    attrs::{collect_attrs, RawAttrs},
+    attrs::{build_attr_arg, collect_attrs, RawAttrs},
     builtin_attr_macro::pseudo_derive_attr_expansion,
-    builtin_fn_macro::EagerExpander,
+    builtin_fn_macro::{stringify_ident_ranges, EagerExpander},
     fixup::{self, reverse_fixups, SyntaxFixupUndoInfo},
     hygiene::{
         apply_mark, span_with_call_site_ctxt, span_with_def_site_ctxt, span_with_mixed_site_ctxt,
         SyntaxContextData, Transparency,
     },
-    proc_macro::ProcMacros,
+    insert_whitespace_into_node::insert_ws_into,
+    mod_path::ModPath,
+    name::{AsName, Name},
+    proc_macro::{ProcMacroExpansionDedup, ProcMacroKind, ProcMacros},
     span_map::{RealSpanMap, SpanMap, SpanMapRef},
     tt, AstId, BuiltinAttrExpander, BuiltinDeriveExpander, BuiltinFnLikeExpander,
     CustomProcMacroExpander, EagerCallInfo, ExpandError, ExpandResult, ExpandTo, ExpansionSpanMap,
-    HirFileId, HirFileIdRepr, MacroCallId, MacroCallKind, MacroCallLoc, MacroDefId, MacroDefKind,
-    MacroFileId,
+    FragmentSpec, HirFileId, HirFileIdExt, HirFileIdRepr, InFile, MacroCallDiagnostic,
+    MacroCallDiagnosticKind, MacroCallId, MacroCallKind, MacroCallLoc, MacroDefId, MacroDefKind,
+    MacroFileId, MacroFileIdExt, TokenLimitExceeded,
 };
 
-/// Total limit on the number of tokens produced by any macro invocation.
+/// Default limit on the number of tokens produced by any macro invocation, see
+/// [`ExpandDatabase::macro_token_limit`].
 ///
 /// If an invocation produces more tokens than this limit, it will not be stored in the database and
 /// an error will be emitted.
 ///
 /// Actual max for `analysis-stats .` at some point: 30672.
-static TOKEN_LIMIT: Limit = Limit::new(1_048_576);
+const TOKEN_LIMIT: usize = 1_048_576;
+
+/// The limits on the number of tokens produced by the macro invocations of a crate, per crate.
+/// Crates without an entry use [`TOKEN_LIMIT`].
+pub type MacroTokenLimits = FxHashMap<CrateId, usize>;
+
+/// Default limit on how deeply macro calls may be nested in the expansions of other macro calls,
+/// see [`ExpandDatabase::macro_recursion_limit`].
+///
+/// This stops infinitely recursive macros long before their expansions grow to the token limit.
+const RECURSION_LIMIT: usize = 128;
+
+/// The limits on the nesting depth of the macro invocations of a crate, per crate. Crates without
+/// an entry use [`RECURSION_LIMIT`].
+pub type MacroRecursionLimits = FxHashMap<CrateId, usize>;
+
+/// The macros exempt from [`TOKEN_LIMIT`] when invoked by a crate, per crate. Macros are identified
+/// by their path `crate_name::macro_name`, where `crate_name` is the name of the defining crate.
+pub type TokenLimitExemptMacros = FxHashMap<CrateId, Box<[String]>>;
+
+/// The factors the token limit is multiplied by for macros invoked by a crate, per crate. Macros
+/// are identified by their path like in [`TokenLimitExemptMacros`].
+pub type TokenLimitMultipliers = FxHashMap<CrateId, Box<[(String, usize)]>>;
+
+/// The files synthesized from the examples in documentation comments, as opposed to the files of
+/// the crates themselves.
+pub type DoctestFiles = FxHashSet<FileId>;
 
 #[derive(Debug, Clone, Eq, PartialEq)]
 /// Old-style `macro_rules` or the new macros 2.0
@@ -57,9 +92,22 @@ impl DeclarativeMacroExpander {
     pub fn expand(
         &self,
         db: &dyn ExpandDatabase,
-        tt: tt::Subtree,
+        tt: &tt::Subtree,
         call_id: MacroCallId,
     ) -> ExpandResult<tt::Subtree> {
+        self.expand_with_rule_index(db, tt, call_id).map(|(tt, _)| tt)
+    }
+
+    /// Like `expand`, but also returns the zero-based index of the rule of the macro the
+    /// expansion was transcribed from, `None` if the macro definition is invalid or has no rules.
+    /// If no rule matches, the error is an [`mbe::ExpandError::FailedRules`] holding the error of
+    /// each rule.
+    pub fn expand_with_rule_index(
+        &self,
+        db: &dyn ExpandDatabase,
+        tt: &tt::Subtree,
+        call_id: MacroCallId,
+    ) -> ExpandResult<(tt::Subtree, Option<usize>)> {
         let loc = db.lookup_intern_macro_call(call_id);
         let toolchain = &db.crate_graph()[loc.def.krate].toolchain;
         let new_meta_vars = toolchain.as_ref().map_or(false, |version| {
@@ -75,13 +123,16 @@ pub fn expand(
         });
         match self.mac.err() {
             Some(e) => ExpandResult::new(
-                tt::Subtree::empty(tt::DelimSpan { open: loc.call_site, close: loc.call_site }),
+                (
+                    tt::Subtree::empty(tt::DelimSpan { open: loc.call_site, close: loc.call_site }),
+                    None,
+                ),
                 ExpandError::other(format!("invalid macro definition: {e}")),
             ),
             None => self
                 .mac
-                .expand(
-                    &tt,
+                .expand_with_rule_index(
+                    tt,
                     |s| s.ctx = apply_mark(db, s.ctx, call_id, self.transparency),
                     new_meta_vars,
                     loc.call_site,
@@ -140,6 +191,32 @@ pub trait ExpandDatabase: SourceDatabase {
     /// The proc macros.
     #[salsa::input]
     fn proc_macros(&self) -> Arc<ProcMacros>;
+    /// The macros whose expansions are not subject to the token limit.
+    #[salsa::input]
+    fn token_limit_exempt_macros(&self) -> Arc<TokenLimitExemptMacros>;
+    /// The factors the token limit is multiplied by for some macros.
+    #[salsa::input]
+    fn token_limit_multipliers(&self) -> Arc<TokenLimitMultipliers>;
+    /// The token limits of the crates that do not use the default one.
+    #[salsa::input]
+    fn macro_token_limits(&self) -> Arc<MacroTokenLimits>;
+    /// The recursion limits of the crates that do not use the default one.
+    #[salsa::input]
+    fn macro_recursion_limits(&self) -> Arc<MacroRecursionLimits>;
+    /// The files synthesized from documentation examples.
+    #[salsa::input]
+    fn doctest_files(&self) -> Arc<DoctestFiles>;
+    /// A counter that is bumped whenever the proc-macro expansions have to be recomputed, see
+    /// [`clear_proc_macro_cache`].
+    #[salsa::input]
+    fn proc_macro_generation(&self) -> u32;
+    /// Where identical proc-macro expansions are shared, `None` if they are not, see
+    /// [`ProcMacroExpansionDedup`].
+    #[salsa::input]
+    fn proc_macro_expansion_dedup(&self) -> Option<Arc<ProcMacroExpansionDedup>>;
+    /// Whether [`ExpandDatabase::is_identity_attr_macro`] tries attribute proc-macros out.
+    #[salsa::input]
+    fn identity_attr_macro_detection(&self) -> bool;
 
     fn ast_id_map(&self, file_id: HirFileId) -> Arc<AstIdMap>;
 
@@ -182,6 +259,21 @@ fn macro_arg(
         &self,
         id: MacroCallId,
     ) -> ValueResult<Option<(Arc<tt::Subtree>, SyntaxFixupUndoInfo)>, Arc<Box<[SyntaxError]>>>;
+    /// Like `macro_arg`, but for function-like macro calls the argument is parsed as a list of
+    /// comma-separated expressions first, and syntax errors in them are fixed up by inserting
+    /// placeholder tokens. This makes calls with half-written arguments expand, at the cost of
+    /// parsing the argument again. The returned undo info reverses the fixups in the expansion.
+    ///
+    /// The arguments of attribute and derive macros are always fixed up, so this is the same as
+    /// `macro_arg` for them.
+    fn macro_arg_with_fixups(
+        &self,
+        id: MacroCallId,
+    ) -> ValueResult<Option<(Arc<tt::Subtree>, SyntaxFixupUndoInfo)>, Arc<Box<[SyntaxError]>>>;
+    /// Parses the argument of a function-like macro call as a single expression. Returns `None`
+    /// for other macro calls and for arguments that aren't exactly one well-formed expression.
+    #[salsa::transparent]
+    fn macro_arg_as_expr(&self, id: MacroCallId) -> Option<ast::Expr>;
     /// Fetches the expander for this macro.
     #[salsa::transparent]
     fn macro_expander(&self, id: MacroDefId) -> TokenExpander;
@@ -201,6 +293,134 @@ fn parse_macro_expansion_error(
         &self,
         macro_call: MacroCallId,
     ) -> ExpandResult<Box<[SyntaxError]>>;
+    /// Returns the delimiter kind the argument of a fn-like macro call was written with, `None` for
+    /// attribute and derive macro calls.
+    fn macro_call_delimiter(&self, macro_call: MacroCallId) -> Option<tt::DelimiterKind>;
+    /// Maps the identifiers within the string literal produced by a `stringify!` call back to the
+    /// spans of the input tokens they originate from. Returns `None` if the call is not a
+    /// `stringify!` call.
+    fn stringify_ident_map(&self, macro_call: MacroCallId) -> Option<Arc<[(TextRange, Span)]>>;
+    /// Returns whether the expansion of the macro call may contribute items, that is whether it
+    /// expands to items or to statements, which can contain items.
+    #[salsa::transparent]
+    fn expansion_produces_items(&self, macro_call: MacroCallId) -> bool;
+    /// Returns the crate invoking the macro and the crate defining it, in that order.
+    #[salsa::transparent]
+    fn macro_call_crates(&self, macro_call: MacroCallId) -> (CrateId, CrateId);
+    /// Returns whether the macro call expands to exactly its input tokens, ignoring spans. For
+    /// attribute macros the input is the item without the attribute.
+    fn is_noop_expansion(&self, macro_call: MacroCallId) -> bool;
+    /// Returns whether the attribute proc-macro `def` expanded a sample item to exactly that item,
+    /// ignoring spans, so that its applications may be assumed to expand to their input. Name
+    /// resolution collects the items of such applications as written instead of expanding them.
+    /// This is only an observation, a proc-macro can behave differently on other input or between
+    /// runs. Always `false` without [`ExpandDatabase::identity_attr_macro_detection`].
+    fn is_identity_attr_macro(&self, def: MacroDefId) -> bool;
+    /// Returns the ranges of the tokens in the expansion of the macro call that do not originate
+    /// from its input, that is the tokens the macro created itself.
+    fn synthesized_token_ranges(&self, macro_call: MacroCallId) -> Arc<[TextRange]>;
+    /// Returns the length in bytes of the source text of the expansion of the macro call.
+    #[salsa::transparent]
+    fn expansion_source_len(&self, macro_call: MacroCallId) -> usize;
+    /// Returns the source text of the expansion of the macro call with whitespace and indentation
+    /// inserted, for showing it to the user. If the expansion has syntax errors it is returned
+    /// unformatted, together with the first error.
+    #[salsa::transparent]
+    fn expansion_for_display(&self, macro_call: MacroCallId) -> ExpandResult<String>;
+    /// Returns whether the macro call is a function-like, derive or attribute macro call, and
+    /// where it is.
+    #[salsa::transparent]
+    fn macro_call_kind(&self, macro_call: MacroCallId) -> MacroCallKind;
+    /// Returns the name and the rendered signature of each function defined at the top level of
+    /// the expansion of the macro call. Empty if the macro call does not expand to items.
+    fn expanded_fn_signatures(&self, macro_call: MacroCallId) -> Arc<[(Name, String)]>;
+    /// Returns whether the macro call originates from a file synthesized from a documentation
+    /// example, see [`ExpandDatabase::doctest_files`].
+    #[salsa::transparent]
+    fn is_doctest_expansion(&self, macro_call: MacroCallId) -> bool;
+    /// Returns the kind the proc-macro server registered the proc-macro as, which may differ
+    /// from how it is invoked. `None` if the macro is not a loaded proc-macro.
+    #[salsa::transparent]
+    fn proc_macro_registered_kind(&self, def: MacroDefId) -> Option<ProcMacroKind>;
+    /// Returns the diagnostics of all stages of expanding the macro call: the errors of its input,
+    /// the expansion error and the errors of parsing the expansion.
+    fn expand_and_collect_diagnostics(&self, macro_call: MacroCallId)
+        -> Arc<[MacroCallDiagnostic]>;
+    /// Returns the token tree of the body of a declarative macro definition, as used to build
+    /// its expander. `None` for builtin and procedural macros.
+    fn macro_def_body_tt(&self, def: MacroDefId) -> Option<Arc<tt::Subtree>>;
+    /// Returns the limit on the number of tokens a macro invocation in the crate may produce.
+    #[salsa::transparent]
+    fn macro_token_limit(&self, krate: CrateId) -> usize;
+    /// Returns how many macro expansions a macro invocation in the crate may be nested in. Deeper
+    /// invocations are not expanded and report an error instead.
+    #[salsa::transparent]
+    fn macro_recursion_limit(&self, krate: CrateId) -> usize;
+    /// Returns the number of tokens the expansion of the macro call produces. If that exceeds the
+    /// token limit of the calling crate, the count is still returned, together with the error.
+    fn macro_expand_token_count(&self, macro_call: MacroCallId) -> ExpandResult<usize>;
+    /// Expands the call of a declarative macro. The expansion is cached, unlike the expansions
+    /// of builtin macros, as matching the input against the rules of the macro is costly and
+    /// several queries need the expansion.
+    ///
+    /// The cache is per call rather than per argument. The argument's spans are anchored at the
+    /// call site and end up in the bindings of the matched rule, and the transcribed tokens carry
+    /// the hygiene marks of the call. So neither the match nor the expansion of an equal argument
+    /// at another call site could be reused.
+    // This query is LRU cached
+    fn expand_declarative(&self, macro_call: MacroCallId) -> ExpandResult<Arc<tt::Subtree>>;
+    /// Returns the ranges of the tokens in the expansion of the macro call that originate from
+    /// the tokens within `source_range` of the file containing the macro call.
+    fn expansion_spans_cover(
+        &self,
+        macro_call: MacroCallId,
+        source_range: TextRange,
+    ) -> Arc<[TextRange]>;
+    /// Returns the path of the macro as written at the call site, like `foo::bar!` for a call of
+    /// `foo::bar!()`. `None` for attribute and derive macro calls.
+    fn fn_like_macro_path(&self, macro_call: MacroCallId) -> Option<ModPath>;
+    /// Returns pointers to the top-level items of the expansion of the macro call, into the syntax
+    /// tree of the macro file. Errors if the macro call does not expand to items.
+    fn macro_expand_to_items_parsed(
+        &self,
+        macro_call: MacroCallId,
+    ) -> ExpandResult<Arc<[AstPtr<ast::Item>]>>;
+    /// Returns the expansion error and the errors of parsing the expansion of the macro call, with
+    /// their ranges in the original file of the macro call. Errors at tokens that do not originate
+    /// from the input of the macro call, like the tokens of the macro definition, and the
+    /// expansion error are given the range of the macro name.
+    fn macro_expansion_diagnostics(
+        &self,
+        macro_call: MacroCallId,
+    ) -> Arc<[(ExpandError, TextRange)]>;
+    /// Returns the distinct fragment specifiers, like `expr` in `$e:expr`, used in the matchers
+    /// of the rules of the declarative macro. Empty for other macros.
+    #[salsa::transparent]
+    fn decl_macro_fragment_specs(&self, def: MacroDefId) -> Vec<FragmentSpec>;
+    /// Returns whether the macro call is an attribute macro call whose expansion contains no
+    /// items, i.e. the attribute removed the item it is applied to. `false` if the expansion
+    /// failed.
+    #[salsa::transparent]
+    fn attr_macro_removed_item(&self, macro_call: MacroCallId) -> bool;
+    /// Returns a hash of the expansion of the macro call that ignores spans, so that equal
+    /// expansions at different call sites hash the same. The hash is deterministic, it doesn't
+    /// change between runs.
+    fn macro_expansion_hash(&self, macro_call: MacroCallId) -> u64;
+    /// Returns the file containing the definition of the macro invoked by the macro call. For
+    /// builtin macros this is the file of their `#[rustc_builtin_macro]` declaration, for
+    /// proc-macros the file declaring the proc-macro function. `None` for proc-macros that were
+    /// not loaded, their declaration is not known to be the source of the expansion.
+    #[salsa::transparent]
+    fn macro_def_file(&self, macro_call: MacroCallId) -> Option<HirFileId>;
+    /// Returns the argument of an eager macro call with the macro calls in it expanded, as the
+    /// eager macro is invoked with. `None` for other macro calls, including the calls eager macros
+    /// use internally for their unexpanded input.
+    #[salsa::transparent]
+    fn eager_macro_input(&self, macro_call: MacroCallId) -> Option<Arc<tt::Subtree>>;
+    /// Returns the token tree of the arguments of an attribute macro call, as passed to the
+    /// expander, or `None` for other macro calls and attributes without arguments.
+    #[salsa::transparent]
+    fn attr_macro_arg(&self, macro_call: MacroCallId) -> Option<Arc<tt::Subtree>>;
 }
 
 #[inline]
@@ -255,23 +475,7 @@ pub fn expand_speculative(
             SyntaxFixupUndoInfo::NONE,
         ),
         MacroCallKind::Derive { .. } | MacroCallKind::Attr { .. } => {
-            let censor = censor_for_macro_input(&loc, speculative_args);
-            let mut fixups = fixup::fixup_syntax(span_map, speculative_args, loc.call_site);
-            fixups.append.retain(|it, _| match it {
-                syntax::NodeOrToken::Node(it) => !censor.contains(it),
-                syntax::NodeOrToken::Token(_) => true,
-            });
-            fixups.remove.extend(censor);
-            (
-                mbe::syntax_node_to_token_tree_modified(
-                    speculative_args,
-                    span_map,
-                    fixups.append,
-                    fixups.remove,
-                    loc.call_site,
-                ),
-                fixups.undo_info,
-            )
+            speculative_item_tt(&loc, speculative_args, span_map)
         }
     };
 
@@ -288,16 +492,7 @@ pub fn expand_speculative(
                     .nth(invoc_attr_index.ast_index())
                     .and_then(|x| Either::left(x.1))
             }?;
-            match attr.token_tree() {
-                Some(token_tree) => {
-                    let mut tree =
-                        syntax_node_to_token_tree(token_tree.syntax(), span_map, loc.call_site);
-                    tree.delimiter = tt::Delimiter::invisible_spanned(loc.call_site);
-
-                    Some(tree)
-                }
-                _ => None,
-            }
+            build_attr_arg(&attr, span_map, loc.call_site)
         }
         _ => None,
     };
@@ -357,6 +552,75 @@ pub fn expand_speculative(
     Some((node.syntax_node(), token))
 }
 
+/// Expands the given derive macro call as if it were attached to `speculative_adt` instead of its
+/// actual item. This is used to preview what a derive would generate for a modified item, for
+/// example with a field added. Returns `None` if the call is not a derive.
+pub fn speculative_expand_derive(
+    db: &dyn ExpandDatabase,
+    actual_macro_call: MacroCallId,
+    speculative_adt: &ast::Adt,
+) -> Option<SyntaxNode> {
+    let loc = db.lookup_intern_macro_call(actual_macro_call);
+    if !matches!(loc.kind, MacroCallKind::Derive { .. }) {
+        return None;
+    }
+
+    let span_map = RealSpanMap::absolute(FileId::BOGUS);
+    let span_map = SpanMapRef::RealSpanMap(&span_map);
+
+    let expansion = match loc.def.kind {
+        MacroDefKind::ProcMacro(expander, ..) => {
+            let (mut tt, undo_info) = speculative_item_tt(&loc, speculative_adt.syntax(), span_map);
+            tt.delimiter = tt::Delimiter::invisible_spanned(loc.call_site);
+            let mut expansion = expander.expand(
+                db,
+                loc.def.krate,
+                loc.krate,
+                &tt,
+                None,
+                span_with_def_site_ctxt(db, loc.def.span, actual_macro_call),
+                span_with_call_site_ctxt(db, loc.def.span, actual_macro_call),
+                span_with_mixed_site_ctxt(db, loc.def.span, actual_macro_call),
+            );
+            fixup::reverse_fixups(&mut expansion.value, &undo_info);
+            expansion
+        }
+        MacroDefKind::BuiltInDerive(expander, ..) => {
+            expander.expand(db, actual_macro_call, speculative_adt, span_map)
+        }
+        _ => return None,
+    };
+    let (node, _) = token_tree_to_syntax_node(&expansion.value, loc.expand_to());
+    Some(node.syntax_node())
+}
+
+/// Converts the item of a speculative attribute or derive macro call to a token tree the same way
+/// `macro_arg` converts the actual item, that is with the attributes that must not be passed to
+/// the macro removed and with syntax errors fixed up.
+fn speculative_item_tt(
+    loc: &MacroCallLoc,
+    item: &SyntaxNode,
+    span_map: SpanMapRef<'_>,
+) -> (tt::Subtree, SyntaxFixupUndoInfo) {
+    let censor = censor_for_macro_input(loc, item);
+    let mut fixups = fixup::fixup_syntax(span_map, item, loc.call_site);
+    fixups.append.retain(|it, _| match it {
+        syntax::NodeOrToken::Node(it) => !censor.contains(it),
+        syntax::NodeOrToken::Token(_) => true,
+    });
+    fixups.remove.extend(censor);
+    (
+        mbe::syntax_node_to_token_tree_modified(
+            item,
+            span_map,
+            fixups.append,
+            fixups.remove,
+            loc.call_site,
+        ),
+        fixups.undo_info,
+    )
+}
+
 fn ast_id_map(db: &dyn ExpandDatabase, file_id: HirFileId) -> Arc<AstIdMap> {
     Arc::new(AstIdMap::from_source(&db.parse_or_expand(file_id)))
 }
@@ -391,15 +655,10 @@ fn parse_macro_expansion(
     let _p = profile::span("parse_macro_expansion");
     let loc = db.lookup_intern_macro_call(macro_file.macro_call_id);
     let expand_to = loc.expand_to();
-    let mbe::ValueResult { value: tt, err } = macro_expand(db, macro_file.macro_call_id, loc);
+    let limit = Limit::new(db.macro_token_limit(loc.krate));
+    let (tt, err) = macro_expand(db, macro_file.macro_call_id, loc, &limit).into_tuple();
 
-    let (parse, rev_token_map) = token_tree_to_syntax_node(
-        match &tt {
-            CowArc::Arc(it) => it,
-            CowArc::Owned(it) => it,
-        },
-        expand_to,
-    );
+    let (parse, rev_token_map) = token_tree_to_syntax_node(&tt, expand_to);
 
     ExpandResult { value: (parse, Arc::new(rev_token_map)), err }
 }
@@ -412,6 +671,448 @@ fn parse_macro_expansion_error(
         .map(|it| it.0.errors().to_vec().into_boxed_slice())
 }
 
+fn macro_call_delimiter(
+    db: &dyn ExpandDatabase,
+    macro_call_id: MacroCallId,
+) -> Option<tt::DelimiterKind> {
+    let MacroCallKind::FnLike { ast_id, .. } = db.macro_call_kind(macro_call_id) else {
+        return None;
+    };
+    let token_tree = ast_id.to_node(db).token_tree()?;
+    match token_tree.left_delimiter_token()?.kind() {
+        T!['('] => Some(tt::DelimiterKind::Parenthesis),
+        T!['{'] => Some(tt::DelimiterKind::Brace),
+        T!['['] => Some(tt::DelimiterKind::Bracket),
+        _ => None,
+    }
+}
+
+fn stringify_ident_map(
+    db: &dyn ExpandDatabase,
+    macro_call_id: MacroCallId,
+) -> Option<Arc<[(TextRange, Span)]>> {
+    let loc = db.lookup_intern_macro_call(macro_call_id);
+    if !matches!(loc.def.kind, MacroDefKind::BuiltIn(BuiltinFnLikeExpander::Stringify, _)) {
+        return None;
+    }
+    let (arg, _) = db.macro_arg(macro_call_id).value?;
+    Some(stringify_ident_ranges(&arg).into())
+}
+
+fn expansion_produces_items(db: &dyn ExpandDatabase, macro_call_id: MacroCallId) -> bool {
+    matches!(
+        db.lookup_intern_macro_call(macro_call_id).expand_to(),
+        ExpandTo::Items | ExpandTo::Statements
+    )
+}
+
+fn macro_call_kind(db: &dyn ExpandDatabase, macro_call_id: MacroCallId) -> MacroCallKind {
+    db.lookup_intern_macro_call(macro_call_id).kind
+}
+
+fn is_doctest_expansion(db: &dyn ExpandDatabase, macro_call_id: MacroCallId) -> bool {
+    let file_id = db.macro_call_kind(macro_call_id).file_id().original_file(db);
+    db.doctest_files().contains(&file_id)
+}
+
+fn proc_macro_registered_kind(db: &dyn ExpandDatabase, def: MacroDefId) -> Option<ProcMacroKind> {
+    match def.kind {
+        MacroDefKind::ProcMacro(expander, ..) => expander.kind(db, def.krate),
+        _ => None,
+    }
+}
+
+fn expand_and_collect_diagnostics(
+    db: &dyn ExpandDatabase,
+    macro_call_id: MacroCallId,
+) -> Arc<[MacroCallDiagnostic]> {
+    let loc = db.lookup_intern_macro_call(macro_call_id);
+    let mut diagnostics = Vec::new();
+
+    // The ranges of input errors are in the file containing the macro call.
+    let input_errors = db.macro_arg(macro_call_id).err;
+    if let Some(errors) = &input_errors {
+        diagnostics.extend(errors.iter().map(|error| MacroCallDiagnostic {
+            kind: MacroCallDiagnosticKind::Input,
+            message: error.to_string(),
+            range:
+                InFile::new(loc.kind.file_id(), error.range()).original_node_file_range_rooted(db),
+        }));
+    }
+
+    let ExpandResult { value: parse_errors, err } = db.parse_macro_expansion_error(macro_call_id);
+    // A malformed input fails the expansion with a less specific error, which is not repeated.
+    if let Some(err) = err.filter(|_| input_errors.is_none()) {
+        // The errors of expanding the input of an eager macro are reported at that input.
+        let eager_errors =
+            loc.eager.as_deref().and_then(|it| it.error.as_ref()).map_or(&[][..], |it| it.errors());
+        for err in err.errors() {
+            let (kind, range) = match &loc.kind {
+                MacroCallKind::FnLike { ast_id, .. } if eager_errors.contains(err) => {
+                    let node = ast_id.to_node(db);
+                    let range = node
+                        .token_tree()
+                        .map_or_else(|| node.syntax().text_range(), |it| it.syntax().text_range());
+                    (
+                        MacroCallDiagnosticKind::Input,
+                        InFile::new(ast_id.file_id, range).original_node_file_range_rooted(db),
+                    )
+                }
+                _ => (MacroCallDiagnosticKind::Expansion, loc.kind.clone().original_call_range(db)),
+            };
+            diagnostics.push(MacroCallDiagnostic { kind, message: err.to_string(), range });
+        }
+    }
+    let macro_file: HirFileId = MacroFileId { macro_call_id }.into();
+    diagnostics.extend(parse_errors.iter().map(|error| MacroCallDiagnostic {
+        kind: MacroCallDiagnosticKind::Parse,
+        message: error.to_string(),
+        range: InFile::new(macro_file, error.range()).original_node_file_range_rooted(db),
+    }));
+    diagnostics.into()
+}
+
+fn macro_expansion_diagnostics(
+    db: &dyn ExpandDatabase,
+    macro_call_id: MacroCallId,
+) -> Arc<[(ExpandError, TextRange)]> {
+    let loc = db.lookup_intern_macro_call(macro_call_id);
+    let call_range = loc.kind.clone().original_call_range(db);
+    let name_range = match loc.kind {
+        MacroCallKind::FnLike { ast_id, .. }
+            if ast_id.file_id.repr() == HirFileIdRepr::FileId(call_range.file_id) =>
+        {
+            ast_id.to_node(db).path().map_or(call_range.range, |path| path.syntax().text_range())
+        }
+        // FIXME: use the range of the path of derive and attribute macros
+        _ => call_range.range,
+    };
+
+    let ((parse, exp_map), err) =
+        db.parse_macro_expansion(MacroFileId { macro_call_id }).into_tuple();
+    let expansion_len = parse.syntax_node().text_range().end();
+    let map_range = |range: TextRange| {
+        if range.start() >= expansion_len {
+            return name_range;
+        }
+        let span = exp_map.span_at(range.start());
+        if span.anchor.file_id != call_range.file_id {
+            return name_range;
+        }
+        let anchor_offset = db
+            .ast_id_map(span.anchor.file_id.into())
+            .get_erased(span.anchor.ast_id)
+            .text_range()
+            .start();
+        let range = span.range + anchor_offset;
+        if call_range.range.contains_range(range) {
+            range
+        } else {
+            name_range
+        }
+    };
+
+    err.map(|err| (err, name_range))
+        .into_iter()
+        .chain(
+            parse
+                .errors()
+                .iter()
+                .map(|error| (ExpandError::other(error.to_string()), map_range(error.range()))),
+        )
+        .collect()
+}
+
+fn decl_macro_fragment_specs(db: &dyn ExpandDatabase, def: MacroDefId) -> Vec<FragmentSpec> {
+    match def.kind {
+        MacroDefKind::Declarative(id) => db
+            .decl_macro_expander(def.krate, id)
+            .mac
+            .fragment_specs()
+            .into_iter()
+            .map(FragmentSpec::from)
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn macro_call_crates(db: &dyn ExpandDatabase, macro_call_id: MacroCallId) -> (CrateId, CrateId) {
+    let loc = db.lookup_intern_macro_call(macro_call_id);
+    (loc.krate, loc.def.krate)
+}
+
+fn expansion_source_len(db: &dyn ExpandDatabase, macro_call_id: MacroCallId) -> usize {
+    let parse = db.parse_macro_expansion(MacroFileId { macro_call_id }).value.0;
+    parse.syntax_node().text_range().len().into()
+}
+
+fn expansion_for_display(
+    db: &dyn ExpandDatabase,
+    macro_call_id: MacroCallId,
+) -> ExpandResult<String> {
+    let ((parse, _), err) = db.parse_macro_expansion(MacroFileId { macro_call_id }).into_tuple();
+    match parse.errors().first() {
+        Some(error) => ExpandResult {
+            value: parse.syntax_node().to_string(),
+            err: err.or_else(|| Some(ExpandError::other(error.to_string()))),
+        },
+        None => ExpandResult { value: insert_ws_into(parse.syntax_node()).to_string(), err },
+    }
+}
+
+fn expanded_fn_signatures(
+    db: &dyn ExpandDatabase,
+    macro_call_id: MacroCallId,
+) -> Arc<[(Name, String)]> {
+    if db.lookup_intern_macro_call(macro_call_id).expand_to() != ExpandTo::Items {
+        return Arc::from_iter([]);
+    }
+    let parse = db.parse_macro_expansion(MacroFileId { macro_call_id }).value.0;
+    parse
+        .syntax_node()
+        .children()
+        .filter_map(ast::Fn::cast)
+        .filter_map(|func| {
+            let name = func.name()?.as_name();
+            // Render the function with whitespace first, as the tokens of the expansion are not
+            // separated by any.
+            let func = ast::Fn::cast(insert_ws_into(func.syntax().clone()))?;
+            let start = func
+                .syntax()
+                .children_with_tokens()
+                .find(|it| {
+                    !matches!(
+                        it.kind(),
+                        SyntaxKind::ATTR | SyntaxKind::COMMENT | SyntaxKind::WHITESPACE
+                    )
+                })?
+                .text_range()
+                .start();
+            let end = match func.body() {
+                Some(body) => body.syntax().text_range().start(),
+                None => func.syntax().text_range().end(),
+            };
+            let signature = func.syntax().text().slice(start..end).to_string();
+            Some((name, signature.trim_end().trim_end_matches(';').to_owned()))
+        })
+        .collect()
+}
+
+fn is_noop_expansion(db: &dyn ExpandDatabase, macro_call_id: MacroCallId) -> bool {
+    let Some((arg, _)) = db.macro_arg(macro_call_id).value else {
+        return false;
+    };
+    let loc = db.lookup_intern_macro_call(macro_call_id);
+    let limit = Limit::new(db.macro_token_limit(loc.krate));
+    let Some(expansion) = macro_expand(db, macro_call_id, loc, &limit).ok_value() else {
+        return false;
+    };
+    // The delimiters of the argument are those of the call, not part of the input.
+    tt_eq_ignoring_spans(&arg.token_trees, &expansion.token_trees)
+}
+
+/// The item attribute proc-macros are tried out on by `is_identity_attr_macro`.
+const IDENTITY_PROBE_ITEM: &str =
+    "#[doc = \"probe\"] pub(crate) fn __ra_probe<T: Clone>(x: &T) -> T { x.clone() }";
+
+fn is_identity_attr_macro(db: &dyn ExpandDatabase, def: MacroDefId) -> bool {
+    if !db.identity_attr_macro_detection() {
+        return false;
+    }
+    let MacroDefKind::ProcMacro(expander, ProcMacroKind::Attr, _) = def.kind else {
+        return false;
+    };
+    // Depend on the generation so that `clear_proc_macro_cache` invalidates the observation.
+    db.proc_macro_generation();
+    let item = ast::SourceFile::parse(IDENTITY_PROBE_ITEM).syntax_node();
+    let map = RealSpanMap::absolute(def.span.anchor.file_id);
+    let input = mbe::syntax_node_to_token_tree(&item, SpanMapRef::RealSpanMap(&map), def.span);
+    let ExpandResult { value, err } =
+        expander.expand(db, def.krate, def.krate, &input, None, def.span, def.span, def.span);
+    err.is_none() && tt_eq_ignoring_spans(&input.token_trees, &value.token_trees)
+}
+
+fn synthesized_token_ranges(
+    db: &dyn ExpandDatabase,
+    macro_call_id: MacroCallId,
+) -> Arc<[TextRange]> {
+    // Spans are compared without their syntax context, as the macro may have marked the input
+    // tokens it passes through with its own.
+    fn collect_input_spans(tt: &tt::Subtree, spans: &mut FxHashSet<(SpanAnchor, TextRange)>) {
+        if tt.delimiter.kind != tt::DelimiterKind::Invisible {
+            spans.insert((tt.delimiter.open.anchor, tt.delimiter.open.range));
+            spans.insert((tt.delimiter.close.anchor, tt.delimiter.close.range));
+        }
+        for tt in tt.token_trees.iter() {
+            match tt {
+                tt::TokenTree::Subtree(tt) => collect_input_spans(tt, spans),
+                tt::TokenTree::Leaf(leaf) => {
+                    let span = leaf.span();
+                    spans.insert((span.anchor, span.range));
+                }
+            }
+        }
+    }
+
+    let mut input_spans = FxHashSet::default();
+    if let Some((arg, _)) = db.macro_arg(macro_call_id).value {
+        collect_input_spans(&arg, &mut input_spans);
+    }
+    let span_map = db.parse_macro_expansion(MacroFileId { macro_call_id }).value.1;
+    let mut start = TextSize::new(0);
+    span_map
+        .iter()
+        .filter_map(|(end, span)| {
+            let range = TextRange::new(std::mem::replace(&mut start, end), end);
+            (!input_spans.contains(&(span.anchor, span.range))).then_some(range)
+        })
+        .collect()
+}
+
+fn expansion_spans_cover(
+    db: &dyn ExpandDatabase,
+    macro_call_id: MacroCallId,
+    source_range: TextRange,
+) -> Arc<[TextRange]> {
+    let loc = db.lookup_intern_macro_call(macro_call_id);
+    let (parse, span_map) = parse_with_map(db, loc.kind.file_id());
+    let root = parse.syntax_node();
+    let tokens = match root.covering_element(source_range) {
+        syntax::NodeOrToken::Node(node) => node
+            .descendants_with_tokens()
+            .filter_map(|it| it.into_token())
+            .filter(|token| {
+                token.text_range().intersect(source_range).map_or(false, |it| !it.is_empty())
+            })
+            .collect(),
+        syntax::NodeOrToken::Token(token) => vec![token],
+    };
+
+    let expansion_span_map = db.parse_macro_expansion(MacroFileId { macro_call_id }).value.1;
+    let mut ranges: Vec<_> = tokens
+        .iter()
+        .filter(|token| !token.kind().is_trivia())
+        .flat_map(|token| {
+            expansion_span_map.ranges_with_span(span_map.span_for_range(token.text_range()))
+        })
+        .collect();
+    ranges.sort_by_key(|range| range.start());
+    ranges.dedup();
+    ranges.into()
+}
+
+fn fn_like_macro_path(db: &dyn ExpandDatabase, macro_call_id: MacroCallId) -> Option<ModPath> {
+    let loc = db.lookup_intern_macro_call(macro_call_id);
+    let MacroCallKind::FnLike { ast_id, .. } = loc.kind else {
+        return None;
+    };
+    let (parse, span_map) = parse_with_map(db, ast_id.file_id);
+    let node = ast_id.to_ptr(db).to_node(&parse.syntax_node());
+    ModPath::from_src(db, node.path()?, span_map.as_ref())
+}
+
+fn macro_expand_to_items_parsed(
+    db: &dyn ExpandDatabase,
+    macro_call_id: MacroCallId,
+) -> ExpandResult<Arc<[AstPtr<ast::Item>]>> {
+    let expand_to = db.lookup_intern_macro_call(macro_call_id).expand_to();
+    if expand_to != ExpandTo::Items {
+        return ExpandResult::new(
+            Arc::from_iter([]),
+            ExpandError::other(format!("macro call expands to {expand_to:?}, not to items")),
+        );
+    }
+    // The expansion is parsed with `TopEntryPoint::MacroItems` for `ExpandTo::Items`.
+    db.parse_macro_expansion(MacroFileId { macro_call_id }).map(|(parse, _)| {
+        parse
+            .syntax_node()
+            .children()
+            .filter_map(ast::Item::cast)
+            .map(|it| AstPtr::new(&it))
+            .collect()
+    })
+}
+
+fn attr_macro_removed_item(db: &dyn ExpandDatabase, macro_call_id: MacroCallId) -> bool {
+    if !matches!(db.lookup_intern_macro_call(macro_call_id).kind, MacroCallKind::Attr { .. }) {
+        return false;
+    }
+    let ExpandResult { value: items, err } = db.macro_expand_to_items_parsed(macro_call_id);
+    err.is_none() && items.is_empty()
+}
+
+fn macro_expansion_hash(db: &dyn ExpandDatabase, macro_call_id: MacroCallId) -> u64 {
+    let loc = db.lookup_intern_macro_call(macro_call_id);
+    let limit = Limit::new(db.macro_token_limit(loc.krate));
+    let expansion = macro_expand(db, macro_call_id, loc, &limit).value;
+    let mut hasher = FxHasher::default();
+    hash_tt_ignoring_spans(&expansion.token_trees, &mut hasher);
+    hasher.finish()
+}
+
+/// Hashes the token trees consistently with [`tt_eq_ignoring_spans`].
+fn hash_tt_ignoring_spans(tts: &[tt::TokenTree], hasher: &mut FxHasher) {
+    tts.len().hash(hasher);
+    for tt in tts {
+        match tt {
+            tt::TokenTree::Subtree(it) => {
+                it.delimiter.kind.hash(hasher);
+                hash_tt_ignoring_spans(&it.token_trees, hasher);
+            }
+            tt::TokenTree::Leaf(tt::Leaf::Literal(it)) => ('l', &it.text).hash(hasher),
+            tt::TokenTree::Leaf(tt::Leaf::Punct(it)) => ('p', it.char, it.spacing).hash(hasher),
+            tt::TokenTree::Leaf(tt::Leaf::Ident(it)) => ('i', &it.text).hash(hasher),
+        }
+    }
+}
+
+fn macro_def_file(db: &dyn ExpandDatabase, macro_call_id: MacroCallId) -> Option<HirFileId> {
+    let def = db.lookup_intern_macro_call(macro_call_id).def;
+    match def.kind {
+        MacroDefKind::ProcMacro(expander, _, ast_id) => {
+            expander.name(db, def.krate).map(|_| ast_id.file_id)
+        }
+        _ => Some(def.ast_id().either(|it| it.file_id, |it| it.file_id)),
+    }
+}
+
+fn eager_macro_input(
+    db: &dyn ExpandDatabase,
+    macro_call_id: MacroCallId,
+) -> Option<Arc<tt::Subtree>> {
+    let loc = db.lookup_intern_macro_call(macro_call_id);
+    match (loc.def.kind, loc.eager.as_deref()) {
+        (MacroDefKind::BuiltInEager(..), Some(EagerCallInfo { arg, .. })) => Some(arg.clone()),
+        _ => None,
+    }
+}
+
+fn attr_macro_arg(db: &dyn ExpandDatabase, macro_call_id: MacroCallId) -> Option<Arc<tt::Subtree>> {
+    match db.lookup_intern_macro_call(macro_call_id).kind {
+        MacroCallKind::Attr { attr_args, .. } => attr_args,
+        _ => None,
+    }
+}
+
+fn tt_eq_ignoring_spans(a: &[tt::TokenTree], b: &[tt::TokenTree]) -> bool {
+    a.len() == b.len()
+        && a.iter().zip(b).all(|(a, b)| match (a, b) {
+            (tt::TokenTree::Subtree(a), tt::TokenTree::Subtree(b)) => {
+                a.delimiter.kind == b.delimiter.kind
+                    && tt_eq_ignoring_spans(&a.token_trees, &b.token_trees)
+            }
+            (tt::TokenTree::Leaf(a), tt::TokenTree::Leaf(b)) => match (a, b) {
+                (tt::Leaf::Literal(a), tt::Leaf::Literal(b)) => a.text == b.text,
+                (tt::Leaf::Punct(a), tt::Leaf::Punct(b)) => {
+                    a.char == b.char && a.spacing == b.spacing
+                }
+                (tt::Leaf::Ident(a), tt::Leaf::Ident(b)) => a.text == b.text,
+                _ => false,
+            },
+            _ => false,
+        })
+}
+
 fn parse_with_map(db: &dyn ExpandDatabase, file_id: HirFileId) -> (Parse<SyntaxNode>, SpanMap) {
     match file_id.repr() {
         HirFileIdRepr::FileId(file_id) => {
@@ -542,6 +1243,67 @@ fn macro_arg(
     }
 }
 
+fn macro_arg_with_fixups(
+    db: &dyn ExpandDatabase,
+    id: MacroCallId,
+) -> ValueResult<Option<(Arc<tt::Subtree>, SyntaxFixupUndoInfo)>, Arc<Box<[SyntaxError]>>> {
+    let loc = db.lookup_intern_macro_call(id);
+    let arg = db.macro_arg(id);
+    // The arguments of eager macros have been expanded already.
+    if !matches!(loc.kind, MacroCallKind::FnLike { .. })
+        || matches!(loc.def.kind, MacroDefKind::BuiltInEager(..))
+    {
+        return arg;
+    }
+    let ValueResult { value: Some((tt, _)), err } = &arg else {
+        return arg;
+    };
+
+    let mut delimited = tt::Subtree::clone(tt);
+    let invisible = delimited.delimiter.kind == tt::DelimiterKind::Invisible;
+    if invisible {
+        // The delimiters of proc macro inputs are stripped, but the parser expects them.
+        delimited.delimiter.kind = tt::DelimiterKind::Parenthesis;
+    }
+    let (parse, map) =
+        mbe::token_tree_to_syntax_node(&delimited, mbe::TopEntryPoint::MacroEagerInput);
+    let syntax = parse.syntax_node();
+    let map = SpanMapRef::ExpansionSpanMap(&map);
+    let fixups = fixup::fixup_syntax(map, &syntax, loc.call_site);
+    if fixups.append.is_empty() && fixups.remove.is_empty() {
+        return arg;
+    }
+
+    let mut tt = mbe::syntax_node_to_token_tree_modified(
+        &syntax,
+        map,
+        fixups.append,
+        fixups.remove,
+        loc.call_site,
+    );
+    if invisible {
+        tt.delimiter.kind = tt::DelimiterKind::Invisible;
+    }
+    ValueResult { value: Some((Arc::new(tt), fixups.undo_info)), err: err.clone() }
+}
+
+fn macro_arg_as_expr(db: &dyn ExpandDatabase, id: MacroCallId) -> Option<ast::Expr> {
+    let loc = db.lookup_intern_macro_call(id);
+    if !matches!(loc.kind, MacroCallKind::FnLike { .. }) {
+        return None;
+    }
+    let (tt, _) = db.macro_arg(id).value?;
+    let mut tt = tt::Subtree::clone(&tt);
+    // Parse the contents of the delimiters, not a parenthesized expression.
+    tt.delimiter.kind = tt::DelimiterKind::Invisible;
+    let (parse, _) = mbe::token_tree_to_syntax_node(&tt, mbe::TopEntryPoint::Expr);
+    if !parse.errors().is_empty() {
+        return None;
+    }
+    // Any input left after the expression is wrapped in an error node with the expression.
+    ast::Expr::cast(parse.syntax_node())
+}
+
 // FIXME: Censoring info should be calculated by the caller! Namely by name resolution
 /// Certain macro calls expect some nodes in the input to be preprocessed away, namely:
 /// - derives expect all `#[derive(..)]` invocations up to the currently invoked one to be stripped
@@ -624,18 +1386,12 @@ fn decl_macro_expander(
         )
     });
 
-    let (mac, transparency) = match id.to_ptr(db).to_node(&root) {
+    let node = id.to_ptr(db).to_node(&root);
+    let body = decl_macro_body(&node, map.as_ref());
+    let (mac, transparency) = match node {
         ast::Macro::MacroRules(macro_rules) => (
-            match macro_rules.token_tree() {
-                Some(arg) => {
-                    let tt = mbe::syntax_node_to_token_tree(
-                        arg.syntax(),
-                        map.as_ref(),
-                        map.span_for_range(macro_rules.macro_rules_token().unwrap().text_range()),
-                    );
-
-                    mbe::DeclarativeMacro::parse_macro_rules(&tt, is_2021, new_meta_vars)
-                }
+            match body {
+                Some(tt) => mbe::DeclarativeMacro::parse_macro_rules(&tt, is_2021, new_meta_vars),
                 None => mbe::DeclarativeMacro::from_err(
                     mbe::ParseError::Expected("expected a token tree".into()),
                     is_2021,
@@ -644,16 +1400,8 @@ fn decl_macro_expander(
             transparency(&macro_rules).unwrap_or(Transparency::SemiTransparent),
         ),
         ast::Macro::MacroDef(macro_def) => (
-            match macro_def.body() {
-                Some(arg) => {
-                    let tt = mbe::syntax_node_to_token_tree(
-                        arg.syntax(),
-                        map.as_ref(),
-                        map.span_for_range(macro_def.macro_token().unwrap().text_range()),
-                    );
-
-                    mbe::DeclarativeMacro::parse_macro2(&tt, is_2021, new_meta_vars)
-                }
+            match body {
+                Some(tt) => mbe::DeclarativeMacro::parse_macro2(&tt, is_2021, new_meta_vars),
                 None => mbe::DeclarativeMacro::from_err(
                     mbe::ParseError::Expected("expected a token tree".into()),
                     is_2021,
@@ -665,6 +1413,84 @@ fn decl_macro_expander(
     Arc::new(DeclarativeMacroExpander { mac, transparency })
 }
 
+/// Converts the body of a `macro_rules!` or `macro` definition to a token tree.
+fn decl_macro_body(mac: &ast::Macro, map: SpanMapRef<'_>) -> Option<tt::Subtree> {
+    let (body, keyword) = match mac {
+        ast::Macro::MacroRules(it) => (it.token_tree()?, it.macro_rules_token()?),
+        ast::Macro::MacroDef(it) => (it.body()?, it.macro_token()?),
+    };
+    Some(mbe::syntax_node_to_token_tree(
+        body.syntax(),
+        map,
+        map.span_for_range(keyword.text_range()),
+    ))
+}
+
+/// Expands a single call of a declarative macro without a database, for testing macros and for
+/// tools that only deal with one `macro_rules!` definition at a time.
+///
+/// `def` is parsed as Rust source and its first `macro_rules!` or `macro` definition is used,
+/// `invocation` is parsed the same way and its first macro call is expanded. The path of the call
+/// is not checked against the name of the macro. The expansion is unhygienic, and spans all point
+/// into a dummy file.
+pub fn expand_declarative_once(
+    def: &str,
+    invocation: &str,
+    edition: Edition,
+) -> Result<tt::Subtree, ExpandError> {
+    let map = RealSpanMap::absolute(FileId::BOGUS);
+    let map = SpanMapRef::RealSpanMap(&map);
+    let call_site = map.span_for_range(TextRange::empty(TextSize::new(0)));
+
+    let def = ast::SourceFile::parse(def).syntax_node();
+    let Some(node) = def.descendants().find_map(ast::Macro::cast) else {
+        return Err(ExpandError::other("expected a macro definition"));
+    };
+    let is_2021 = edition >= Edition::Edition2021;
+    let mac = match decl_macro_body(&node, map) {
+        Some(tt) => match node {
+            ast::Macro::MacroRules(_) => {
+                mbe::DeclarativeMacro::parse_macro_rules(&tt, is_2021, true)
+            }
+            ast::Macro::MacroDef(_) => mbe::DeclarativeMacro::parse_macro2(&tt, is_2021, true),
+        },
+        None => mbe::DeclarativeMacro::from_err(
+            mbe::ParseError::Expected("expected a token tree".into()),
+            is_2021,
+        ),
+    };
+    let transparency = match node {
+        ast::Macro::MacroRules(_) => Transparency::SemiTransparent,
+        ast::Macro::MacroDef(_) => Transparency::Opaque,
+    };
+    let expander = DeclarativeMacroExpander { mac, transparency };
+    if let Some(e) = expander.mac.err() {
+        return Err(ExpandError::other(format!("invalid macro definition: {e}")));
+    }
+
+    let invocation = ast::SourceFile::parse(invocation).syntax_node();
+    let Some(tt) =
+        invocation.descendants().find_map(ast::MacroCall::cast).and_then(|call| call.token_tree())
+    else {
+        return Err(ExpandError::MalformedInvocation);
+    };
+    let tt = mbe::syntax_node_to_token_tree(tt.syntax(), map, call_site);
+    let ValueResult { value, err } = expander.mac.expand(&tt, |_| (), true, call_site);
+    match err {
+        Some(err) => Err(err.into()),
+        None => Ok(value),
+    }
+}
+
+fn macro_def_body_tt(db: &dyn ExpandDatabase, id: MacroDefId) -> Option<Arc<tt::Subtree>> {
+    let MacroDefKind::Declarative(ast_id) = id.kind else {
+        return None;
+    };
+    let (root, map) = parse_with_map(db, ast_id.file_id);
+    let mac = ast_id.to_ptr(db).to_node(&root.syntax_node());
+    decl_macro_body(&mac, map.as_ref()).map(Arc::new)
+}
+
 fn macro_expander(db: &dyn ExpandDatabase, id: MacroDefId) -> TokenExpander {
     match id.kind {
         MacroDefKind::Declarative(ast_id) => {
@@ -683,15 +1509,67 @@ enum CowArc<T> {
     Owned(T),
 }
 
+impl<T> CowArc<T> {
+    fn into_arc(self) -> Arc<T> {
+        match self {
+            CowArc::Arc(it) => it,
+            CowArc::Owned(it) => Arc::new(it),
+        }
+    }
+}
+
+impl<T> std::ops::Deref for CowArc<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        match self {
+            CowArc::Arc(it) => it,
+            CowArc::Owned(it) => it,
+        }
+    }
+}
+
+/// Expands the macro call like the `parse_macro_expansion` query does, but with `limit` as the
+/// limit on the number of tokens the expansion may produce. As the limit may differ from call to
+/// call, the expansion is not cached.
+pub fn expand_macro_with_limit(
+    db: &dyn ExpandDatabase,
+    macro_call_id: MacroCallId,
+    limit: &Limit,
+) -> ExpandResult<Arc<tt::Subtree>> {
+    let loc = db.lookup_intern_macro_call(macro_call_id);
+    match loc.def.kind {
+        MacroDefKind::ProcMacro(..) => expand_proc_macro_with_limit(db, macro_call_id, limit),
+        _ => macro_expand(db, macro_call_id, loc, limit).map(CowArc::into_arc),
+    }
+}
+
 fn macro_expand(
     db: &dyn ExpandDatabase,
     macro_call_id: MacroCallId,
     loc: MacroCallLoc,
+    limit: &Limit,
 ) -> ExpandResult<CowArc<tt::Subtree>> {
     let _p = profile::span("macro_expand");
 
+    // Recursive macros are cut off here, instead of when their expansions exceed the token limit.
+    let recursion_limit = db.macro_recursion_limit(loc.krate);
+    let depth = loc.kind.file_id().macro_file().map_or(0, |file| file.expansion_level(db));
+    if Limit::new(recursion_limit).check(depth as usize).is_err() {
+        return ExpandResult {
+            value: CowArc::Owned(tt::Subtree {
+                delimiter: tt::Delimiter::invisible_spanned(loc.call_site),
+                token_trees: Vec::new(),
+            }),
+            err: Some(ExpandError::other(format!(
+                "macro recursion limit reached ({recursion_limit})"
+            ))),
+        };
+    }
+
     let ExpandResult { value: tt, mut err } = match loc.def.kind {
         MacroDefKind::ProcMacro(..) => return db.expand_proc_macro(macro_call_id).map(CowArc::Arc),
+        MacroDefKind::Declarative(_) => db.expand_declarative(macro_call_id).map(CowArc::Arc),
         MacroDefKind::BuiltInDerive(expander, ..) => {
             let (root, map) = parse_with_map(db, loc.kind.file_id());
             let root = root.syntax_node();
@@ -700,10 +1578,10 @@ fn macro_expand(
 
             // FIXME: Use censoring
             let _censor = censor_for_macro_input(&loc, node.syntax());
-            expander.expand(db, macro_call_id, &node, map.as_ref())
+            expander.expand(db, macro_call_id, &node, map.as_ref()).map(CowArc::Owned)
         }
         _ => {
-            let ValueResult { value, err } = db.macro_arg(macro_call_id);
+            let (value, err) = db.macro_arg(macro_call_id).into_tuple();
             let Some((macro_arg, undo_info)) = value else {
                 return ExpandResult {
                     value: CowArc::Owned(tt::Subtree {
@@ -712,17 +1590,14 @@ fn macro_expand(
                     }),
                     // FIXME: We should make sure to enforce an invariant that invalid macro
                     // calls do not reach this call path!
-                    err: Some(ExpandError::other("invalid token tree")),
+                    err: Some(ExpandError::InvalidTokenTree),
                 };
             };
 
             let arg = &*macro_arg;
             match loc.def.kind {
-                MacroDefKind::Declarative(id) => {
-                    db.decl_macro_expander(loc.def.krate, id).expand(db, arg.clone(), macro_call_id)
-                }
                 MacroDefKind::BuiltIn(it, _) => {
-                    it.expand(db, macro_call_id, arg).map_err(Into::into)
+                    it.expand(db, macro_call_id, arg).map_err(Into::into).map(CowArc::Owned)
                 }
                 // This might look a bit odd, but we do not expand the inputs to eager macros here.
                 // Eager macros inputs are expanded, well, eagerly when we collect the macro calls.
@@ -746,27 +1621,34 @@ fn macro_expand(
                     };
                 }
                 MacroDefKind::BuiltInEager(it, _) => {
-                    it.expand(db, macro_call_id, arg).map_err(Into::into)
+                    it.expand(db, macro_call_id, arg).map_err(Into::into).map(CowArc::Owned)
                 }
                 MacroDefKind::BuiltInAttr(it, _) => {
                     let mut res = it.expand(db, macro_call_id, arg);
                     fixup::reverse_fixups(&mut res.value, &undo_info);
-                    res
+                    res.map(CowArc::Owned)
                 }
                 _ => unreachable!(),
             }
         }
     };
 
-    if let Some(EagerCallInfo { error, .. }) = loc.eager.as_deref() {
-        // FIXME: We should report both errors!
-        err = error.clone().or(err);
+    let eager_error = loc.eager.as_deref().and_then(|it| it.error.clone());
+    if let Some(error) = &eager_error {
+        // The call was expanded with what could be recovered of its failed input, so the errors of
+        // expanding it stem from the input's errors and are not reported again.
+        err = Some(error.clone());
     }
 
     // Skip checking token tree limit for include! macro call
-    if !loc.def.is_include() {
+    if !loc.def.is_include() && !is_exempt_from_token_limit(db, &loc) {
         // Set a hard limit for the expanded tt
-        if let Err(value) = check_tt_count(&tt) {
+        if let Err(value) = check_tt_count(db, &tt, limit, &loc) {
+            // Exceeding the limit does not stem from a failed input, so both errors are reported.
+            let value = match eager_error {
+                Some(error) => value.map_err(|err| error.combine(err)),
+                None => value,
+            };
             return value.map(|()| {
                 CowArc::Owned(tt::Subtree {
                     delimiter: tt::Delimiter::invisible_spanned(loc.call_site),
@@ -776,10 +1658,51 @@ fn macro_expand(
         }
     }
 
-    ExpandResult { value: CowArc::Owned(tt), err }
+    ExpandResult { value: tt, err }
+}
+
+fn expand_declarative(
+    db: &dyn ExpandDatabase,
+    macro_call_id: MacroCallId,
+) -> ExpandResult<Arc<tt::Subtree>> {
+    let loc = db.lookup_intern_macro_call(macro_call_id);
+    let MacroDefKind::Declarative(id) = loc.def.kind else { unreachable!() };
+    let Some((macro_arg, _)) = db.macro_arg(macro_call_id).value else {
+        return ExpandResult {
+            value: Arc::new(tt::Subtree {
+                delimiter: tt::Delimiter::invisible_spanned(loc.call_site),
+                token_trees: Vec::new(),
+            }),
+            // FIXME: We should make sure to enforce an invariant that invalid macro
+            // calls do not reach this call path!
+            err: Some(ExpandError::InvalidTokenTree),
+        };
+    };
+    db.decl_macro_expander(loc.def.krate, id).expand(db, &macro_arg, macro_call_id).map(Arc::new)
 }
 
 fn expand_proc_macro(db: &dyn ExpandDatabase, id: MacroCallId) -> ExpandResult<Arc<tt::Subtree>> {
+    // Depend on the generation so that `clear_proc_macro_cache` invalidates all expansions.
+    db.proc_macro_generation();
+    let krate = db.lookup_intern_macro_call(id).krate;
+    expand_proc_macro_with_limit(db, id, &Limit::new(db.macro_token_limit(krate)))
+}
+
+/// Invalidates the expansions of all proc-macro calls, for example after the proc-macro server
+/// was restarted. Queries not depending on proc-macro expansions are unaffected.
+pub fn clear_proc_macro_cache(db: &mut dyn ExpandDatabase) {
+    if let Some(dedup) = db.proc_macro_expansion_dedup() {
+        dedup.clear();
+    }
+    let generation = db.proc_macro_generation();
+    db.set_proc_macro_generation_with_durability(generation.wrapping_add(1), Durability::HIGH);
+}
+
+fn expand_proc_macro_with_limit(
+    db: &dyn ExpandDatabase,
+    id: MacroCallId,
+    limit: &Limit,
+) -> ExpandResult<Arc<tt::Subtree>> {
     let loc = db.lookup_intern_macro_call(id);
     let Some((macro_arg, undo_info)) = db.macro_arg(id).value else {
         return ExpandResult {
@@ -789,7 +1712,7 @@ fn expand_proc_macro(db: &dyn ExpandDatabase, id: MacroCallId) -> ExpandResult<A
             }),
             // FIXME: We should make sure to enforce an invariant that invalid macro
             // calls do not reach this call path!
-            err: Some(ExpandError::other("invalid token tree")),
+            err: Some(ExpandError::InvalidTokenTree),
         };
     };
 
@@ -798,35 +1721,38 @@ fn expand_proc_macro(db: &dyn ExpandDatabase, id: MacroCallId) -> ExpandResult<A
         _ => unreachable!(),
     };
 
-    let attr_arg = match &loc.kind {
-        MacroCallKind::Attr { attr_args: Some(attr_args), .. } => Some(&**attr_args),
-        _ => None,
-    };
+    let attr_arg = db.attr_macro_arg(id);
 
     let ExpandResult { value: mut tt, err } = expander.expand(
         db,
         loc.def.krate,
         loc.krate,
         &macro_arg,
-        attr_arg,
+        attr_arg.as_deref(),
         span_with_def_site_ctxt(db, loc.def.span, id),
         span_with_call_site_ctxt(db, loc.def.span, id),
         span_with_mixed_site_ctxt(db, loc.def.span, id),
     );
 
     // Set a hard limit for the expanded tt
-    if let Err(value) = check_tt_count(&tt) {
-        return value.map(|()| {
-            Arc::new(tt::Subtree {
-                delimiter: tt::Delimiter::invisible_spanned(loc.call_site),
-                token_trees: vec![],
-            })
-        });
+    if !is_exempt_from_token_limit(db, &loc) {
+        if let Err(value) = check_tt_count(db, &tt, limit, &loc) {
+            return value.map(|()| {
+                Arc::new(tt::Subtree {
+                    delimiter: tt::Delimiter::invisible_spanned(loc.call_site),
+                    token_trees: vec![],
+                })
+            });
+        }
     }
 
     fixup::reverse_fixups(&mut tt, &undo_info);
 
-    ExpandResult { value: Arc::new(tt), err }
+    let value = match db.proc_macro_expansion_dedup() {
+        Some(dedup) => dedup.intern(expander, loc.def.krate, &macro_arg, attr_arg.as_deref(), tt),
+        None => Arc::new(tt),
+    };
+    ExpandResult { value, err }
 }
 
 fn token_tree_to_syntax_node(
@@ -843,16 +1769,95 @@ fn token_tree_to_syntax_node(
     mbe::token_tree_to_syntax_node(tt, entry_point)
 }
 
-fn check_tt_count(tt: &tt::Subtree) -> Result<(), ExpandResult<()>> {
+/// Returns whether the macro invoked by `loc` is exempt from the token limit in the calling crate.
+fn is_exempt_from_token_limit(db: &dyn ExpandDatabase, loc: &MacroCallLoc) -> bool {
+    let exempt_macros = db.token_limit_exempt_macros();
+    let Some(exempt_macros) = exempt_macros.get(&loc.krate) else {
+        return false;
+    };
+    macro_path_position(db, loc, exempt_macros.iter().map(|path| &**path)).is_some()
+}
+
+/// Returns the factor the token limit is multiplied by for the macro invoked by `loc` in the
+/// calling crate.
+fn token_limit_multiplier(db: &dyn ExpandDatabase, loc: &MacroCallLoc) -> usize {
+    let multipliers = db.token_limit_multipliers();
+    let Some(multipliers) = multipliers.get(&loc.krate) else {
+        return 1;
+    };
+    macro_path_position(db, loc, multipliers.iter().map(|(path, _)| &**path))
+        .map_or(1, |idx| multipliers[idx].1)
+}
+
+/// Returns the index of the first of `paths` of the form `crate_name::macro_name` that names the
+/// macro invoked by `loc`.
+fn macro_path_position<'a>(
+    db: &dyn ExpandDatabase,
+    loc: &MacroCallLoc,
+    mut paths: impl Iterator<Item = &'a str>,
+) -> Option<usize> {
+    let name = match loc.def.kind {
+        MacroDefKind::Declarative(ast_id) => {
+            ast_id.to_node(db).name().map(|it| SmolStr::from(it.text().as_str()))
+        }
+        MacroDefKind::ProcMacro(expander, ..) => expander.name(db, loc.def.krate),
+        _ => None,
+    }?;
+    let crate_name = db.crate_graph()[loc.def.krate].display_name.clone()?;
+    paths.position(|path| {
+        path.split_once("::").map_or(false, |(krate, macro_name)| {
+            krate == &**crate_name.crate_name() && name == macro_name
+        })
+    })
+}
+
+fn macro_expand_token_count(
+    db: &dyn ExpandDatabase,
+    macro_call_id: MacroCallId,
+) -> ExpandResult<usize> {
+    let loc = db.lookup_intern_macro_call(macro_call_id);
+    let limit = Limit::new(db.macro_token_limit(loc.krate));
+    // This goes through the cached expansions. An expansion exceeding the limit is replaced by an
+    // empty one, but the error still carries its count.
+    let ExpandResult { value: tt, err } = macro_expand(db, macro_call_id, loc, &limit);
+    let count = match &err {
+        Some(ExpandError::TokenLimitExceeded(it)) => it.count,
+        _ => tt.count(),
+    };
+    ExpandResult { value: count, err }
+}
+
+fn macro_token_limit(db: &dyn ExpandDatabase, krate: CrateId) -> usize {
+    db.macro_token_limits().get(&krate).copied().unwrap_or(TOKEN_LIMIT)
+}
+
+fn macro_recursion_limit(db: &dyn ExpandDatabase, krate: CrateId) -> usize {
+    db.macro_recursion_limits().get(&krate).copied().unwrap_or(RECURSION_LIMIT)
+}
+
+/// Checks the number of tokens of `tt` against `limit`, the token limit of `krate`, the crate
+/// invoking the macro.
+fn check_tt_count(
+    db: &dyn ExpandDatabase,
+    tt: &tt::Subtree,
+    limit: &Limit,
+    loc: &MacroCallLoc,
+) -> Result<(), ExpandResult<()>> {
+    let limit = Limit::new(limit.inner().saturating_mul(token_limit_multiplier(db, loc)));
     let count = tt.count();
-    if TOKEN_LIMIT.check(count).is_err() {
+    if limit.check(count).is_err() {
+        let krate = loc.krate;
+        let crate_name = match &db.crate_graph()[krate].display_name {
+            Some(name) => format!("`{name}`"),
+            None => format!("{krate:?}"),
+        };
         Err(ExpandResult {
             value: (),
-            err: Some(ExpandError::other(format!(
-                "macro invocation exceeds token limit: produced {} tokens, limit is {}",
+            err: Some(ExpandError::TokenLimitExceeded(Box::new(TokenLimitExceeded {
+                krate: crate_name.into_boxed_str(),
                 count,
-                TOKEN_LIMIT.inner(),
-            ))),
+                limit: limit.inner(),
+            }))),
         })
     } else {
         Ok(())
@@ -169,7 +169,7 @@ fn eager_macro_recur(
                 }
             },
             None => {
-                error = Some(ExpandError::other("malformed macro invocation"));
+                error = Some(ExpandError::MalformedInvocation);
                 offset += call.syntax().text_range().len();
                 continue;
             }
@@ -16,6 +16,7 @@
 pub mod files;
 pub mod change;
 pub mod hygiene;
+pub mod insert_whitespace_into_node;
 pub mod mod_path;
 pub mod name;
 pub mod proc_macro;
@@ -128,14 +129,88 @@ pub enum ExpandError {
     UnresolvedProcMacro(CrateId),
     Mbe(mbe::ExpandError),
     RecursionOverflowPoisoned,
+    /// The token tree of the macro call is missing or unbalanced.
+    InvalidTokenTree,
+    /// The macro call has no path.
+    MalformedInvocation,
+    /// The expansion produced more tokens than the token limit of the calling crate allows.
+    TokenLimitExceeded(Box<TokenLimitExceeded>),
     Other(Box<Box<str>>),
     ProcMacroPanic(Box<Box<str>>),
+    /// Several errors of the same expansion, like the error of expanding the input of an eager
+    /// macro and the expansion exceeding the token limit. Never empty and never nested.
+    Multi(Box<[ExpandError]>),
+}
+
+/// The details of an [`ExpandError::TokenLimitExceeded`].
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct TokenLimitExceeded {
+    /// The calling crate, as shown in the error message.
+    pub krate: Box<str>,
+    /// The number of tokens the expansion produced.
+    pub count: usize,
+    pub limit: usize,
 }
 
 impl ExpandError {
     pub fn other(msg: impl Into<Box<str>>) -> Self {
         ExpandError::Other(Box::new(msg.into()))
     }
+
+    /// Combines two errors of the same expansion into one keeping both, unless they are equal.
+    pub fn combine(self, other: ExpandError) -> Self {
+        let mut errors = self.into_errors();
+        for err in other.into_errors() {
+            if !errors.contains(&err) {
+                errors.push(err);
+            }
+        }
+        match <[_; 1]>::try_from(errors) {
+            Ok([err]) => err,
+            Err(errors) => ExpandError::Multi(errors.into_boxed_slice()),
+        }
+    }
+
+    /// Returns the errors this error consists of, see [`ExpandError::Multi`].
+    pub fn errors(&self) -> &[ExpandError] {
+        match self {
+            ExpandError::Multi(errors) => errors,
+            _ => std::slice::from_ref(self),
+        }
+    }
+
+    fn into_errors(self) -> Vec<ExpandError> {
+        match self {
+            ExpandError::Multi(errors) => errors.into_vec(),
+            _ => vec![self],
+        }
+    }
+
+    /// Returns whether the error is likely caused by an incomplete edit of the macro call, like an
+    /// unbalanced token tree or input not matching any rule yet, and likely goes away as the user
+    /// keeps typing. Errors of the macro definition or the expander are not recoverable.
+    pub fn is_recoverable(&self) -> bool {
+        match self {
+            ExpandError::InvalidTokenTree | ExpandError::MalformedInvocation => true,
+            ExpandError::Mbe(err) => match err.best_rule_error() {
+                mbe::ExpandError::NoMatchingRule
+                | mbe::ExpandError::UnexpectedToken
+                | mbe::ExpandError::LeftoverTokens => true,
+                mbe::ExpandError::BindingError(_)
+                | mbe::ExpandError::UnresolvedBinding(_)
+                | mbe::ExpandError::ConversionError
+                | mbe::ExpandError::LimitExceeded
+                | mbe::ExpandError::CountError(_)
+                | mbe::ExpandError::FailedRules { .. } => false,
+            },
+            ExpandError::UnresolvedProcMacro(_)
+            | ExpandError::RecursionOverflowPoisoned
+            | ExpandError::TokenLimitExceeded(_)
+            | ExpandError::Other(_)
+            | ExpandError::ProcMacroPanic(_) => false,
+            ExpandError::Multi(errors) => errors.iter().all(ExpandError::is_recoverable),
+        }
+    }
 }
 
 impl From<mbe::ExpandError> for ExpandError {
@@ -152,15 +227,51 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
             ExpandError::RecursionOverflowPoisoned => {
                 f.write_str("overflow expanding the original macro")
             }
+            ExpandError::InvalidTokenTree => f.write_str("invalid token tree"),
+            ExpandError::MalformedInvocation => f.write_str("malformed macro invocation"),
+            ExpandError::TokenLimitExceeded(it) => write!(
+                f,
+                "macro invocation exceeds token limit of crate {}: produced {} tokens, limit is {}",
+                it.krate, it.count, it.limit
+            ),
             ExpandError::ProcMacroPanic(it) => {
                 f.write_str("proc-macro panicked: ")?;
                 f.write_str(it)
             }
             ExpandError::Other(it) => f.write_str(it),
+            ExpandError::Multi(errors) => {
+                for (i, err) in errors.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str("; ")?;
+                    }
+                    err.fmt(f)?;
+                }
+                Ok(())
+            }
         }
     }
 }
 
+/// A diagnostic for a macro call, see [`ExpandDatabase::expand_and_collect_diagnostics`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MacroCallDiagnostic {
+    pub kind: MacroCallDiagnosticKind,
+    pub message: String,
+    /// The range the diagnostic applies to, mapped up to the original file. Falls back to the
+    /// range of the macro call if the exact range can't be mapped.
+    pub range: FileRange,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum MacroCallDiagnosticKind {
+    /// The input of the macro call is malformed, for example an unbalanced token tree.
+    Input,
+    /// Expanding the macro failed.
+    Expansion,
+    /// The expansion of the macro call failed to parse.
+    Parse,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct MacroCallLoc {
     pub def: MacroDefId,
@@ -387,8 +498,7 @@ fn is_eager(&self, db: &dyn ExpandDatabase) -> bool {
     }
 
     fn is_attr_macro(&self, db: &dyn ExpandDatabase) -> bool {
-        let loc: MacroCallLoc = db.lookup_intern_macro_call(self.macro_call_id);
-        matches!(loc.kind, MacroCallKind::Attr { .. })
+        matches!(db.macro_call_kind(self.macro_call_id), MacroCallKind::Attr { .. })
     }
 
     fn is_derive_attr_pseudo_expansion(&self, db: &dyn ExpandDatabase) -> bool {
@@ -556,7 +666,7 @@ pub fn original_call_range_with_body(self, db: &dyn ExpandDatabase) -> FileRange
         let file_id = loop {
             match kind.file_id().repr() {
                 HirFileIdRepr::MacroFile(file) => {
-                    kind = db.lookup_intern_macro_call(file.macro_call_id).kind;
+                    kind = db.macro_call_kind(file.macro_call_id);
                 }
                 HirFileIdRepr::FileId(file_id) => break file_id,
             }
@@ -581,7 +691,7 @@ pub fn original_call_range(self, db: &dyn ExpandDatabase) -> FileRange {
         let file_id = loop {
             match kind.file_id().repr() {
                 HirFileIdRepr::MacroFile(file) => {
-                    kind = db.lookup_intern_macro_call(file.macro_call_id).kind;
+                    kind = db.macro_call_kind(file.macro_call_id);
                 }
                 HirFileIdRepr::FileId(file_id) => break file_id,
             }
@@ -865,4 +975,133 @@ pub fn from_call_site(call: &ast::MacroCall) -> ExpandTo {
     }
 }
 
+/// A fragment specifier of a declarative macro, like `expr` in `$e:expr`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FragmentSpec {
+    Path,
+    Ty,
+    Pat,
+    PatParam,
+    Stmt,
+    Block,
+    Meta,
+    Item,
+    Vis,
+    Expr,
+    Ident,
+    Tt,
+    Lifetime,
+    Literal,
+}
+
+impl From<mbe::MetaVarKind> for FragmentSpec {
+    fn from(kind: mbe::MetaVarKind) -> Self {
+        match kind {
+            mbe::MetaVarKind::Path => FragmentSpec::Path,
+            mbe::MetaVarKind::Ty => FragmentSpec::Ty,
+            mbe::MetaVarKind::Pat => FragmentSpec::Pat,
+            mbe::MetaVarKind::PatParam => FragmentSpec::PatParam,
+            mbe::MetaVarKind::Stmt => FragmentSpec::Stmt,
+            mbe::MetaVarKind::Block => FragmentSpec::Block,
+            mbe::MetaVarKind::Meta => FragmentSpec::Meta,
+            mbe::MetaVarKind::Item => FragmentSpec::Item,
+            mbe::MetaVarKind::Vis => FragmentSpec::Vis,
+            mbe::MetaVarKind::Expr => FragmentSpec::Expr,
+            mbe::MetaVarKind::Ident => FragmentSpec::Ident,
+            mbe::MetaVarKind::Tt => FragmentSpec::Tt,
+            mbe::MetaVarKind::Lifetime => FragmentSpec::Lifetime,
+            mbe::MetaVarKind::Literal => FragmentSpec::Literal,
+        }
+    }
+}
+
 intern::impl_internable!(ModPath, attrs::AttrInput);
+
+#[cfg(test)]
+mod tests {
+    use base_db::Edition;
+
+    use super::{db::expand_declarative_once, ExpandError};
+
+    #[test]
+    fn recoverable_errors() {
+        let recoverable = [
+            ExpandError::InvalidTokenTree,
+            ExpandError::MalformedInvocation,
+            mbe::ExpandError::NoMatchingRule.into(),
+            mbe::ExpandError::UnexpectedToken.into(),
+            mbe::ExpandError::LeftoverTokens.into(),
+            mbe::ExpandError::FailedRules {
+                best: 1,
+                errors: Box::new([
+                    mbe::ExpandError::BindingError(Box::new("expected ident".into())),
+                    mbe::ExpandError::UnexpectedToken,
+                ]),
+            }
+            .into(),
+        ];
+        for err in recoverable {
+            assert!(err.is_recoverable(), "{err}");
+        }
+
+        let persistent = [
+            ExpandError::RecursionOverflowPoisoned,
+            ExpandError::other("invalid macro definition: expected subtree"),
+            ExpandError::other("compile_error! invoked"),
+            ExpandError::ProcMacroPanic(Box::new("panicked".into())),
+            mbe::ExpandError::ConversionError.into(),
+            mbe::ExpandError::LimitExceeded.into(),
+            mbe::ExpandError::CountError(mbe::CountError::OutOfBounds).into(),
+            mbe::ExpandError::FailedRules {
+                best: 0,
+                errors: Box::new([mbe::ExpandError::CountError(mbe::CountError::Misplaced)]),
+            }
+            .into(),
+        ];
+        for err in persistent {
+            assert!(!err.is_recoverable(), "{err}");
+        }
+    }
+
+    #[test]
+    fn combine_errors() {
+        let unresolved = ExpandError::other("unresolved macro unresolved");
+        let no_rule: ExpandError = mbe::ExpandError::NoMatchingRule.into();
+        assert_eq!(unresolved.clone().combine(unresolved.clone()), unresolved);
+
+        let combined = unresolved.clone().combine(no_rule.clone());
+        assert_eq!(combined.errors(), [unresolved.clone(), no_rule.clone()]);
+        assert_eq!(
+            combined.to_string(),
+            "unresolved macro unresolved; no rule matches input tokens"
+        );
+        assert!(!combined.is_recoverable());
+
+        let nested = combined.clone().combine(ExpandError::MalformedInvocation.combine(no_rule));
+        assert_eq!(
+            nested.errors(),
+            [unresolved, mbe::ExpandError::NoMatchingRule.into(), ExpandError::MalformedInvocation]
+        );
+    }
+
+    #[test]
+    fn expand_declarative_without_db() {
+        let def = r#"
+macro_rules! pair {
+    ($a:expr, $b:expr) => { ($b, $a) };
+}
+"#;
+        let expansion = expand_declarative_once(def, "pair!(1, 2 + 3)", Edition::Edition2021);
+        assert_eq!(expansion.unwrap().to_string(), "((2 + 3) , 1)");
+
+        let err = expand_declarative_once(def, "pair!(1)", Edition::Edition2021).unwrap_err();
+        assert!(err.is_recoverable(), "{err}");
+
+        let err = expand_declarative_once("fn f() {}", "pair!(1, 2)", Edition::Edition2021);
+        assert_eq!(err, Err(ExpandError::other("expected a macro definition")));
+        let err = expand_declarative_once("macro_rules! m { () }", "m!()", Edition::Edition2021);
+        assert!(matches!(err, Err(ExpandError::Other(_))));
+        let err = expand_declarative_once(def, "pair", Edition::Edition2021);
+        assert_eq!(err, Err(ExpandError::MalformedInvocation));
+    }
+}
@@ -1,13 +1,18 @@
 //! Proc Macro Expander stub
 
 use core::fmt;
-use std::{panic::RefUnwindSafe, sync};
+use std::{
+    hash::{Hash, Hasher},
+    panic::RefUnwindSafe,
+    sync::{self, Mutex},
+};
 
 use base_db::{CrateId, Env};
-use rustc_hash::FxHashMap;
+use rustc_hash::{FxHashMap, FxHasher};
 use span::Span;
 use stdx::never;
 use syntax::SmolStr;
+use triomphe::Arc;
 
 use crate::{db::ExpandDatabase, tt, ExpandError, ExpandResult};
 
@@ -51,6 +56,62 @@ pub struct ProcMacro {
     pub expander: sync::Arc<dyn ProcMacroExpander>,
 }
 
+/// Shares the allocations of identical proc-macro expansions. Most proc-macros are deterministic,
+/// so when an expansion is recomputed, for example because the token limit of its crate changed,
+/// expanding the same input usually produces the same output. An expansion is only shared if it
+/// is equal to the stored one, so this is invisible to salsa.
+///
+/// The input and the expansion are compared including their spans. Spans are anchored at the
+/// call site, so expansions of different call sites are never shared, even if their tokens are.
+///
+/// The expansions are kept alive by the queries that produced them. Expansions no longer used
+/// by any query are evicted whenever the table would have to grow.
+#[derive(Debug, Default)]
+pub struct ProcMacroExpansionDedup {
+    expansions: Mutex<FxHashMap<u64, Arc<tt::Subtree>>>,
+}
+
+impl ProcMacroExpansionDedup {
+    /// Returns the stored expansion of the input if it is equal to `expansion`, and stores
+    /// `expansion` otherwise.
+    pub(crate) fn intern(
+        &self,
+        expander: CustomProcMacroExpander,
+        def_crate: CrateId,
+        input: &tt::Subtree,
+        attr_input: Option<&tt::Subtree>,
+        expansion: tt::Subtree,
+    ) -> Arc<tt::Subtree> {
+        let mut hasher = FxHasher::default();
+        (expander, def_crate, input, attr_input).hash(&mut hasher);
+        let key = hasher.finish();
+        let mut expansions = self.expansions.lock().unwrap();
+        if let Some(existing) = expansions.get(&key).filter(|it| ***it == expansion) {
+            return existing.clone();
+        }
+        if expansions.len() == expansions.capacity() {
+            expansions.retain(|_, it| !it.is_unique());
+        }
+        let expansion = Arc::new(expansion);
+        expansions.insert(key, expansion.clone());
+        expansion
+    }
+
+    /// Returns the number of stored expansions that are still in use.
+    pub fn len(&self) -> usize {
+        self.expansions.lock().unwrap().values().filter(|it| !it.is_unique()).count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Forgets all stored expansions.
+    pub fn clear(&self) {
+        self.expansions.lock().unwrap().clear();
+    }
+}
+
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
 pub struct CustomProcMacroExpander {
     proc_macro_id: ProcMacroId,
@@ -72,6 +133,27 @@ pub fn is_dummy(&self) -> bool {
         self.proc_macro_id.0 == DUMMY_ID
     }
 
+    /// The name of the proc-macro, `None` if it can't be found in the proc-macros of `def_crate`.
+    pub fn name(self, db: &dyn ExpandDatabase, def_crate: CrateId) -> Option<SmolStr> {
+        match db.proc_macros().get(&def_crate) {
+            Some(Ok(proc_macros)) => {
+                proc_macros.get(self.proc_macro_id.0 as usize).map(|it| it.name.clone())
+            }
+            Some(Err(_)) | None => None,
+        }
+    }
+
+    /// The kind the proc-macro was registered as by the proc-macro server, `None` if it can't be
+    /// found in the proc-macros of `def_crate`.
+    pub fn kind(self, db: &dyn ExpandDatabase, def_crate: CrateId) -> Option<ProcMacroKind> {
+        match db.proc_macros().get(&def_crate) {
+            Some(Ok(proc_macros)) => {
+                proc_macros.get(self.proc_macro_id.0 as usize).map(|it| it.kind)
+            }
+            Some(Err(_)) | None => None,
+        }
+    }
+
     pub fn expand(
         self,
         db: &dyn ExpandDatabase,
@@ -139,3 +221,54 @@ pub fn expand(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use base_db::{CrateId, FileId};
+    use la_arena::RawIdx;
+    use span::{SpanAnchor, SyntaxContextId, ROOT_ERASED_FILE_AST_ID};
+    use syntax::{TextRange, TextSize};
+    use triomphe::Arc;
+
+    use super::{CustomProcMacroExpander, ProcMacroExpansionDedup, ProcMacroId};
+    use crate::{quote, tt};
+
+    const DUMMY: tt::Span = tt::Span {
+        range: TextRange::empty(TextSize::new(0)),
+        anchor: SpanAnchor { file_id: FileId::BOGUS, ast_id: ROOT_ERASED_FILE_AST_ID },
+        ctx: SyntaxContextId::ROOT,
+    };
+
+    #[test]
+    fn dedup_shares_identical_expansions() {
+        let dedup = ProcMacroExpansionDedup::default();
+        let krate = CrateId::from_raw(RawIdx::from(0));
+        let (first, second) = (
+            CustomProcMacroExpander::new(ProcMacroId(0)),
+            CustomProcMacroExpander::new(ProcMacroId(1)),
+        );
+        let input = quote!(DUMMY => fn foo() {});
+        let expansion = quote!(DUMMY => fn bar() {});
+
+        let it = dedup.intern(first, krate, &input, None, expansion.clone());
+        let again = dedup.intern(first, krate, &input, None, expansion.clone());
+        assert!(Arc::ptr_eq(&it, &again));
+        let other_expander = dedup.intern(second, krate, &input, None, expansion.clone());
+        assert!(!Arc::ptr_eq(&it, &other_expander));
+        let changed = dedup.intern(first, krate, &input, None, quote!(DUMMY => fn baz() {}));
+        assert_ne!(it, changed);
+        assert_eq!(dedup.len(), 2);
+
+        drop((it, again, other_expander, changed));
+        assert!(dedup.is_empty());
+        // Unused expansions are evicted once the table has to grow.
+        let kept = (0..64)
+            .map(|i| {
+                let input = quote!(DUMMY => #i);
+                dedup.intern(first, krate, &input, None, expansion.clone())
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(dedup.len(), kept.len());
+        assert_eq!(dedup.expansions.lock().unwrap().len(), kept.len());
+    }
+}
@@ -4,9 +4,9 @@
 //!
 //! But we need this for at least LRU caching at the query level.
 pub use hir_def::db::{
-    AttrsQuery, BlockDefMapQuery, BlockItemTreeQueryQuery, BodyQuery, BodyWithSourceMapQuery,
-    ConstDataQuery, ConstVisibilityQuery, CrateDefMapQueryQuery, CrateLangItemsQuery,
-    CrateSupportsNoStdQuery, DefDatabase, DefDatabaseStorage, EnumDataQuery,
+    AllMacroDefsInCrateQuery, AttrsQuery, BlockDefMapQuery, BlockItemTreeQueryQuery, BodyQuery,
+    BodyWithSourceMapQuery, ConstDataQuery, ConstVisibilityQuery, CrateDefMapQueryQuery,
+    CrateLangItemsQuery, CrateSupportsNoStdQuery, DefDatabase, DefDatabaseStorage, EnumDataQuery,
     EnumVariantDataWithDiagnosticsQuery, ExprScopesQuery, ExternCrateDeclDataQuery,
     FieldVisibilitiesQuery, FieldsAttrsQuery, FieldsAttrsSourceMapQuery, FileItemTreeQuery,
     FunctionDataQuery, FunctionVisibilityQuery, GenericParamsQuery, ImplDataWithDiagnosticsQuery,
@@ -15,13 +15,22 @@
     InternFunctionQuery, InternImplQuery, InternInTypeConstQuery, InternMacro2Query,
     InternMacroRulesQuery, InternProcMacroQuery, InternStaticQuery, InternStructQuery,
     InternTraitAliasQuery, InternTraitQuery, InternTypeAliasQuery, InternUnionQuery,
-    InternUseQuery, LangItemQuery, Macro2DataQuery, MacroRulesDataQuery, ProcMacroDataQuery,
-    StaticDataQuery, StructDataWithDiagnosticsQuery, TraitAliasDataQuery,
-    TraitDataWithDiagnosticsQuery, TypeAliasDataQuery, UnionDataWithDiagnosticsQuery,
+    InternUseQuery, LangItemQuery, Macro2DataQuery, MacroExpandToDepthQuery, MacroRulesDataQuery,
+    NestedMacroCallsQuery, ProcMacroDataQuery, StaticDataQuery, StructDataWithDiagnosticsQuery,
+    TraitAliasDataQuery, TraitDataWithDiagnosticsQuery, TypeAliasDataQuery,
+    UnionDataWithDiagnosticsQuery,
 };
 pub use hir_expand::db::{
-    AstIdMapQuery, DeclMacroExpanderQuery, ExpandDatabase, ExpandDatabaseStorage,
-    ExpandProcMacroQuery, InternMacroCallQuery, InternSyntaxContextQuery, MacroArgQuery,
-    ParseMacroExpansionErrorQuery, ParseMacroExpansionQuery, ProcMacrosQuery, RealSpanMapQuery,
+    AstIdMapQuery, DeclMacroExpanderQuery, DoctestFilesQuery, ExpandAndCollectDiagnosticsQuery,
+    ExpandDatabase, ExpandDatabaseStorage, ExpandDeclarativeQuery, ExpandProcMacroQuery,
+    ExpandedFnSignaturesQuery, ExpansionSpansCoverQuery, FnLikeMacroPathQuery,
+    IdentityAttrMacroDetectionQuery, InternMacroCallQuery, InternSyntaxContextQuery,
+    IsIdentityAttrMacroQuery, IsNoopExpansionQuery, MacroArgQuery, MacroArgWithFixupsQuery,
+    MacroCallDelimiterQuery, MacroDefBodyTtQuery, MacroExpandToItemsParsedQuery,
+    MacroExpandTokenCountQuery, MacroExpansionDiagnosticsQuery, MacroExpansionHashQuery,
+    MacroRecursionLimitsQuery, MacroTokenLimitsQuery, ParseMacroExpansionErrorQuery,
+    ParseMacroExpansionQuery, ProcMacroExpansionDedupQuery, ProcMacroGenerationQuery,
+    ProcMacrosQuery, RealSpanMapQuery, StringifyIdentMapQuery, SynthesizedTokenRangesQuery,
+    TokenLimitExemptMacrosQuery, TokenLimitMultipliersQuery,
 };
 pub use hir_ty::db::*;
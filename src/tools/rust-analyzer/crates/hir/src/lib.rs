@@ -126,6 +126,7 @@
         attrs::{Attr, AttrId},
         change::Change,
         hygiene::{marks_rev, SyntaxContextExt},
+        insert_whitespace_into_node::insert_ws_into,
         name::{known, Name},
         proc_macro::ProcMacros,
         tt, ExpandResult, HirFileId, HirFileIdExt, InFile, InMacroFile, InRealFile, MacroFileId,
@@ -170,6 +170,9 @@ macro_rules! purge_each_query {
             hir::db::InternExternCrateQuery
             hir::db::InternInTypeConstQuery
             hir::db::InternUseQuery
+            hir::db::AllMacroDefsInCrateQuery
+            hir::db::NestedMacroCallsQuery
+            hir::db::MacroExpandToDepthQuery
 
             // InternDatabase
             hir::db::InternFunctionQuery
@@ -191,13 +194,37 @@ macro_rules! purge_each_query {
             // ExpandDatabase
             hir::db::AstIdMapQuery
             hir::db::DeclMacroExpanderQuery
+            hir::db::ExpandDeclarativeQuery
             hir::db::ExpandProcMacroQuery
             hir::db::InternMacroCallQuery
             hir::db::InternSyntaxContextQuery
             hir::db::MacroArgQuery
+            hir::db::MacroArgWithFixupsQuery
             hir::db::ParseMacroExpansionQuery
+            hir::db::MacroExpandTokenCountQuery
+            hir::db::ExpansionSpansCoverQuery
+            hir::db::FnLikeMacroPathQuery
+            hir::db::MacroExpandToItemsParsedQuery
+            hir::db::MacroExpansionDiagnosticsQuery
+            hir::db::MacroExpansionHashQuery
             hir::db::RealSpanMapQuery
             hir::db::ProcMacrosQuery
+            hir::db::TokenLimitExemptMacrosQuery
+            hir::db::TokenLimitMultipliersQuery
+            hir::db::MacroTokenLimitsQuery
+            hir::db::MacroRecursionLimitsQuery
+            hir::db::DoctestFilesQuery
+            hir::db::ProcMacroGenerationQuery
+            hir::db::ProcMacroExpansionDedupQuery
+            hir::db::IdentityAttrMacroDetectionQuery
+            hir::db::MacroCallDelimiterQuery
+            hir::db::StringifyIdentMapQuery
+            hir::db::IsNoopExpansionQuery
+            hir::db::SynthesizedTokenRangesQuery
+            hir::db::ExpandedFnSignaturesQuery
+            hir::db::ExpandAndCollectDiagnosticsQuery
+            hir::db::MacroDefBodyTtQuery
+            hir::db::IsIdentityAttrMacroQuery
 
             // LineIndexDatabase
             crate::LineIndexQuery
@@ -142,6 +142,14 @@ pub fn new(lru_capacity: Option<usize>) -> RootDatabase {
         let mut db = RootDatabase { storage: ManuallyDrop::new(salsa::Storage::default()) };
         db.set_crate_graph_with_durability(Default::default(), Durability::HIGH);
         db.set_proc_macros_with_durability(Default::default(), Durability::HIGH);
+        db.set_token_limit_exempt_macros_with_durability(Default::default(), Durability::HIGH);
+        db.set_token_limit_multipliers_with_durability(Default::default(), Durability::HIGH);
+        db.set_macro_token_limits_with_durability(Default::default(), Durability::HIGH);
+        db.set_macro_recursion_limits_with_durability(Default::default(), Durability::HIGH);
+        db.set_doctest_files_with_durability(Default::default(), Durability::HIGH);
+        db.set_proc_macro_generation_with_durability(0, Durability::HIGH);
+        db.set_proc_macro_expansion_dedup_with_durability(None, Durability::HIGH);
+        db.set_identity_attr_macro_detection_with_durability(false, Durability::HIGH);
         db.set_local_roots_with_durability(Default::default(), Durability::HIGH);
         db.set_library_roots_with_durability(Default::default(), Durability::HIGH);
         db.set_expand_proc_attr_macros_with_durability(false, Durability::HIGH);
@@ -159,6 +167,8 @@ pub fn update_base_query_lru_capacities(&mut self, lru_capacity: Option<usize>)
         base_db::ParseQuery.in_db_mut(self).set_lru_capacity(lru_capacity);
         // macro expansions are usually rather small, so we can afford to keep more of them alive
         hir::db::ParseMacroExpansionQuery.in_db_mut(self).set_lru_capacity(4 * lru_capacity);
+        hir::db::MacroExpandToDepthQuery.in_db_mut(self).set_lru_capacity(lru_capacity);
+        hir::db::ExpandDeclarativeQuery.in_db_mut(self).set_lru_capacity(4 * lru_capacity);
         hir::db::BorrowckQuery.in_db_mut(self).set_lru_capacity(base_db::DEFAULT_BORROWCK_LRU_CAP);
     }
 
@@ -177,6 +187,18 @@ pub fn update_lru_capacities(&mut self, lru_capacities: &FxHashMap<Box<str>, usi
                 .copied()
                 .unwrap_or(4 * base_db::DEFAULT_PARSE_LRU_CAP),
         );
+        hir_db::MacroExpandToDepthQuery.in_db_mut(self).set_lru_capacity(
+            lru_capacities
+                .get(stringify!(MacroExpandToDepthQuery))
+                .copied()
+                .unwrap_or(base_db::DEFAULT_PARSE_LRU_CAP),
+        );
+        hir_db::ExpandDeclarativeQuery.in_db_mut(self).set_lru_capacity(
+            lru_capacities
+                .get(stringify!(ExpandDeclarativeQuery))
+                .copied()
+                .unwrap_or(4 * base_db::DEFAULT_PARSE_LRU_CAP),
+        );
         hir_db::BorrowckQuery.in_db_mut(self).set_lru_capacity(
             lru_capacities
                 .get(stringify!(BorrowckQuery))
@@ -11,6 +11,9 @@
 
 use crate::{parser::MetaVarKind, ExpandError, ExpandResult};
 
+/// Expands `input` with the first rule matching it without errors, returning the index of the rule
+/// along with the expansion. If there is no such rule, the expansion is transcribed from the rule
+/// matching the most tokens, for error recovery, and the error holds the errors of all rules.
 pub(crate) fn expand_rules<S: Span>(
     rules: &[crate::Rule<S>],
     input: &tt::Subtree<S>,
@@ -18,24 +21,29 @@ pub(crate) fn expand_rules<S: Span>(
     is_2021: bool,
     new_meta_vars: bool,
     call_site: S,
-) -> ExpandResult<tt::Subtree<S>> {
-    let mut match_: Option<(matcher::Match<S>, &crate::Rule<S>)> = None;
-    for rule in rules {
+) -> ExpandResult<(tt::Subtree<S>, Option<usize>)> {
+    let mut match_: Option<(matcher::Match<S>, usize)> = None;
+    let mut errors = Vec::with_capacity(rules.len());
+    for (idx, rule) in rules.iter().enumerate() {
         let new_match = matcher::match_(&rule.lhs, input, is_2021);
 
-        if new_match.err.is_none() {
-            // If we find a rule that applies without errors, we're done.
-            // Unconditionally returning the transcription here makes the
-            // `test_repeat_bad_var` test fail.
-            let ExpandResult { value, err: transcribe_err } = transcriber::transcribe(
-                &rule.rhs,
-                &new_match.bindings,
-                marker,
-                new_meta_vars,
-                call_site,
-            );
-            if transcribe_err.is_none() {
-                return ExpandResult::ok(value);
+        match &new_match.err {
+            Some(err) => errors.push(err.clone()),
+            None => {
+                // If we find a rule that applies without errors, we're done.
+                // Unconditionally returning the transcription here makes the
+                // `test_repeat_bad_var` test fail.
+                let ExpandResult { value, err: transcribe_err } = transcriber::transcribe(
+                    &rule.rhs,
+                    &new_match.bindings,
+                    marker,
+                    new_meta_vars,
+                    call_site,
+                );
+                match transcribe_err {
+                    Some(err) => errors.push(err),
+                    None => return ExpandResult::ok((value, Some(idx))),
+                }
             }
         }
         // Use the rule if we matched more tokens, or bound variables count
@@ -43,23 +51,36 @@ pub(crate) fn expand_rules<S: Span>(
             if (new_match.unmatched_tts, -(new_match.bound_count as i32))
                 < (prev_match.unmatched_tts, -(prev_match.bound_count as i32))
             {
-                match_ = Some((new_match, rule));
+                match_ = Some((new_match, idx));
             }
         } else {
-            match_ = Some((new_match, rule));
+            match_ = Some((new_match, idx));
         }
     }
-    if let Some((match_, rule)) = match_ {
-        // if we got here, there was no match without errors
-        let ExpandResult { value, err: transcribe_err } =
-            transcriber::transcribe(&rule.rhs, &match_.bindings, marker, new_meta_vars, call_site);
-        ExpandResult { value, err: match_.err.or(transcribe_err) }
+    if let Some((match_, idx)) = match_ {
+        // if we got here, there was no match without errors, and the error of the rule is
+        // already recorded
+        let value = transcriber::transcribe(
+            &rules[idx].rhs,
+            &match_.bindings,
+            marker,
+            new_meta_vars,
+            call_site,
+        )
+        .value;
+        ExpandResult::new(
+            (value, Some(idx)),
+            ExpandError::FailedRules { best: idx, errors: errors.into_boxed_slice() },
+        )
     } else {
         ExpandResult::new(
-            tt::Subtree {
-                delimiter: tt::Delimiter::invisible_spanned(call_site),
-                token_trees: vec![],
-            },
+            (
+                tt::Subtree {
+                    delimiter: tt::Delimiter::invisible_spanned(call_site),
+                    token_trees: vec![],
+                },
+                None,
+            ),
             ExpandError::NoMatchingRule,
         )
     }
@@ -23,7 +23,7 @@
 use std::fmt;
 
 use crate::{
-    parser::{MetaTemplate, MetaVarKind, Op},
+    parser::{MetaTemplate, Op},
     tt_iter::TtIter,
 };
 
@@ -31,6 +31,7 @@
 pub use ::parser::TopEntryPoint;
 pub use tt::{Delimiter, DelimiterKind, Punct};
 
+pub use crate::parser::MetaVarKind;
 pub use crate::syntax_bridge::{
     parse_exprs_with_sep, parse_to_token_tree, parse_to_token_tree_static_span,
     syntax_node_to_token_tree, syntax_node_to_token_tree_modified, token_tree_to_syntax_node,
@@ -76,6 +77,12 @@ pub enum ExpandError {
     ConversionError,
     LimitExceeded,
     NoMatchingRule,
+    /// No rule matched the input without errors. `errors` holds the error of each rule, in the
+    /// order of the rules, and `best` is the index of the rule the expansion was recovered with.
+    FailedRules {
+        best: usize,
+        errors: Box<[ExpandError]>,
+    },
     UnexpectedToken,
     CountError(CountError),
 }
@@ -86,12 +93,22 @@ impl ExpandError {
     fn binding_error(e: impl Into<Box<str>>) -> ExpandError {
         ExpandError::BindingError(Box::new(e.into()))
     }
+
+    /// Returns the error of the rule the expansion was recovered with if no rule matched, the
+    /// error itself otherwise.
+    pub fn best_rule_error(&self) -> &ExpandError {
+        match self {
+            ExpandError::FailedRules { best, errors } => &errors[*best],
+            _ => self,
+        }
+    }
 }
 
 impl fmt::Display for ExpandError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             ExpandError::NoMatchingRule => f.write_str("no rule matches input tokens"),
+            ExpandError::FailedRules { .. } => self.best_rule_error().fmt(f),
             ExpandError::UnexpectedToken => f.write_str("unexpected token in input"),
             ExpandError::BindingError(e) => f.write_str(e),
             ExpandError::UnresolvedBinding(binding) => {
@@ -247,6 +264,32 @@ pub fn err(&self) -> Option<&ParseError> {
         self.err.as_deref()
     }
 
+    /// Returns the distinct fragment specifiers of the meta variables in the matchers of all
+    /// rules, in the order they first appear.
+    pub fn fragment_specs(&self) -> Vec<MetaVarKind> {
+        fn collect<S: Span>(template: &MetaTemplate<S>, specs: &mut Vec<MetaVarKind>) {
+            for op in template.iter() {
+                match op {
+                    Op::Var { kind: Some(kind), .. } => {
+                        if !specs.contains(kind) {
+                            specs.push(*kind);
+                        }
+                    }
+                    Op::Repeat { tokens, .. } | Op::Subtree { tokens, .. } => {
+                        collect(tokens, specs)
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        let mut specs = Vec::new();
+        for rule in self.rules.iter() {
+            collect(&rule.lhs, &mut specs);
+        }
+        specs
+    }
+
     pub fn expand(
         &self,
         tt: &tt::Subtree<S>,
@@ -254,6 +297,19 @@ pub fn expand(
         new_meta_vars: bool,
         call_site: S,
     ) -> ExpandResult<tt::Subtree<S>> {
+        self.expand_with_rule_index(tt, marker, new_meta_vars, call_site).map(|(tt, _)| tt)
+    }
+
+    /// Like `expand`, but also returns the zero-based index of the rule the expansion was
+    /// transcribed from. If no rule matches without errors that is the rule used for error
+    /// recovery, and `None` if the macro has no rules.
+    pub fn expand_with_rule_index(
+        &self,
+        tt: &tt::Subtree<S>,
+        marker: impl Fn(&mut S) + Copy,
+        new_meta_vars: bool,
+        call_site: S,
+    ) -> ExpandResult<(tt::Subtree<S>, Option<usize>)> {
         expander::expand_rules(&self.rules, tt, marker, self.is_2021, new_meta_vars, call_site)
     }
 }
@@ -342,6 +398,26 @@ pub fn map_err<E2>(self, f: impl FnOnce(E) -> E2) -> ValueResult<T, E2> {
     pub fn result(self) -> Result<T, E> {
         self.err.map_or(Ok(self.value), Err)
     }
+
+    pub fn value_ref(&self) -> &T {
+        &self.value
+    }
+
+    /// Returns the value if there is no error.
+    pub fn ok_value(self) -> Option<T> {
+        match self.err {
+            Some(_) => None,
+            None => Some(self.value),
+        }
+    }
+
+    pub fn into_tuple(self) -> (T, Option<E>) {
+        (self.value, self.err)
+    }
+
+    pub fn from_tuple((value, err): (T, Option<E>)) -> Self {
+        Self { value, err }
+    }
 }
 
 impl<T: Default, E> From<Result<T, E>> for ValueResult<T, E> {
@@ -349,3 +425,29 @@ fn from(result: Result<T, E>) -> Self {
         result.map_or_else(Self::only_err, Self::ok)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::ValueResult;
+
+    #[test]
+    fn value_ref() {
+        assert_eq!(*ValueResult::<_, ()>::ok(1).value_ref(), 1);
+        assert_eq!(*ValueResult::new(2, ()).value_ref(), 2);
+    }
+
+    #[test]
+    fn ok_value() {
+        assert_eq!(ValueResult::<_, ()>::ok(1).ok_value(), Some(1));
+        assert_eq!(ValueResult::new(2, ()).ok_value(), None);
+    }
+
+    #[test]
+    fn tuple_round_trip() {
+        assert_eq!(ValueResult::<_, ()>::ok(1).into_tuple(), (1, None));
+        assert_eq!(ValueResult::new(2, 'e').into_tuple(), (2, Some('e')));
+        let ValueResult { value, err } = ValueResult::from_tuple((3, Some('e')));
+        assert_eq!((value, err), (3, Some('e')));
+        assert_eq!(ValueResult::from_tuple((4, None::<()>)).into_tuple(), (4, None));
+    }
+}
@@ -95,8 +95,9 @@ pub(crate) enum RepeatKind {
     ZeroOrOne,
 }
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq)]
-pub(crate) enum MetaVarKind {
+/// The fragment specifier of a meta variable in a matcher, like `expr` in `$e:expr`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum MetaVarKind {
     Path,
     Ty,
     Pat,
@@ -355,6 +355,13 @@ pub fn parse_with_proc_macros(
             change: Change {
                 source_change,
                 proc_macros: proc_macros.is_empty().not().then_some(proc_macros),
+                token_limit_exempt_macros: None,
+                token_limit_multipliers: None,
+                macro_token_limits: None,
+                macro_recursion_limits: None,
+                doctest_files: None,
+                dedup_proc_macro_expansions: None,
+                detect_identity_attr_macros: None,
             },
         }
     }
@@ -77,7 +77,18 @@ pub const fn empty(span: DelimSpan<S>) -> Self {
         Subtree { delimiter: Delimiter::invisible_delim_spanned(span), token_trees: vec![] }
     }
 
+    #[deprecated(note = "use `map_spans` instead")]
     pub fn visit_ids(&mut self, f: &mut impl FnMut(S) -> S) {
+        self.map_spans(f)
+    }
+
+    /// Rewrites the span of every leaf and delimiter in this subtree with `f`, for example to
+    /// relocate the tokens to another file.
+    pub fn map_spans(&mut self, mut f: impl FnMut(S) -> S) {
+        self.map_spans_with(&mut f)
+    }
+
+    fn map_spans_with(&mut self, f: &mut impl FnMut(S) -> S) {
         self.delimiter.open = f(self.delimiter.open);
         self.delimiter.close = f(self.delimiter.close);
         self.token_trees.iter_mut().for_each(|tt| match tt {
@@ -86,7 +97,7 @@ pub fn visit_ids(&mut self, f: &mut impl FnMut(S) -> S) {
                 crate::Leaf::Punct(it) => it.span = f(it.span),
                 crate::Leaf::Ident(it) => it.span = f(it.span),
             },
-            crate::TokenTree::Subtree(s) => s.visit_ids(f),
+            crate::TokenTree::Subtree(s) => s.map_spans_with(f),
         })
     }
 }
@@ -303,6 +314,47 @@ pub fn count(&self) -> usize {
     }
 }
 
+impl<Ctx> Subtree<span::SpanData<Ctx>> {
+    /// Returns the leaf whose span covers `offset`. Span ranges are relative to their anchor, so
+    /// this is only meaningful if all leaves share the same anchor.
+    ///
+    /// If `offset` does not fall into any leaf, for example because it is in whitespace between
+    /// tokens or on a delimiter, the leaf starting closest after `offset` is returned instead.
+    /// Returns `None` if there is no such leaf either.
+    pub fn token_at_offset(&self, offset: TextSize) -> Option<&Leaf<span::SpanData<Ctx>>> {
+        fn covering<'a, Ctx>(
+            subtree: &'a Subtree<span::SpanData<Ctx>>,
+            offset: TextSize,
+            following: &mut Option<&'a Leaf<span::SpanData<Ctx>>>,
+        ) -> Option<&'a Leaf<span::SpanData<Ctx>>> {
+            for tt in &subtree.token_trees {
+                match tt {
+                    TokenTree::Leaf(leaf) => {
+                        let range = leaf.span().range;
+                        if range.contains(offset) {
+                            return Some(leaf);
+                        }
+                        if range.start() > offset
+                            && following.map_or(true, |it| range.start() < it.span().range.start())
+                        {
+                            *following = Some(leaf);
+                        }
+                    }
+                    TokenTree::Subtree(subtree) => {
+                        if let Some(leaf) = covering(subtree, offset, following) {
+                            return Some(leaf);
+                        }
+                    }
+                }
+            }
+            None
+        }
+
+        let mut following = None;
+        covering(self, offset, &mut following).or(following)
+    }
+}
+
 impl<S> Subtree<S> {
     /// A simple line string used for debugging
     pub fn as_debug_string(&self) -> String {
@@ -352,34 +404,147 @@ pub fn as_debug_string(&self) -> String {
 pub mod buffer;
 
 pub fn pretty<S>(tkns: &[TokenTree<S>]) -> String {
-    fn tokentree_to_text<S>(tkn: &TokenTree<S>) -> String {
+    let mut buf = String::new();
+    pretty_into(tkns, &mut buf, &mut |_, _| ());
+    buf
+}
+
+/// Like [`pretty`], but additionally returns the range every identifier occupies in the resulting
+/// string together with the span of that identifier.
+pub fn pretty_with_ident_ranges<S: Copy>(tkns: &[TokenTree<S>]) -> (String, Vec<(TextRange, S)>) {
+    let mut buf = String::new();
+    let mut ranges = Vec::new();
+    pretty_into(tkns, &mut buf, &mut |range, span: &S| ranges.push((range, *span)));
+    (buf, ranges)
+}
+
+fn pretty_into<S>(
+    tkns: &[TokenTree<S>],
+    buf: &mut String,
+    on_ident: &mut dyn FnMut(TextRange, &S),
+) {
+    let mut last_to_joint = true;
+    for tkn in tkns {
+        if !last_to_joint {
+            buf.push(' ');
+        }
+        last_to_joint = false;
         match tkn {
-            TokenTree::Leaf(Leaf::Ident(ident)) => ident.text.clone().into(),
-            TokenTree::Leaf(Leaf::Literal(literal)) => literal.text.clone().into(),
-            TokenTree::Leaf(Leaf::Punct(punct)) => format!("{}", punct.char),
+            TokenTree::Leaf(Leaf::Ident(ident)) => {
+                let start = TextSize::of(buf.as_str());
+                buf.push_str(&ident.text);
+                on_ident(TextRange::at(start, TextSize::of(ident.text.as_str())), &ident.span);
+            }
+            TokenTree::Leaf(Leaf::Literal(literal)) => buf.push_str(&literal.text),
+            TokenTree::Leaf(Leaf::Punct(punct)) => {
+                buf.push(punct.char);
+                last_to_joint = punct.spacing == Spacing::Joint;
+            }
             TokenTree::Subtree(subtree) => {
-                let content = pretty(&subtree.token_trees);
                 let (open, close) = match subtree.delimiter.kind {
                     DelimiterKind::Brace => ("{", "}"),
                     DelimiterKind::Bracket => ("[", "]"),
                     DelimiterKind::Parenthesis => ("(", ")"),
                     DelimiterKind::Invisible => ("", ""),
                 };
-                format!("{open}{content}{close}")
+                buf.push_str(open);
+                pretty_into(&subtree.token_trees, buf, on_ident);
+                buf.push_str(close);
             }
         }
     }
+}
 
-    tkns.iter()
-        .fold((String::new(), true), |(last, last_to_joint), tkn| {
-            let s = [last, tokentree_to_text(tkn)].join(if last_to_joint { "" } else { " " });
-            let mut is_joint = false;
-            if let TokenTree::Leaf(Leaf::Punct(punct)) = tkn {
-                if punct.spacing == Spacing::Joint {
-                    is_joint = true;
-                }
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Span = span::SpanData<()>;
+
+    fn span(range: std::ops::Range<u32>) -> Span {
+        Span {
+            range: TextRange::new(range.start.into(), range.end.into()),
+            anchor: span::SpanAnchor {
+                file_id: span::FileId::BOGUS,
+                ast_id: span::ROOT_ERASED_FILE_AST_ID,
+            },
+            ctx: (),
+        }
+    }
+
+    fn ident(text: &str, range: std::ops::Range<u32>) -> TokenTree<Span> {
+        Leaf::Ident(Ident::new(text, span(range))).into()
+    }
+
+    /// `foo (bar) 92`
+    fn subtree() -> Subtree<Span> {
+        let delimited = Subtree {
+            delimiter: Delimiter {
+                open: span(4..5),
+                close: span(8..9),
+                kind: DelimiterKind::Parenthesis,
+            },
+            token_trees: vec![ident("bar", 5..8)],
+        };
+        let literal = Leaf::Literal(Literal { text: "92".into(), span: span(10..12) });
+        Subtree {
+            delimiter: Delimiter::invisible_spanned(span(0..12)),
+            token_trees: vec![ident("foo", 0..3), delimited.into(), literal.into()],
+        }
+    }
+
+    fn spans(subtree: &Subtree<Span>, acc: &mut Vec<Span>) {
+        acc.push(subtree.delimiter.open);
+        for tt in &subtree.token_trees {
+            match tt {
+                TokenTree::Leaf(leaf) => acc.push(*leaf.span()),
+                TokenTree::Subtree(subtree) => spans(subtree, acc),
             }
-            (s, is_joint)
-        })
-        .0
+        }
+        acc.push(subtree.delimiter.close);
+    }
+
+    fn text_at(subtree: &Subtree<Span>, offset: u32) -> Option<String> {
+        subtree.token_at_offset(offset.into()).map(|leaf| leaf.to_string())
+    }
+
+    #[test]
+    fn token_at_offset_in_token() {
+        let subtree = subtree();
+        assert_eq!(text_at(&subtree, 0).as_deref(), Some("foo"));
+        assert_eq!(text_at(&subtree, 2).as_deref(), Some("foo"));
+        assert_eq!(text_at(&subtree, 6).as_deref(), Some("bar"));
+        assert_eq!(text_at(&subtree, 11).as_deref(), Some("92"));
+    }
+
+    #[test]
+    fn token_at_offset_between_tokens() {
+        let subtree = subtree();
+        // In whitespace and on delimiters, the next token is returned.
+        assert_eq!(text_at(&subtree, 3).as_deref(), Some("bar"));
+        assert_eq!(text_at(&subtree, 4).as_deref(), Some("bar"));
+        assert_eq!(text_at(&subtree, 8).as_deref(), Some("92"));
+        assert_eq!(text_at(&subtree, 12), None);
+    }
+
+    #[test]
+    fn map_spans_rewrites_file_ids() {
+        let original = subtree();
+        let file_id = span::FileId::from_raw(1);
+        let mut mapped = original.clone();
+        mapped
+            .map_spans(|span| Span { anchor: span::SpanAnchor { file_id, ..span.anchor }, ..span });
+
+        assert_eq!(mapped.to_string(), original.to_string());
+        assert_eq!(mapped.count(), original.count());
+        let (mut before, mut after) = (Vec::new(), Vec::new());
+        spans(&original, &mut before);
+        spans(&mapped, &mut after);
+        assert_eq!(before.len(), after.len());
+        for (before, after) in before.iter().zip(&after) {
+            assert_eq!(after.anchor.file_id, file_id);
+            assert_eq!(after.anchor.ast_id, before.anchor.ast_id);
+            assert_eq!(after.range, before.range);
+        }
+    }
 }